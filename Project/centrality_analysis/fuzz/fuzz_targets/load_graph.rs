@@ -0,0 +1,19 @@
+#![no_main]
+
+use centrality_analysis::load_graph_from_reader;
+use libfuzzer_sys::fuzz_target;
+use petgraph::visit::EdgeRef;
+
+fuzz_target!(|data: &[u8]| {
+    // Invalid UTF-8 is reported as an error; anything else loads without panicking.
+    let Ok(graph) = load_graph_from_reader(data) else {
+        return;
+    };
+    // Every edge needs a line of at least three bytes ("1 2"), so memory stays
+    // proportional to the input however large the ids are.
+    assert!(graph.edge_count() <= (data.len() + 1) / 4 + 1);
+    assert!(graph.node_count() <= 2 * graph.edge_count());
+    for edge in graph.edge_references() {
+        assert_ne!(graph[edge.source()], graph[edge.target()]);
+    }
+});
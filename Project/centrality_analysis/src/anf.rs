@@ -0,0 +1,209 @@
+use crate::AuthorGraph;
+use petgraph::visit::EdgeRef;
+use std::collections::VecDeque;
+
+/// Neighbourhood function: `pairs[t]` is the number of ordered author pairs
+/// (including each author with itself) at distance at most `t`.
+#[derive(Debug, Clone)]
+pub struct NeighbourhoodFunction {
+    pub pairs: Vec<f64>,
+}
+
+impl NeighbourhoodFunction {
+    /// Number of pairs at each exact distance.
+    pub fn distance_distribution(&self) -> Vec<f64> {
+        self.pairs
+            .iter()
+            .enumerate()
+            .map(|(t, &n)| if t == 0 { n } else { (n - self.pairs[t - 1]).max(0.0) })
+            .collect()
+    }
+
+    /// Distance within which `quantile` of the connected pairs lie, linearly
+    /// interpolated between integer distances (SNAP's effective diameter at 0.9).
+    pub fn effective_diameter(&self, quantile: f64) -> f64 {
+        let Some(&total) = self.pairs.last() else {
+            return 0.0;
+        };
+        let target = quantile * total;
+        for (t, &n) in self.pairs.iter().enumerate() {
+            if n >= target {
+                let previous = if t == 0 { 0.0 } else { self.pairs[t - 1] };
+                if n <= previous {
+                    return t as f64;
+                }
+                return t as f64 - 1.0 + (target - previous) / (n - previous);
+            }
+        }
+        (self.pairs.len() - 1) as f64
+    }
+
+    /// Mean distance over connected pairs of distinct authors.
+    pub fn average_distance(&self) -> f64 {
+        let distribution = self.distance_distribution();
+        let connected: f64 = distribution.iter().skip(1).sum();
+        if connected <= 0.0 {
+            return 0.0;
+        }
+        distribution.iter().enumerate().map(|(t, &n)| t as f64 * n).sum::<f64>() / connected
+    }
+}
+
+/// Exact neighbourhood function from a breadth-first search out of every author, in
+/// O(nodes * edges) time.
+pub fn exact_neighbourhood_function(graph: &AuthorGraph) -> NeighbourhoodFunction {
+    let mut counts: Vec<f64> = Vec::new();
+    let mut distance = vec![usize::MAX; graph.node_count()];
+    let mut queue = VecDeque::new();
+    for source in graph.node_indices() {
+        distance.iter_mut().for_each(|d| *d = usize::MAX);
+        distance[source.index()] = 0;
+        queue.push_back(source);
+        while let Some(node) = queue.pop_front() {
+            let d = distance[node.index()];
+            if counts.len() <= d {
+                counts.resize(d + 1, 0.0);
+            }
+            counts[d] += 1.0;
+            for next in graph.neighbors(node) {
+                if distance[next.index()] == usize::MAX {
+                    distance[next.index()] = d + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    let pairs = counts
+        .iter()
+        .scan(0.0, |total, &count| {
+            *total += count;
+            Some(*total)
+        })
+        .collect();
+    NeighbourhoodFunction { pairs }
+}
+
+/// HyperLogLog counter with `2^log2_registers` registers.
+#[derive(Clone)]
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new(log2_registers: u32) -> HyperLogLog {
+        HyperLogLog { registers: vec![0; 1 << log2_registers] }
+    }
+
+    fn insert(&mut self, hash: u64) {
+        let bits = self.registers.len().trailing_zeros();
+        let index = (hash >> (64 - bits)) as usize;
+        let rank = ((hash << bits).leading_zeros() + 1).min(64 - bits + 1) as u8;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// Register-wise maximum; returns whether anything changed.
+    fn union(&mut self, other: &HyperLogLog) -> bool {
+        let mut changed = false;
+        for (mine, &theirs) in self.registers.iter_mut().zip(&other.registers) {
+            if theirs > *mine {
+                *mine = theirs;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Cardinality estimate with the small-range (linear counting) correction.
+    fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter().map(|&r| (-(r as f64)).exp2()).sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+/// 64-bit finalizer of SplitMix64, used to hash node indices.
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Estimate the neighbourhood function with HyperANF (Boldi, Rosa & Vigna, 2011): each
+/// author keeps a HyperLogLog counter of the authors within distance `t`, and one pass
+/// over the edges per step takes unions with the neighbours' counters.
+///
+/// Memory is `2^log2_registers` bytes per author (4 to 16 is sensible); `seed` varies the
+/// hash function, so repeated runs give independent estimates.
+pub fn hyper_anf(graph: &AuthorGraph, log2_registers: u32, seed: u64) -> NeighbourhoodFunction {
+    let log2_registers = log2_registers.clamp(4, 16);
+    let mut counters: Vec<HyperLogLog> = graph
+        .node_indices()
+        .map(|node| {
+            let mut counter = HyperLogLog::new(log2_registers);
+            counter.insert(mix(node.index() as u64 ^ mix(seed)));
+            counter
+        })
+        .collect();
+    let mut pairs = vec![counters.iter().map(HyperLogLog::estimate).sum()];
+
+    loop {
+        let mut next = counters.clone();
+        let mut changed = false;
+        for edge in graph.edge_references() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            changed |= next[a].union(&counters[b]);
+            changed |= next[b].union(&counters[a]);
+        }
+        if !changed {
+            break;
+        }
+        counters = next;
+        // Estimates are noisy, so keep the function monotone.
+        let estimate: f64 = counters.iter().map(HyperLogLog::estimate).sum();
+        pairs.push(estimate.max(*pairs.last().unwrap()));
+    }
+    NeighbourhoodFunction { pairs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyper_anf_on_path() {
+        // Exact neighbourhood function of a 5-path: 5, 13, 19, 23, 25.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+
+        assert_eq!(exact_neighbourhood_function(&graph).pairs, vec![5.0, 13.0, 19.0, 23.0, 25.0]);
+        let function = hyper_anf(&graph, 10, 3);
+        assert_eq!(function.pairs.len(), 5);
+        for (estimate, exact) in function.pairs.iter().zip([5.0, 13.0, 19.0, 23.0, 25.0]) {
+            assert!((estimate - exact).abs() / exact < 0.05, "{} vs {}", estimate, exact);
+        }
+    }
+
+    #[test]
+    fn test_effective_diameter_interpolates() {
+        let function = NeighbourhoodFunction { pairs: vec![5.0, 13.0, 19.0, 23.0, 25.0] };
+        // 90% of 25 is 22.5, three quarters of the way from 19 (t = 2) to 23 (t = 3).
+        assert!((function.effective_diameter(0.9) - 2.875).abs() < 1e-12);
+        assert_eq!(function.distance_distribution(), vec![5.0, 8.0, 6.0, 4.0, 2.0]);
+        assert!((function.average_distance() - 2.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A preprocessing operation that changed the graph, and how many items it affected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub operation: String,
+    pub count: usize,
+}
+
+/// The operations applied to a graph between reading the input and analysing it, in the
+/// order they ran, so that results can be traced back to preprocessing decisions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuditLog {
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Record that `operation` affected `count` items, adding to an earlier entry of the
+    /// same operation. Operations that changed nothing are not recorded.
+    pub fn record(&mut self, operation: &str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        match self.entries.iter_mut().find(|entry| entry.operation == operation) {
+            Some(entry) => entry.count += count,
+            None => self.entries.push(AuditEntry { operation: operation.to_string(), count }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self.entries.iter().map(|entry| format!("{}: {}", entry.operation, entry.count)).collect();
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodelink::{graph_from_node_link, NodeLinkData};
+    use crate::{load_graph_from_reader_audited, LoadOptions};
+
+    #[test]
+    fn test_loaders_record_dropped_input() {
+        let mut log = AuditLog::default();
+        let input = "# comment\n1 2\n2 1\n3 3\n2 3\n1 2 3 4\n\n1 2\n";
+        let graph = load_graph_from_reader_audited(input.as_bytes(), &LoadOptions::default(), &mut log).unwrap();
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(log.to_string(), "repeated edges merged: 2, self-loops dropped: 1, malformed lines skipped: 1");
+
+        let json = r#"{"nodes": [{"id": 1}, {"id": 2}], "edges": [{"source": 1, "target": 2}, {"source": 2, "target": 2}]}"#;
+        let data: NodeLinkData = serde_json::from_str(json).unwrap();
+        graph_from_node_link(&data, &LoadOptions::default(), &mut log).unwrap();
+        assert_eq!(log.entries[1], AuditEntry { operation: "self-loops dropped".to_string(), count: 2 });
+        assert_eq!(serde_json::to_string(&AuditLog::default()).unwrap(), "[]");
+    }
+}
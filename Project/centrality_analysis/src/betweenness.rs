@@ -0,0 +1,771 @@
+use crate::signal::Signal;
+use crate::{AuthorGraph, EdgeWeights, WeightedAuthorGraph};
+use petgraph::algo::kosaraju_scc;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::str::FromStr;
+
+/// Shortest-path DAG of one breadth-first search, the building block of Brandes-style
+/// accumulation (Brandes, 2001).
+pub(crate) struct ShortestPathDag {
+    /// Reached nodes in non-decreasing distance from the source, the source first.
+    pub order: Vec<NodeIndex>,
+    /// Number of shortest paths from the source, indexed by `NodeIndex::index()`.
+    pub sigma: Vec<f64>,
+    /// Neighbours one step closer to the source on a shortest path.
+    pub predecessors: Vec<Vec<NodeIndex>>,
+}
+
+impl ShortestPathDag {
+    pub fn from_source(graph: &AuthorGraph, source: NodeIndex) -> ShortestPathDag {
+        let n = graph.node_count();
+        let mut distance = vec![usize::MAX; n];
+        let mut sigma = vec![0.0; n];
+        let mut predecessors = vec![Vec::new(); n];
+        let mut order = Vec::new();
+        distance[source.index()] = 0;
+        sigma[source.index()] = 1.0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in graph.neighbors(node) {
+                if distance[next.index()] == usize::MAX {
+                    distance[next.index()] = distance[node.index()] + 1;
+                    queue.push_back(next);
+                }
+                if distance[next.index()] == distance[node.index()] + 1 {
+                    sigma[next.index()] += sigma[node.index()];
+                    predecessors[next.index()].push(node);
+                }
+            }
+        }
+        ShortestPathDag { order, sigma, predecessors }
+    }
+
+    /// The DAG of shortest paths by total weight, found by Dijkstra's algorithm. Path
+    /// lengths within a relative 1e-12 of each other count as equal, so rounding does not
+    /// break ties between paths such as 0.1 + 0.2 and 0.3. `eccentricity` counts hops
+    /// along one of the paths and is meaningless here.
+    pub fn from_source_weighted(graph: &WeightedAuthorGraph, source: NodeIndex) -> ShortestPathDag {
+        let n = graph.node_count();
+        let mut distance = vec![f64::INFINITY; n];
+        let mut settled = vec![false; n];
+        let mut sigma = vec![0.0; n];
+        let mut predecessors = vec![Vec::new(); n];
+        let mut order = Vec::new();
+        distance[source.index()] = 0.0;
+        sigma[source.index()] = 1.0;
+        let mut heap = BinaryHeap::from([Tentative(0.0, source)]);
+        while let Some(Tentative(reached, node)) = heap.pop() {
+            if settled[node.index()] {
+                continue;
+            }
+            settled[node.index()] = true;
+            order.push(node);
+            for edge in graph.edges(node) {
+                let next = if edge.source() == node { edge.target() } else { edge.source() };
+                if settled[next.index()] {
+                    continue;
+                }
+                let candidate = reached + edge.weight();
+                let known = distance[next.index()];
+                if (candidate - known).abs() <= 1e-12 * candidate {
+                    sigma[next.index()] += sigma[node.index()];
+                    predecessors[next.index()].push(node);
+                } else if candidate < known {
+                    distance[next.index()] = candidate;
+                    sigma[next.index()] = sigma[node.index()];
+                    predecessors[next.index()] = vec![node];
+                    heap.push(Tentative(candidate, next));
+                }
+            }
+        }
+        ShortestPathDag { order, sigma, predecessors }
+    }
+
+    /// Distance from the source to the farthest node it reaches.
+    pub fn eccentricity(&self) -> usize {
+        let mut distance = 0;
+        let mut node = *self.order.last().expect("the source is always reached");
+        while let Some(&previous) = self.predecessors[node.index()].first() {
+            distance += 1;
+            node = previous;
+        }
+        distance
+    }
+
+    /// Brandes' dependencies of the source on every node: `delta[v]` is the sum over
+    /// targets `t` of the fraction of shortest source–t paths through `v`.
+    pub fn dependencies(&self) -> Vec<f64> {
+        self.dependencies_on(|_| true)
+    }
+
+    /// Dependencies counting only the targets for which `counted` holds.
+    pub fn dependencies_on(&self, counted: impl Fn(NodeIndex) -> bool) -> Vec<f64> {
+        let mut delta = vec![0.0; self.sigma.len()];
+        for &w in self.order.iter().rev() {
+            let target = if counted(w) { 1.0 } else { 0.0 };
+            let coefficient = (target + delta[w.index()]) / self.sigma[w.index()];
+            for &v in &self.predecessors[w.index()] {
+                delta[v.index()] += self.sigma[v.index()] * coefficient;
+            }
+        }
+        delta
+    }
+
+    /// Number of shortest paths from the source that continue through each node to a
+    /// target beyond it, given `sigma` paths into the node.
+    pub fn paths_through(&self) -> Vec<f64> {
+        let mut below = vec![0.0; self.sigma.len()];
+        for &w in self.order.iter().rev() {
+            let paths = 1.0 + below[w.index()];
+            for &v in &self.predecessors[w.index()] {
+                below[v.index()] += paths;
+            }
+        }
+        self.order.iter().skip(1).for_each(|&v| below[v.index()] *= self.sigma[v.index()]);
+        below[self.order[0].index()] = 0.0;
+        below
+    }
+
+    /// Newman's load: a unit of flow sent from the source to every node, split equally
+    /// among the predecessors at each step back, as the amount passing through each node.
+    pub fn load(&self) -> Vec<f64> {
+        let mut flow = vec![0.0; self.sigma.len()];
+        for &w in self.order.iter().skip(1).rev() {
+            let predecessors = &self.predecessors[w.index()];
+            let share = (1.0 + flow[w.index()]) / predecessors.len() as f64;
+            for &v in predecessors {
+                flow[v.index()] += share;
+            }
+        }
+        flow[self.order[0].index()] = 0.0;
+        flow
+    }
+}
+
+/// A node and its distance from the source, ordered so that the max-heap pops the closest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Tentative(pub f64, pub NodeIndex);
+
+impl Eq for Tentative {}
+
+impl Ord for Tentative {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+impl PartialOrd for Tentative {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Betweenness centrality by Brandes' algorithm: for every author, the number of pairs of
+/// other authors whose shortest paths pass through them, each pair weighted by the
+/// fraction of its shortest paths that do. Normalized scores divide by the number of
+/// such pairs, `(n-1)(n-2)/2`, so they lie in [0, 1] as in networkx.
+pub fn betweenness_centrality(graph: &AuthorGraph, normalized: bool) -> HashMap<usize, f64> {
+    let n = graph.node_count();
+    let mut centrality = vec![0.0; n];
+    for source in graph.node_indices() {
+        let dag = ShortestPathDag::from_source(graph, source);
+        let delta = dag.dependencies();
+        for &v in &dag.order[1..] {
+            centrality[v.index()] += delta[v.index()];
+        }
+    }
+    // Every pair is counted from both of its endpoints.
+    let scale = if normalized && n > 2 { 1.0 / ((n - 1) * (n - 2)) as f64 } else { 0.5 };
+    graph.node_indices().map(|node| (graph[node], centrality[node.index()] * scale)).collect()
+}
+
+/// Betweenness centrality over shortest paths by total length, the weights read as
+/// `weights` says, normalized as in [`betweenness_centrality`]. With
+/// `EdgeWeights::Distance` it matches networkx's `betweenness_centrality` with
+/// `weight="weight"`.
+pub fn weighted_betweenness_centrality(
+    graph: &WeightedAuthorGraph,
+    weights: EdgeWeights,
+    normalized: bool,
+) -> HashMap<usize, f64> {
+    let graph = &weights.lengths(graph);
+    let n = graph.node_count();
+    let mut centrality = vec![0.0; n];
+    for source in graph.node_indices() {
+        let dag = ShortestPathDag::from_source_weighted(graph, source);
+        let delta = dag.dependencies();
+        for &v in &dag.order[1..] {
+            centrality[v.index()] += delta[v.index()];
+        }
+    }
+    let scale = if normalized && n > 2 { 1.0 / ((n - 1) * (n - 2)) as f64 } else { 0.5 };
+    graph.node_indices().map(|node| (graph[node], centrality[node.index()] * scale)).collect()
+}
+
+/// Sampled betweenness with the number of samples it took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetweennessEstimate {
+    /// Estimates of the normalized betweenness of [`betweenness_centrality`].
+    pub scores: HashMap<usize, f64>,
+    pub samples: usize,
+    /// Samples after which the estimate is accurate enough whatever the data (the VC
+    /// bound); sampling stops earlier when the observed variance allows.
+    pub max_samples: usize,
+    /// Largest error bound over all authors when sampling stopped, at most `epsilon`.
+    pub error_bound: f64,
+}
+
+/// Edge betweenness: for every collaboration, keyed by its authors `(smaller, larger)`,
+/// the number of pairs of authors whose shortest paths use it, each pair weighted by the
+/// fraction of its shortest paths that do. Normalized scores divide by all `n(n-1)/2`
+/// pairs, as in networkx. High scores mark the collaborations bridging communities.
+pub fn edge_betweenness_centrality(graph: &AuthorGraph, normalized: bool) -> HashMap<(usize, usize), f64> {
+    let n = graph.node_count();
+    let key = |a: NodeIndex, b: NodeIndex| {
+        let (a, b) = (graph[a], graph[b]);
+        (a.min(b), a.max(b))
+    };
+    let mut centrality: HashMap<(usize, usize), f64> =
+        graph.edge_indices().filter_map(|edge| graph.edge_endpoints(edge)).map(|(a, b)| (key(a, b), 0.0)).collect();
+    for source in graph.node_indices() {
+        let dag = ShortestPathDag::from_source(graph, source);
+        let mut delta = vec![0.0; n];
+        for &w in dag.order.iter().rev() {
+            let coefficient = (1.0 + delta[w.index()]) / dag.sigma[w.index()];
+            for &v in &dag.predecessors[w.index()] {
+                let credit = dag.sigma[v.index()] * coefficient;
+                *centrality.entry(key(v, w)).or_insert(0.0) += credit;
+                delta[v.index()] += credit;
+            }
+        }
+    }
+    // Every pair is counted from both of its endpoints.
+    let scale = if normalized && n > 1 { 1.0 / (n * (n - 1)) as f64 } else { 0.5 };
+    centrality.values_mut().for_each(|score| *score *= scale);
+    centrality
+}
+
+/// An author of the top-k betweenness set, with bounds on its normalized score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BetweennessBounds {
+    pub author: usize,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// The exact top-k betweenness set and how much work it took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopBetweenness {
+    /// Best first by lower bound; bounds coincide when every source was searched.
+    pub ranking: Vec<BetweennessBounds>,
+    /// Breadth-first searches run, out of one per author for the full computation.
+    pub searches: usize,
+}
+
+/// Whether the neighbours of `node` are pairwise collaborators, so that no shortest path
+/// passes through it.
+fn is_simplicial(graph: &AuthorGraph, node: NodeIndex) -> bool {
+    let neighbours: Vec<NodeIndex> = graph.neighbors(node).collect();
+    neighbours
+        .iter()
+        .enumerate()
+        .all(|(i, &a)| neighbours[i + 1..].iter().all(|&b| graph.find_edge(a, b).is_some()))
+}
+
+/// The `k` authors of highest betweenness, found exactly without searching from every
+/// author.
+///
+/// Searches run source by source, largest component first, and each counts only the
+/// pairs whose other endpoint has not been a source yet, so every pair is counted once.
+/// An author's counted pairs are a lower bound on its betweenness; adding one for every
+/// pair of not-yet-searched authors in its component gives an upper bound, which is zero
+/// for authors whose collaborators all know each other. Once no author outside the
+/// current top `k` can reach the `k`-th lower bound, the set is final. Components whose
+/// authors are all out of reach are never searched.
+pub fn top_k_betweenness(graph: &AuthorGraph, k: usize) -> TopBetweenness {
+    let n = graph.node_count();
+    let scale = if n > 2 { 2.0 / ((n - 1) * (n - 2)) as f64 } else { 1.0 };
+    let simplicial: Vec<bool> = graph.node_indices().map(|node| is_simplicial(graph, node)).collect();
+    let mut components = kosaraju_scc(graph);
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    let mut component_of = vec![0; n];
+    for (c, component) in components.iter().enumerate() {
+        component.iter().for_each(|node| component_of[node.index()] = c);
+    }
+    // Authors of each component not searched from yet.
+    let mut remaining: Vec<usize> = components.iter().map(Vec::len).collect();
+    let mut searched = vec![false; n];
+    let mut lower = vec![0.0; n];
+    let pairs = |r: usize| (r * r.saturating_sub(1) / 2) as f64;
+    let upper = |node: usize, lower: &[f64], remaining: &[usize], searched: &[bool]| {
+        if simplicial[node] {
+            0.0
+        } else {
+            lower[node] + pairs(remaining[component_of[node]] - usize::from(!searched[node]))
+        }
+    };
+
+    // The current top k by lower bound, and the smallest lower bound among them.
+    let top = |lower: &[f64]| {
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| lower[b].partial_cmp(&lower[a]).unwrap_or(std::cmp::Ordering::Equal).then(a.cmp(&b)));
+        order.truncate(k.min(n));
+        let threshold = order.last().map_or(f64::INFINITY, |&last| lower[last]);
+        (order, threshold)
+    };
+    let check_every = (n / 100).max(1);
+    let mut searches = 0;
+    'components: for (c, component) in components.iter().enumerate() {
+        for (i, &source) in component.iter().enumerate() {
+            if i == 0 || searches % check_every == 0 {
+                let (order, threshold) = top(&lower);
+                let mut in_top = vec![false; n];
+                order.iter().for_each(|&node| in_top[node] = true);
+                let open = |node: usize| !in_top[node] && upper(node, &lower, &remaining, &searched) >= threshold;
+                if !(0..n).any(open) {
+                    break 'components;
+                }
+                if !component.iter().any(|node| open(node.index())) {
+                    continue 'components;
+                }
+            }
+            let dag = ShortestPathDag::from_source(graph, source);
+            let delta = dag.dependencies_on(|target| target != source && !searched[target.index()]);
+            for &v in &dag.order[1..] {
+                lower[v.index()] += delta[v.index()];
+            }
+            searched[source.index()] = true;
+            remaining[c] -= 1;
+            searches += 1;
+        }
+    }
+
+    let (order, _) = top(&lower);
+    let ranking = order
+        .into_iter()
+        .map(|node| BetweennessBounds {
+            author: graph[NodeIndex::new(node)],
+            lower: lower[node] * scale,
+            upper: upper(node, &lower, &remaining, &searched) * scale,
+        })
+        .collect();
+    TopBetweenness { ranking, searches }
+}
+
+/// Draws uniformly random shortest paths, reusing its buffers between samples.
+struct PathSampler {
+    distance: Vec<u32>,
+    sigma: Vec<f64>,
+    visited: Vec<NodeIndex>,
+}
+
+impl PathSampler {
+    fn new(n: usize) -> PathSampler {
+        PathSampler { distance: vec![u32::MAX; n], sigma: vec![0.0; n], visited: Vec::new() }
+    }
+
+    /// Count the inner nodes of a random shortest `s`–`t` path in `hits`. The search
+    /// stops once the level before `t` is complete, so near pairs are cheap.
+    fn sample(&mut self, graph: &AuthorGraph, s: NodeIndex, t: NodeIndex, rng: &mut StdRng, hits: &mut [usize]) {
+        for node in self.visited.drain(..) {
+            self.distance[node.index()] = u32::MAX;
+            self.sigma[node.index()] = 0.0;
+        }
+        self.distance[s.index()] = 0;
+        self.sigma[s.index()] = 1.0;
+        self.visited.push(s);
+        let mut head = 0;
+        while let Some(&node) = self.visited.get(head) {
+            if self.distance[node.index()] >= self.distance[t.index()] {
+                break;
+            }
+            head += 1;
+            for next in graph.neighbors(node) {
+                if self.distance[next.index()] == u32::MAX {
+                    self.distance[next.index()] = self.distance[node.index()] + 1;
+                    self.visited.push(next);
+                }
+                if self.distance[next.index()] == self.distance[node.index()] + 1 {
+                    self.sigma[next.index()] += self.sigma[node.index()];
+                }
+            }
+        }
+        if self.distance[t.index()] == u32::MAX {
+            return;
+        }
+        // Walk back to the source, choosing predecessors in proportion to their paths.
+        let mut node = t;
+        loop {
+            let level = self.distance[node.index()] - 1;
+            let mut pick = rng.gen::<f64>() * self.sigma[node.index()];
+            let mut chosen = None;
+            for previous in graph.neighbors(node) {
+                if self.distance[previous.index()] == level {
+                    chosen = Some(previous);
+                    pick -= self.sigma[previous.index()];
+                    if pick < 0.0 {
+                        break;
+                    }
+                }
+            }
+            let chosen = chosen.expect("every reached node has a predecessor");
+            if chosen == s {
+                break;
+            }
+            hits[chosen.index()] += 1;
+            node = chosen;
+        }
+    }
+}
+
+/// Growth of the sample between two stopping checks.
+const SCHEDULE_GROWTH: f64 = 1.5;
+
+/// Adaptive-sampling betweenness: with probability at least `1 - delta`, every estimate
+/// is within `epsilon` of the normalized betweenness.
+///
+/// Each sample is a uniformly random pair of authors and a uniformly random shortest path
+/// between them, which credits the authors inside it (Riondato and Kornaropoulos, 2016).
+/// Sampling stops at the first of a geometric schedule of checkpoints where an empirical
+/// Bernstein bound, made simultaneous over authors and checkpoints, is below `epsilon`
+/// for every author, as in progressive-sampling schemes such as KADABRA; otherwise at
+/// the VC-dimension bound, computed from the vertex diameter, which holds on its own.
+/// The checkpoints and the VC bound each get half of `delta`.
+pub fn approximate_betweenness(graph: &AuthorGraph, epsilon: f64, delta: f64, seed: u64) -> BetweennessEstimate {
+    let n = graph.node_count();
+    if n < 3 {
+        let scores = graph.node_indices().map(|node| (graph[node], 0.0)).collect();
+        return BetweennessEstimate { scores, samples: 0, max_samples: 0, error_bound: 0.0 };
+    }
+    // A random ordered pair has a shortest path through `v` with probability
+    // b(v) (n - 2) / n, so sample means are bounded on that scale.
+    let scale = n as f64 / (n - 2) as f64;
+    let tolerance = epsilon / scale;
+
+    // Vertex diameter (nodes on the longest shortest path): at most one more than twice
+    // the eccentricity of any node, taken per component.
+    let vertex_diameter = kosaraju_scc(graph)
+        .iter()
+        .map(|component| ShortestPathDag::from_source(graph, component[0]).eccentricity() * 2 + 1)
+        .max()
+        .unwrap_or(1);
+    let vc_dimension = ((vertex_diameter.max(3) - 2) as f64).log2().floor() + 1.0;
+    let max_samples = ((0.5 / (tolerance * tolerance)) * (vc_dimension + (2.0 / delta).ln())).ceil() as usize;
+
+    // Checkpoints from the smallest sample at which an unseen author can pass the test.
+    let first = 1.0 + 7.0 * ((2 * n) as f64 / delta).ln() / (3.0 * tolerance);
+    let mut checkpoints = Vec::new();
+    let mut next = first;
+    while (next as usize) < max_samples {
+        checkpoints.push(next as usize);
+        next *= SCHEDULE_GROWTH;
+    }
+    let log_term = (4.0 * n as f64 * checkpoints.len().max(1) as f64 / delta).ln();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut sampler = PathSampler::new(n);
+    let mut hits = vec![0usize; n];
+    let mut samples = 0;
+    let mut error_bound = epsilon;
+    for target in checkpoints.iter().copied().chain([max_samples]) {
+        while samples < target {
+            samples += 1;
+            let (s, t) = (rng.gen_range(0..n), rng.gen_range(0..n - 1));
+            let t = if t >= s { t + 1 } else { t };
+            sampler.sample(graph, NodeIndex::new(s), NodeIndex::new(t), &mut rng, &mut hits);
+        }
+        if target == max_samples {
+            break;
+        }
+        // Empirical Bernstein bound (Maurer and Pontil, 2009) on each mean.
+        let r = samples as f64;
+        let bound = hits
+            .iter()
+            .map(|&h| {
+                let p = h as f64 / r;
+                let variance = p * (1.0 - p) * r / (r - 1.0);
+                (2.0 * variance * log_term / r).sqrt() + 7.0 * log_term / (3.0 * (r - 1.0))
+            })
+            .fold(0.0, f64::max);
+        if bound <= tolerance {
+            error_bound = bound * scale;
+            break;
+        }
+    }
+    let scores = graph
+        .node_indices()
+        .map(|node| (graph[node], hits[node.index()] as f64 / samples as f64 * scale))
+        .collect();
+    BetweennessEstimate { scores, samples, max_samples, error_bound }
+}
+
+/// Shortest-path centralities computed from one breadth-first search per author.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathCentrality {
+    /// Stress centrality (Shimbel, 1953): the number of shortest paths between other
+    /// authors through each author, unnormalized.
+    Stress,
+    /// Load centrality (Goh et al., 2001; Newman, 2001): like betweenness, but flow splits
+    /// equally at each branching rather than in proportion to path counts. Normalized by
+    /// `(n-1)(n-2)` like networkx's `load_centrality`.
+    Load,
+}
+
+impl FromStr for PathCentrality {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<PathCentrality, String> {
+        match name {
+            "stress" => Ok(PathCentrality::Stress),
+            "load" => Ok(PathCentrality::Load),
+            _ => Err(format!("unknown path centrality: {} (expected stress or load)", name)),
+        }
+    }
+}
+
+/// Compute the chosen shortest-path centrality for every author.
+pub fn path_centrality(graph: &AuthorGraph, measure: PathCentrality) -> HashMap<usize, f64> {
+    let n = graph.node_count();
+    let mut centrality = vec![0.0; n];
+    for source in graph.node_indices() {
+        let dag = ShortestPathDag::from_source(graph, source);
+        let scores = match measure {
+            PathCentrality::Stress => dag.paths_through(),
+            PathCentrality::Load => dag.load(),
+        };
+        for &v in &dag.order {
+            centrality[v.index()] += scores[v.index()];
+        }
+    }
+    // Stress counts each undirected path from both of its endpoints; load keeps both
+    // directions, as networkx does.
+    let scale = match measure {
+        PathCentrality::Stress => 0.5,
+        PathCentrality::Load if n > 2 => 1.0 / ((n - 1) * (n - 2)) as f64,
+        PathCentrality::Load => 1.0,
+    };
+    graph.node_indices().map(|node| (graph[node], centrality[node.index()] * scale)).collect()
+}
+
+/// Percolation centrality (Piraveenan, Prokopenko & Hossain, 2013): betweenness in which
+/// the paths from source `s` are weighted by its percolation state `x_s` in [0, 1], e.g.
+/// how far a spreading process has reached it, relative to the states of the others:
+///
+/// PC(v) = 1/(n-2) * sum over s != v != t of sigma_st(v) / sigma_st * x_s / (sum x - x_v).
+///
+/// Authors missing from `states` are taken as not percolated (state zero).
+pub fn percolation_centrality(graph: &AuthorGraph, states: &Signal) -> HashMap<usize, f64> {
+    let n = graph.node_count();
+    let state: Vec<f64> = graph
+        .node_indices()
+        .map(|node| states.get(&graph[node]).copied().unwrap_or(0.0))
+        .collect();
+    let total: f64 = state.iter().sum();
+    let mut centrality = vec![0.0; n];
+    for source in graph.node_indices() {
+        if state[source.index()] == 0.0 {
+            continue;
+        }
+        let dag = ShortestPathDag::from_source(graph, source);
+        let delta = dag.dependencies();
+        for &v in &dag.order[1..] {
+            let others = total - state[v.index()];
+            if others > 0.0 {
+                centrality[v.index()] += delta[v.index()] * state[source.index()] / others;
+            }
+        }
+    }
+    let scale = if n > 2 { 1.0 / (n - 2) as f64 } else { 1.0 };
+    graph.node_indices().map(|node| (graph[node], centrality[node.index()] * scale)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_weighted_graph_from_reader_audited;
+
+    /// Triangles 0-1-2 and 3-4-5 joined by the bridge 2-3, plus the pendant 5-6.
+    fn bridged_triangles() -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..7).map(|author| graph.add_node(author)).collect();
+        for &(a, b) in &[(0, 1), (0, 2), (1, 2), (2, 3), (3, 4), (3, 5), (4, 5), (5, 6)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_percolation_centrality_matches_networkx() {
+        let graph = bridged_triangles();
+        let states: Signal = [(0, 1.0), (1, 0.5), (4, 0.2), (6, 0.9)].into_iter().collect();
+        // networkx.percolation_centrality(G, states=states)
+        let expected = [0.0, 0.0, 0.6307692, 0.6, 0.0, 0.4769231, 0.0];
+        let centrality = percolation_centrality(&graph, &states);
+        for (author, value) in expected.iter().enumerate() {
+            assert!((centrality[&author] - value).abs() < 1e-6, "{}: {}", author, centrality[&author]);
+        }
+    }
+
+    #[test]
+    fn test_betweenness_matches_networkx() {
+        let graph = bridged_triangles();
+        // networkx.betweenness_centrality(G, normalized=False), and normalized by 15 pairs.
+        let raw = [0.0, 0.0, 8.0, 9.0, 0.0, 5.0, 0.0];
+        let unnormalized = betweenness_centrality(&graph, false);
+        let normalized = betweenness_centrality(&graph, true);
+        for (author, value) in raw.iter().enumerate() {
+            assert!((unnormalized[&author] - value).abs() < 1e-12);
+            assert!((normalized[&author] - value / 15.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_weighted_betweenness_matches_networkx() {
+        // 0-1 weighs 2, tying with 0-2-1; 3-4-5 is shorter than 3-5, so 4 takes over
+        // part of 3's and 5's traffic.
+        let weight = |a: usize, b: usize| match (a, b) {
+            (0, 1) => 2.0,
+            (3, 4) | (4, 5) => 0.5,
+            _ => 1.0,
+        };
+        let unweighted = bridged_triangles();
+        let graph = unweighted.map(|_, &author| author, |edge, _| {
+            let (a, b) = unweighted.edge_endpoints(edge).unwrap();
+            weight(unweighted[a], unweighted[b])
+        });
+        let raw = [0.0, 0.0, 8.5, 9.0, 4.0, 5.0, 0.0];
+        let centrality = weighted_betweenness_centrality(&graph, EdgeWeights::Distance, true);
+        for (author, value) in raw.iter().enumerate() {
+            assert!((centrality[&author] - value / 15.0).abs() < 1e-12, "{}: {}", author, centrality[&author]);
+        }
+        // With unit weights it is the hop-count betweenness, whatever they mean.
+        let unit = unweighted.map(|_, &author| author, |_, _| 1.0);
+        for weights in [EdgeWeights::Similarity, EdgeWeights::Distance] {
+            assert_eq!(weighted_betweenness_centrality(&unit, weights, false), betweenness_centrality(&unweighted, false));
+        }
+    }
+
+    #[test]
+    fn test_heavy_collaborations_attract_shortest_paths() {
+        // The square 0-1-2-3 with 4 joint papers on 0-1 and 1-2: as similarities, 0-1-2
+        // (length 1/2) beats 0-3-2 (length 2), so 1 carries the 0-2 pair; read as
+        // distances the same weights push the pair onto 3. The 1-3 pair ties either way.
+        let input = "0 1 4\n1 2 4\n2 3\n3 0\n";
+        let graph = load_weighted_graph_from_reader_audited(input.as_bytes(), &Default::default(), &mut Default::default());
+        let graph = graph.unwrap();
+        let similarity = weighted_betweenness_centrality(&graph, EdgeWeights::Similarity, false);
+        assert_eq!((similarity[&1], similarity[&3]), (1.0, 0.0));
+        let distance = weighted_betweenness_centrality(&graph, EdgeWeights::Distance, false);
+        assert_eq!((distance[&1], distance[&3]), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_edge_betweenness_matches_networkx() {
+        // networkx.edge_betweenness_centrality(G, normalized=False); normalized by 21 pairs.
+        let expected = [
+            ((0, 1), 1.0), ((0, 2), 5.0), ((1, 2), 5.0), ((2, 3), 12.0),
+            ((3, 4), 4.0), ((3, 5), 8.0), ((4, 5), 2.0), ((5, 6), 6.0),
+        ];
+        let raw = edge_betweenness_centrality(&bridged_triangles(), false);
+        let normalized = edge_betweenness_centrality(&bridged_triangles(), true);
+        assert_eq!(raw.len(), 8);
+        for (edge, value) in expected {
+            assert!((raw[&edge] - value).abs() < 1e-12, "{:?}: {}", edge, raw[&edge]);
+            assert!((normalized[&edge] - value / 21.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_dependencies_count_path_fractions() {
+        // 4-cycle 0-1-2-3: node 2 is reached from 0 by two shortest paths.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..4).map(|author| graph.add_node(author)).collect();
+        for i in 0..4 {
+            graph.add_edge(nodes[i], nodes[(i + 1) % 4], ());
+        }
+        let dag = ShortestPathDag::from_source(&graph, nodes[0]);
+        assert_eq!(dag.sigma, vec![1.0, 1.0, 2.0, 1.0]);
+        // Nodes 1 and 3 each lie on half of the paths to 2; the source on all three.
+        assert_eq!(dag.dependencies(), vec![3.0, 0.5, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_stress_and_load_differ_from_betweenness() {
+        // The 3-rung ladder 0-1, 2-3, 4-5, where networkx's load (1.75 unnormalized for
+        // the corners) differs from betweenness (5/6).
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|author| graph.add_node(author)).collect();
+        for &(a, b) in &[(0, 1), (0, 2), (1, 3), (2, 3), (2, 4), (4, 5), (3, 5)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        let stress = path_centrality(&graph, PathCentrality::Stress);
+        let load = path_centrality(&graph, "load".parse().unwrap());
+        let expected = [(2.0, 1.75), (2.0, 1.75), (7.0, 6.5), (7.0, 6.5), (2.0, 1.75), (2.0, 1.75)];
+        for (author, (paths, flow)) in expected.iter().enumerate() {
+            assert_eq!(stress[&author], *paths);
+            assert!((load[&author] - flow / 20.0).abs() < 1e-12, "{}: {}", author, load[&author]);
+        }
+        assert!("closeness".parse::<PathCentrality>().is_err());
+    }
+
+    #[test]
+    fn test_approximate_betweenness_within_epsilon() {
+        // A 12x12 grid, where betweenness peaks in the middle.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..144).map(|author| graph.add_node(author)).collect();
+        for i in 0..144 {
+            if i % 12 < 11 {
+                graph.add_edge(nodes[i], nodes[i + 1], ());
+            }
+            if i < 132 {
+                graph.add_edge(nodes[i], nodes[i + 12], ());
+            }
+        }
+        let exact = betweenness_centrality(&graph, true);
+        let estimate = approximate_betweenness(&graph, 0.02, 0.1, 3);
+        assert!(estimate.samples < estimate.max_samples, "{} samples", estimate.samples);
+        assert!(estimate.error_bound <= 0.02);
+        for (author, score) in &estimate.scores {
+            assert!((score - exact[author]).abs() <= 0.02, "{}: {} vs {}", author, score, exact[author]);
+        }
+        assert_eq!(approximate_betweenness(&bridged_triangles(), 0.1, 0.1, 1).scores.len(), 7);
+    }
+
+    #[test]
+    fn test_top_k_betweenness_is_exact() {
+        // Two grids of different sizes joined by a path, plus a separate triangle.
+        let mut edges = String::new();
+        for (offset, side) in [(0, 9), (100, 6)] {
+            for i in 0..side * side {
+                if i % side < side - 1 {
+                    edges.push_str(&format!("{} {}\n", offset + i, offset + i + 1));
+                }
+                if i < side * (side - 1) {
+                    edges.push_str(&format!("{} {}\n", offset + i, offset + i + side));
+                }
+            }
+        }
+        edges.push_str("80 90\n90 91\n91 100\n200 201\n201 202\n202 200\n");
+        let graph = crate::load_graph_from_reader(edges.as_bytes()).unwrap();
+        let exact = betweenness_centrality(&graph, true);
+        let top = top_k_betweenness(&graph, 5);
+        assert!(top.searches < graph.node_count(), "{} searches", top.searches);
+        let mut expected: Vec<(usize, f64)> = exact.into_iter().collect();
+        expected.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+        let mut found: Vec<usize> = top.ranking.iter().map(|bounds| bounds.author).collect();
+        let mut wanted: Vec<usize> = expected[..5].iter().map(|&(author, _)| author).collect();
+        found.sort_unstable();
+        wanted.sort_unstable();
+        assert_eq!(found, wanted);
+        for bounds in &top.ranking {
+            let score = expected.iter().find(|(author, _)| *author == bounds.author).unwrap().1;
+            assert!(bounds.lower <= score + 1e-12 && score <= bounds.upper + 1e-12);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{top_k, AuthorGraph};
+use petgraph::visit::EdgeRef;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// Where a top-ranked author lands when the collaborations are resampled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankInterval {
+    pub author: usize,
+    /// Score and rank (1 = best) on the observed graph.
+    pub score: f64,
+    pub rank: usize,
+    /// Bootstrap percentile interval of the rank.
+    pub lower: usize,
+    pub upper: usize,
+    /// Fraction of the resamples in which the author is in the top `k`.
+    pub top_k_share: f64,
+    /// Whether the interval is disjoint from those of the authors ranked just above and
+    /// below, i.e. the position itself, not just membership of the top `k`, is supported
+    /// by the data.
+    pub separated: bool,
+}
+
+/// Rank of every author under `scores`, 1 for the best; tied authors share the best rank
+/// among them.
+pub fn ranks(scores: &HashMap<usize, f64>) -> HashMap<usize, usize> {
+    let mut sorted: Vec<f64> = scores.values().copied().collect();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    scores
+        .iter()
+        .map(|(&author, &score)| (author, sorted.partition_point(|&other| other > score) + 1))
+        .collect()
+}
+
+/// Bootstrap confidence intervals for the ranks of the top `k` authors under `measure`.
+/// Each of `resamples` replicates draws as many collaborations as the graph has, with
+/// replacement (an edge drawn twice counts once), keeps every author, and ranks them
+/// again. The intervals are the `confidence` percentile intervals of the replicate ranks.
+pub fn bootstrap_ranks<F>(
+    graph: &AuthorGraph,
+    measure: F,
+    k: usize,
+    resamples: usize,
+    confidence: f64,
+    seed: u64,
+) -> Vec<RankInterval>
+where
+    F: Fn(&AuthorGraph) -> HashMap<usize, f64>,
+{
+    let observed = measure(graph);
+    let observed_ranks = ranks(&observed);
+    let top = top_k(&observed, k);
+    let edges: Vec<_> = graph.edge_references().map(|edge| (edge.source(), edge.target())).collect();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut replicate_ranks: Vec<Vec<usize>> = vec![Vec::with_capacity(resamples); top.len()];
+    for _ in 0..resamples {
+        let mut resampled = graph.clone();
+        resampled.clear_edges();
+        let mut drawn = HashSet::new();
+        for _ in 0..edges.len() {
+            let (a, b) = edges[rng.gen_range(0..edges.len())];
+            if drawn.insert((a, b)) {
+                resampled.add_edge(a, b, ());
+            }
+        }
+        let replicate = ranks(&measure(&resampled));
+        for (position, (author, _)) in top.iter().enumerate() {
+            replicate_ranks[position].push(replicate[author]);
+        }
+    }
+
+    let tail = (1.0 - confidence) / 2.0;
+    let mut intervals: Vec<RankInterval> = top
+        .iter()
+        .zip(replicate_ranks)
+        .map(|(&(author, score), mut sample)| {
+            sample.sort_unstable();
+            let rank = observed_ranks[&author];
+            let quantile = |q: f64| {
+                let index = ((q * sample.len() as f64).ceil() as usize).clamp(1, sample.len().max(1)) - 1;
+                sample.get(index).copied().unwrap_or(rank)
+            };
+            let in_top = sample.iter().filter(|&&r| r <= k).count();
+            RankInterval {
+                author,
+                score,
+                rank,
+                lower: quantile(tail),
+                upper: quantile(1.0 - tail),
+                top_k_share: in_top as f64 / sample.len().max(1) as f64,
+                separated: false,
+            }
+        })
+        .collect();
+    for position in 0..intervals.len() {
+        let (lower, upper) = (intervals[position].lower, intervals[position].upper);
+        let above = position == 0 || intervals[position - 1].upper < lower;
+        let below = intervals.get(position + 1).is_none_or(|next| upper < next.lower);
+        intervals[position].separated = above && below;
+    }
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{degree_centrality, load_graph_from_reader};
+
+    fn degree(graph: &AuthorGraph) -> HashMap<usize, f64> {
+        degree_centrality(graph).into_iter().map(|(author, degree)| (author, degree as f64)).collect()
+    }
+
+    #[test]
+    fn test_ranks_share_ties() {
+        let scores: HashMap<usize, f64> = [(1, 0.5), (2, 0.9), (3, 0.5), (4, 0.1)].into_iter().collect();
+        let ranks = ranks(&scores);
+        assert_eq!((ranks[&2], ranks[&1], ranks[&3], ranks[&4]), (1, 2, 2, 4));
+    }
+
+    #[test]
+    fn test_bootstrap_separates_a_dominant_hub() {
+        // Author 0 collaborates with 30 others, authors 1 and 2 with 6 each.
+        let mut edges = String::new();
+        for other in 3..33 {
+            edges.push_str(&format!("0 {}\n", other));
+        }
+        for other in 33..39 {
+            edges.push_str(&format!("1 {}\n2 {}\n", other, other + 6));
+        }
+        let graph = load_graph_from_reader(edges.as_bytes()).unwrap();
+        let intervals = bootstrap_ranks(&graph, degree, 3, 200, 0.9, 5);
+        assert_eq!(intervals.len(), 3);
+        assert_eq!((intervals[0].author, intervals[0].lower, intervals[0].upper), (0, 1, 1));
+        assert!(intervals[0].separated && intervals[0].top_k_share == 1.0);
+        // 1 and 2 are interchangeable, so neither position is meaningful.
+        assert!(!intervals[1].separated && !intervals[2].separated);
+        assert!(intervals[1].lower == 2 && intervals[1].upper >= 3);
+    }
+}
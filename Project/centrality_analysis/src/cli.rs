@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Options that never take a value.
+const SWITCHES: &[&str] = &["append", "bar-chart", "dense", "directed", "estimate", "exact", "largest-component", "overwrite", "timings", "uncorrected", "weighted"];
+
+/// Command-line arguments split into positional arguments and `--name [value]` options.
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<String, Option<String>>,
+}
+
+impl Args {
+    /// Parse the arguments following the program name.
+    ///
+    /// Options are written `--name value` or `--name=value`; names listed in
+    /// `SWITCHES` are boolean and never consume the following argument.
+    pub fn parse(raw: &[String]) -> Args {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut iter = raw.iter().peekable();
+        while let Some(arg) = iter.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                positional.push(arg.clone());
+                continue;
+            };
+            if let Some((name, value)) = option.split_once('=') {
+                options.insert(name.to_string(), Some(value.to_string()));
+            } else if SWITCHES.contains(&option) {
+                options.insert(option.to_string(), None);
+            } else {
+                let value = iter.next_if(|next| !next.starts_with("--")).cloned();
+                options.insert(option.to_string(), value);
+            }
+        }
+        Args { positional, options }
+    }
+
+    /// Whether `--name` was given at all.
+    pub fn has(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    /// Whether `value` was given on the command line, as a positional argument or as the
+    /// value of an option.
+    pub fn names(&self, value: &str) -> bool {
+        self.positional.iter().any(|arg| arg == value) || self.options.values().any(|option| option.as_deref() == Some(value))
+    }
+
+    /// The raw value of `--name`, if it was given one.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|value| value.as_deref())
+    }
+
+    /// Parse the value of `--name`, or `None` when it is absent.
+    pub fn parse_optional<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        match self.options.get(name) {
+            None => Ok(None),
+            Some(None) => Err(format!("--{} needs a value", name)),
+            Some(Some(value)) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid value for --{}: {}", name, value)),
+        }
+    }
+
+    /// Parse the value of `--name`, falling back to `default` when it is absent.
+    pub fn parse_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.options.get(name) {
+            None => Ok(default),
+            Some(None) => Err(format!("--{} needs a value", name)),
+            Some(Some(value)) => value
+                .parse()
+                .map_err(|_| format!("invalid value for --{}: {}", name, value)),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::AuthorGraph;
+use petgraph::graph::NodeIndex;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Enumerate the maximal cliques with at least `min_size` authors, using Bron–Kerbosch
+/// with Tomita pivoting over a degeneracy ordering (Eppstein, Löffler & Strash, 2010).
+///
+/// Each clique lists its authors in ascending order; cliques are sorted largest first.
+pub fn maximal_cliques(graph: &AuthorGraph, min_size: usize) -> Vec<Vec<usize>> {
+    let neighbours: Vec<HashSet<usize>> = graph
+        .node_indices()
+        .map(|node| graph.neighbors(node).map(|other| other.index()).collect())
+        .collect();
+
+    let mut cliques = Vec::new();
+    let order = degeneracy_order(graph);
+    let mut position = vec![0; order.len()];
+    for (rank, &node) in order.iter().enumerate() {
+        position[node] = rank;
+    }
+    for &node in &order {
+        // Later neighbours may still join; earlier ones have already been expanded.
+        let (candidates, excluded): (Vec<usize>, Vec<usize>) =
+            neighbours[node].iter().partition(|&&other| position[other] > position[node]);
+        let mut clique = vec![node];
+        expand(&neighbours, &mut clique, candidates, excluded, min_size, &mut cliques);
+    }
+
+    let mut cliques: Vec<Vec<usize>> = cliques
+        .into_iter()
+        .map(|clique| {
+            let mut authors: Vec<usize> = clique.into_iter().map(|i| graph[NodeIndex::new(i)]).collect();
+            authors.sort_unstable();
+            authors
+        })
+        .collect();
+    cliques.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    cliques
+}
+
+fn expand(
+    neighbours: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: Vec<usize>,
+    mut excluded: Vec<usize>,
+    min_size: usize,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if clique.len() + candidates.len() < min_size {
+        return;
+    }
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(clique.clone());
+        }
+        return;
+    }
+
+    // Pivot on the vertex covering most candidates; only its non-neighbours need branching.
+    let pivot = candidates
+        .iter()
+        .chain(&excluded)
+        .copied()
+        .max_by_key(|&u| candidates.iter().filter(|v| neighbours[u].contains(v)).count())
+        .unwrap();
+    let branches: Vec<usize> = candidates.iter().copied().filter(|v| !neighbours[pivot].contains(v)).collect();
+    for v in branches {
+        clique.push(v);
+        expand(
+            neighbours,
+            clique,
+            candidates.iter().copied().filter(|u| neighbours[v].contains(u)).collect(),
+            excluded.iter().copied().filter(|u| neighbours[v].contains(u)).collect(),
+            min_size,
+            cliques,
+        );
+        clique.pop();
+        candidates.retain(|&u| u != v);
+        excluded.push(v);
+    }
+}
+
+/// Node indices in degeneracy order: repeatedly remove a node of minimum remaining degree.
+pub(crate) fn degeneracy_order(graph: &AuthorGraph) -> Vec<usize> {
+    let neighbours: Vec<Vec<usize>> = graph
+        .node_indices()
+        .map(|node| graph.neighbors(node).map(|other| other.index()).collect())
+        .collect();
+    let mut degree: Vec<usize> = neighbours.iter().map(Vec::len).collect();
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); degree.iter().max().map_or(1, |d| d + 1)];
+    for (node, &d) in degree.iter().enumerate() {
+        buckets[d].push(node);
+    }
+    let mut removed = vec![false; neighbours.len()];
+    let mut order = Vec::with_capacity(neighbours.len());
+    let mut lowest = 0;
+    while order.len() < neighbours.len() {
+        // Buckets hold stale entries; skip those whose degree has since dropped.
+        let Some(node) = buckets[lowest].pop() else {
+            lowest += 1;
+            continue;
+        };
+        if removed[node] || degree[node] != lowest {
+            continue;
+        }
+        removed[node] = true;
+        order.push(node);
+        for &other in &neighbours[node] {
+            if !removed[other] {
+                degree[other] -= 1;
+                buckets[degree[other]].push(other);
+                lowest = lowest.min(degree[other]);
+            }
+        }
+    }
+    order
+}
+
+/// Number of maximal cliques of each size, ordered by size.
+pub fn clique_size_distribution(cliques: &[Vec<usize>]) -> BTreeMap<usize, usize> {
+    let mut distribution = BTreeMap::new();
+    for clique in cliques {
+        *distribution.entry(clique.len()).or_insert(0) += 1;
+    }
+    distribution
+}
+
+/// Size of the largest clique, or 0 for an empty list.
+pub fn clique_number(cliques: &[Vec<usize>]) -> usize {
+    cliques.iter().map(Vec::len).max().unwrap_or(0)
+}
+
+/// Write a clique as a subgraph edge list in the input format, so it can be loaded again.
+pub fn write_clique<P: AsRef<Path>>(clique: &[usize], path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "# Maximal clique of {} authors", clique.len())?;
+    for (i, a) in clique.iter().enumerate() {
+        for b in &clique[i + 1..] {
+            writeln!(writer, "{}\t{}", a, b)?;
+        }
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph;
+
+    /// A 4-clique {1,2,3,4} sharing author 4 with the triangle {4,5,6}, plus the edge 6–7.
+    fn overlapping_cliques() -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..=7).map(|author| graph.add_node(author)).collect();
+        let edges = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (4, 5), (4, 6), (5, 6), (6, 7)];
+        for (a, b) in edges {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        let graph = overlapping_cliques();
+        let cliques = maximal_cliques(&graph, 1);
+        assert_eq!(cliques, vec![vec![1, 2, 3, 4], vec![4, 5, 6], vec![6, 7], vec![0]]);
+        assert_eq!(clique_number(&cliques), 4);
+
+        let distribution = clique_size_distribution(&cliques);
+        assert_eq!(distribution.into_iter().collect::<Vec<_>>(), vec![(1, 1), (2, 1), (3, 1), (4, 1)]);
+
+        // The size cutoff drops small cliques without losing the large ones.
+        assert_eq!(maximal_cliques(&graph, 3), vec![vec![1, 2, 3, 4], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_write_clique_round_trips() {
+        let path = "clique_test.txt";
+        write_clique(&[2, 5, 9], path).unwrap();
+        let graph = load_graph(path).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        fs::remove_file(path).unwrap();
+    }
+}
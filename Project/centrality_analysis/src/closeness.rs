@@ -0,0 +1,266 @@
+use crate::{AuthorGraph, EdgeWeights, WeightedAuthorGraph};
+use petgraph::algo::{dijkstra, kosaraju_scc};
+use petgraph::graph::NodeIndex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+/// Breadth-first search from `source`, leaving the authors it reaches in `order` and
+/// their hop distances in `distance`. Only entries set by the previous search are cleared,
+/// so searches inside small components do not pay for the whole graph.
+fn search(graph: &AuthorGraph, source: NodeIndex, distance: &mut [u32], order: &mut Vec<NodeIndex>) {
+    for node in order.drain(..) {
+        distance[node.index()] = u32::MAX;
+    }
+    distance[source.index()] = 0;
+    order.push(source);
+    let mut head = 0;
+    while let Some(&node) = order.get(head) {
+        head += 1;
+        for next in graph.neighbors(node) {
+            if distance[next.index()] == u32::MAX {
+                distance[next.index()] = distance[node.index()] + 1;
+                order.push(next);
+            }
+        }
+    }
+}
+
+/// Wasserman–Faust closeness of an author whose component has `reached` authors at a
+/// total distance of `sum` from it, in a graph of `n` authors.
+fn scaled_closeness(reached: usize, sum: f64, n: usize) -> f64 {
+    if sum <= 0.0 {
+        return 0.0;
+    }
+    let others = (reached - 1) as f64;
+    others / sum * others / (n - 1) as f64
+}
+
+/// Closeness centrality: the inverse of the average distance to the `r - 1` other authors
+/// an author reaches, scaled by `(r - 1) / (n - 1)` (Wasserman and Faust) so authors in
+/// small components do not outrank those in the giant one. Matches networkx's
+/// `closeness_centrality`.
+pub fn closeness_centrality(graph: &AuthorGraph) -> HashMap<usize, f64> {
+    let n = graph.node_count();
+    let (mut distance, mut order) = (vec![u32::MAX; n], Vec::new());
+    graph
+        .node_indices()
+        .map(|node| {
+            search(graph, node, &mut distance, &mut order);
+            let sum: u64 = order.iter().map(|other| distance[other.index()] as u64).sum();
+            (graph[node], scaled_closeness(order.len(), sum as f64, n))
+        })
+        .collect()
+}
+
+/// Closeness of [`closeness_centrality`] with distances summed over edge lengths, the
+/// weights read as `weights` says, by Dijkstra's algorithm from every author. With
+/// `EdgeWeights::Distance` it matches networkx's `closeness_centrality` with
+/// `distance="weight"`.
+pub fn weighted_closeness_centrality(graph: &WeightedAuthorGraph, weights: EdgeWeights) -> HashMap<usize, f64> {
+    let graph = &weights.lengths(graph);
+    let n = graph.node_count();
+    graph
+        .node_indices()
+        .map(|node| {
+            let distance = dijkstra(graph, node, None, |edge| *edge.weight());
+            (graph[node], scaled_closeness(distance.len(), distance.values().sum(), n))
+        })
+        .collect()
+}
+
+/// Harmonic closeness: the sum of `1 / d(u, v)` over all other authors, unreachable ones
+/// contributing zero, divided by `n - 1` so it lies in [0, 1]. Networkx's
+/// `harmonic_centrality` is the same sum without the division.
+pub fn harmonic_centrality(graph: &AuthorGraph) -> HashMap<usize, f64> {
+    let n = graph.node_count();
+    let (mut distance, mut order) = (vec![u32::MAX; n], Vec::new());
+    graph
+        .node_indices()
+        .map(|node| {
+            search(graph, node, &mut distance, &mut order);
+            let harmonic: f64 = order[1..].iter().map(|other| 1.0 / distance[other.index()] as f64).sum();
+            (graph[node], if n > 1 { harmonic / (n - 1) as f64 } else { 0.0 })
+        })
+        .collect()
+}
+
+/// Harmonic closeness of [`harmonic_centrality`] with distances summed over edge lengths,
+/// the weights read as `weights` says, by Dijkstra's algorithm from every author.
+pub fn weighted_harmonic_centrality(graph: &WeightedAuthorGraph, weights: EdgeWeights) -> HashMap<usize, f64> {
+    let graph = &weights.lengths(graph);
+    let n = graph.node_count();
+    graph
+        .node_indices()
+        .map(|node| {
+            let distance = dijkstra(graph, node, None, |edge| *edge.weight());
+            let harmonic: f64 = distance.iter().filter(|&(&other, _)| other != node).map(|(_, d)| 1.0 / d).sum();
+            (graph[node], if n > 1 { harmonic / (n - 1) as f64 } else { 0.0 })
+        })
+        .collect()
+}
+
+/// A sampled closeness estimate and a confidence interval around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosenessEstimate {
+    pub closeness: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Eppstein–Wang estimate of [`closeness_centrality`]: each author's average distance is
+/// taken over `samples` sources drawn uniformly, with replacement, from its component
+/// rather than over the whole component, so a component of `r` authors costs `samples`
+/// searches instead of `r`. Components of at most `samples` authors are searched from
+/// every author and get exact values.
+///
+/// Each interval holds the true value with probability at least `confidence` (for that
+/// author alone), by Hoeffding's inequality on distances bounded by the diameter, which
+/// is at most twice the smallest eccentricity among the sources.
+pub fn approximate_closeness(
+    graph: &AuthorGraph,
+    samples: usize,
+    confidence: f64,
+    seed: u64,
+) -> HashMap<usize, ClosenessEstimate> {
+    let n = graph.node_count();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (mut distance, mut order) = (vec![u32::MAX; n], Vec::new());
+    let mut sums = vec![0u64; n];
+    let mut estimates = HashMap::with_capacity(n);
+    for component in kosaraju_scc(graph) {
+        let r = component.len();
+        let exact = r <= samples.max(1);
+        let sources: Vec<NodeIndex> = if exact {
+            component.clone()
+        } else {
+            (0..samples).map(|_| component[rng.gen_range(0..r)]).collect()
+        };
+        let mut diameter = u32::MAX;
+        for &source in &sources {
+            search(graph, source, &mut distance, &mut order);
+            let eccentricity = order.last().map_or(0, |last| distance[last.index()]);
+            diameter = diameter.min(2 * eccentricity);
+            for node in &order {
+                sums[node.index()] += distance[node.index()] as u64;
+            }
+        }
+
+        // Half-width of the interval on the mean distance to a uniformly drawn source.
+        let k = sources.len() as f64;
+        let margin = if exact { 0.0 } else { diameter as f64 * ((2.0 / (1.0 - confidence)).ln() / (2.0 * k)).sqrt() };
+        // Every other author is at least one hop away.
+        let closest = (r - 1) as f64 / r as f64;
+        for node in component {
+            let mean = sums[node.index()] as f64 / k;
+            sums[node.index()] = 0;
+            let closeness = scaled_closeness(r, mean * r as f64, n);
+            let estimate = if exact {
+                ClosenessEstimate { closeness, lower: closeness, upper: closeness }
+            } else {
+                ClosenessEstimate {
+                    closeness,
+                    lower: scaled_closeness(r, (mean + margin) * r as f64, n),
+                    upper: scaled_closeness(r, (mean - margin).max(closest) * r as f64, n),
+                }
+            };
+            estimates.insert(graph[node], estimate);
+        }
+    }
+    estimates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_graph_from_reader, load_weighted_graph_from_reader_audited};
+
+    fn two_components() -> AuthorGraph {
+        load_graph_from_reader("0 1\n0 2\n1 2\n2 3\n3 4\n3 5\n4 5\n5 6\n7 8\n".as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_closeness_matches_networkx() {
+        let closeness = closeness_centrality(&two_components());
+        let expected = [
+            0.3214285714285714, 0.3214285714285714, 0.45, 0.5, 0.375, 0.4090909090909091, 0.28125, 0.125, 0.125,
+        ];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((closeness[&author] - value).abs() < 1e-12, "author {}: {}", author, closeness[&author]);
+        }
+    }
+
+    #[test]
+    fn test_weighted_closeness_matches_networkx() {
+        let input = "0 1 2\n0 2\n1 2\n2 3\n3 4 0.5\n3 5\n4 5 0.5\n5 6\n7 8 4\n";
+        let graph = load_weighted_graph_from_reader_audited(input.as_bytes(), &Default::default(), &mut Default::default());
+        let graph = graph.unwrap();
+        let closeness = weighted_closeness_centrality(&graph, EdgeWeights::Distance);
+        let expected = [
+            0.3103448275862069, 0.3103448275862069, 0.47368421052631576, 0.5294117647058824, 0.5, 0.42857142857142855,
+            0.29032258064516125, 0.03125, 0.03125,
+        ];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((closeness[&author] - value).abs() < 1e-12, "author {}: {}", author, closeness[&author]);
+        }
+        // Four joint papers make 7 and 8 a quarter apart instead of 4: (1 / 0.25) / 8.
+        assert_eq!(weighted_closeness_centrality(&graph, EdgeWeights::Similarity)[&7], 0.5);
+    }
+
+    #[test]
+    fn test_weighted_harmonic_matches_networkx() {
+        // nx.harmonic_centrality(G, distance="weight") divided by n - 1 = 8.
+        let input = "0 1 2\n0 2\n1 2\n2 3\n3 4 0.5\n3 5\n4 5 0.5\n5 6\n7 8 4\n";
+        let graph = load_weighted_graph_from_reader_audited(input.as_bytes(), &Default::default(), &mut Default::default());
+        let graph = graph.unwrap();
+        let harmonic = weighted_harmonic_centrality(&graph, EdgeWeights::Distance);
+        let expected = [
+            2.9833333333333334, 2.9833333333333334, 4.499999999999999, 5.5, 6.133333333333334, 5.166666666666666, 3.0,
+            0.25, 0.25,
+        ];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((harmonic[&author] - value / 8.0).abs() < 1e-12, "author {}: {}", author, harmonic[&author]);
+        }
+        assert_eq!(weighted_harmonic_centrality(&graph, EdgeWeights::Similarity)[&7], 0.5);
+    }
+
+    #[test]
+    fn test_harmonic_matches_networkx() {
+        // nx.harmonic_centrality divided by n - 1 = 8.
+        let harmonic = harmonic_centrality(&two_components());
+        let expected = [41.0 / 12.0, 41.0 / 12.0, 13.0 / 3.0, 4.5, 11.0 / 3.0, 25.0 / 6.0, 17.0 / 6.0, 1.0, 1.0];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((harmonic[&author] - value / 8.0).abs() < 1e-12, "author {}: {}", author, harmonic[&author]);
+        }
+    }
+
+    #[test]
+    fn test_approximate_closeness() {
+        // With as many samples as the largest component every value is exact.
+        let graph = two_components();
+        let exact = closeness_centrality(&graph);
+        for (author, estimate) in approximate_closeness(&graph, 7, 0.95, 1) {
+            assert!((estimate.closeness - exact[&author]).abs() < 1e-12);
+            assert_eq!((estimate.lower, estimate.upper), (estimate.closeness, estimate.closeness));
+        }
+
+        // A 10x10 grid sampled from 30 sources: the intervals bracket the true values.
+        let mut edges = String::new();
+        for i in 0..100 {
+            if i % 10 < 9 {
+                edges.push_str(&format!("{} {}\n", i, i + 1));
+            }
+            if i < 90 {
+                edges.push_str(&format!("{} {}\n", i, i + 10));
+            }
+        }
+        let grid = load_graph_from_reader(edges.as_bytes()).unwrap();
+        let exact = closeness_centrality(&grid);
+        let estimates = approximate_closeness(&grid, 30, 0.95, 7);
+        assert_eq!(estimates.len(), 100);
+        for (author, estimate) in estimates {
+            assert!(estimate.lower <= estimate.closeness && estimate.closeness <= estimate.upper);
+            assert!(estimate.lower <= exact[&author] && exact[&author] <= estimate.upper, "author {}", author);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::layout::Layout;
+use crate::AuthorGraph;
+use petgraph::algo::kosaraju_scc;
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Undirected;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+
+/// Stop coarsening once a level keeps more than this fraction of its nodes.
+const MIN_REDUCTION: f64 = 0.95;
+
+/// Smoothing sweeps per level when a coarse layout is carried back to the finer level.
+const REFINEMENT_SWEEPS: usize = 10;
+
+/// Surrogate graph: node weights count merged authors, edge weights merged collaborations.
+pub type WeightedGraph = Graph<usize, usize, Undirected>;
+
+/// A hierarchy of successively coarser graphs.
+#[derive(Debug, Clone)]
+pub struct Coarsening {
+    /// `levels[0]` is the input graph with unit weights; the last level is the coarsest.
+    pub levels: Vec<WeightedGraph>,
+    /// `mappings[i][v]` is the node of `levels[i + 1]` that node `v` of `levels[i]` merged into.
+    pub mappings: Vec<Vec<usize>>,
+}
+
+impl Coarsening {
+    pub fn coarsest(&self) -> &WeightedGraph {
+        self.levels.last().expect("a coarsening always has the input level")
+    }
+
+    /// Coarsest node of every input node, indexed by `NodeIndex::index()`.
+    pub fn membership(&self) -> Vec<usize> {
+        let mut membership: Vec<usize> = (0..self.levels[0].node_count()).collect();
+        for mapping in &self.mappings {
+            membership.iter_mut().for_each(|node| *node = mapping[*node]);
+        }
+        membership
+    }
+}
+
+/// Coarsen by repeated heavy-edge matching until at most `target_nodes` remain or a level
+/// stops shrinking; `seed` fixes the order in which nodes pick their partners.
+pub fn coarsen(graph: &AuthorGraph, target_nodes: usize, seed: u64) -> Coarsening {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut levels = vec![graph.map(|_, _| 1, |_, _| 1)];
+    let mut mappings = Vec::new();
+    while levels.last().unwrap().node_count() > target_nodes {
+        let current = levels.last().unwrap();
+        let (coarse, mapping) = contract(current, &mut rng);
+        if coarse.node_count() as f64 > MIN_REDUCTION * current.node_count() as f64 {
+            break;
+        }
+        levels.push(coarse);
+        mappings.push(mapping);
+    }
+    Coarsening { levels, mappings }
+}
+
+/// Lay out a large graph through its coarsening: `layout` places the coarsest level of
+/// at most `target_nodes` nodes, and every finer level then starts from the position of
+/// the node it merged into, a little apart from its partners, and is pulled towards its
+/// neighbours by weighted averaging. Costs `layout` on the coarse graph plus a few passes
+/// over the edges of each level, so an expensive layout such as the spectral one stays
+/// cheap on huge inputs.
+pub fn coarsened_layout<F: Fn(&AuthorGraph) -> Layout>(
+    graph: &AuthorGraph,
+    target_nodes: usize,
+    seed: u64,
+    layout: F,
+) -> Layout {
+    let coarsening = coarsen(graph, target_nodes, seed);
+    let coarsest: AuthorGraph = coarsening.coarsest().map(|node, _| node.index(), |_, _| ());
+    let mut positions = layout(&coarsest);
+    let mut rng = StdRng::seed_from_u64(seed);
+    for (level, mapping) in coarsening.levels.iter().zip(&coarsening.mappings).rev() {
+        positions = refine(level, mapping, &positions, &mut rng);
+    }
+    positions
+}
+
+/// Positions of the nodes of `level` from those of the next coarser level.
+fn refine(level: &WeightedGraph, mapping: &[usize], coarse: &Layout, rng: &mut StdRng) -> Layout {
+    // About half the typical spacing of the level's nodes in the square.
+    let spread = 0.5 / (level.node_count().max(1) as f64).sqrt();
+    let mut jitter = || rng.gen_range(-spread..=spread);
+    let anchors: Layout = mapping
+        .iter()
+        .map(|&node| {
+            let (x, y) = coarse[node];
+            ((x + jitter()).clamp(-1.0, 1.0), (y + jitter()).clamp(-1.0, 1.0))
+        })
+        .collect();
+    let mut positions = anchors.clone();
+    for _ in 0..REFINEMENT_SWEEPS {
+        positions = level
+            .node_indices()
+            .map(|node| {
+                let (mut x, mut y, mut total) = (0.0, 0.0, 0.0);
+                for edge in level.edges(node) {
+                    let other = if edge.source() == node { edge.target() } else { edge.source() };
+                    let weight = *edge.weight() as f64;
+                    x += weight * positions[other.index()].0;
+                    y += weight * positions[other.index()].1;
+                    total += weight;
+                }
+                let anchor = anchors[node.index()];
+                if total == 0.0 {
+                    anchor
+                } else {
+                    (0.5 * (anchor.0 + x / total), 0.5 * (anchor.1 + y / total))
+                }
+            })
+            .collect();
+    }
+    // Averaging pulls every component towards its centre, which would compound over the
+    // levels, so each one is scaled back to the spread of its anchors.
+    for component in kosaraju_scc(level) {
+        let (anchor_centre, anchor_spread) = centre_and_spread(component.iter().map(|node| anchors[node.index()]));
+        let (centre, spread) = centre_and_spread(component.iter().map(|node| positions[node.index()]));
+        let scale = if spread > 0.0 { anchor_spread / spread } else { 1.0 };
+        for node in component {
+            let (x, y) = positions[node.index()];
+            positions[node.index()] = (
+                (anchor_centre.0 + scale * (x - centre.0)).clamp(-1.0, 1.0),
+                (anchor_centre.1 + scale * (y - centre.1)).clamp(-1.0, 1.0),
+            );
+        }
+    }
+    positions
+}
+
+/// Centroid of `points` and their root mean square distance from it.
+fn centre_and_spread(points: impl Iterator<Item = (f64, f64)> + Clone) -> ((f64, f64), f64) {
+    let count = points.clone().count().max(1) as f64;
+    let (x, y) = points.clone().fold((0.0, 0.0), |(x, y), point| (x + point.0, y + point.1));
+    let centre = (x / count, y / count);
+    let squares: f64 = points.map(|(x, y)| (x - centre.0).powi(2) + (y - centre.1).powi(2)).sum();
+    (centre, (squares / count).sqrt())
+}
+
+/// One level of heavy-edge matching: every node merges with the unmatched neighbour it
+/// shares the heaviest edge with, preferring lighter partners on ties.
+fn contract(graph: &WeightedGraph, rng: &mut StdRng) -> (WeightedGraph, Vec<usize>) {
+    let mut order: Vec<NodeIndex> = graph.node_indices().collect();
+    order.shuffle(rng);
+
+    let mut mapping = vec![usize::MAX; graph.node_count()];
+    let mut coarse = WeightedGraph::new_undirected();
+    for node in order {
+        if mapping[node.index()] != usize::MAX {
+            continue;
+        }
+        let partner = graph
+            .edges(node)
+            .filter(|edge| edge.target() != node && mapping[edge.target().index()] == usize::MAX)
+            .max_by(|a, b| {
+                a.weight()
+                    .cmp(b.weight())
+                    .then_with(|| graph[b.target()].cmp(&graph[a.target()]))
+                    .then_with(|| b.target().cmp(&a.target()))
+            })
+            .map(|edge| edge.target());
+        let merged = coarse.add_node(graph[node] + partner.map_or(0, |p| graph[p]));
+        mapping[node.index()] = merged.index();
+        if let Some(partner) = partner {
+            mapping[partner.index()] = merged.index();
+        }
+    }
+
+    // Collapse parallel edges; edges inside a merged pair disappear.
+    let mut weights: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for edge in graph.edge_references() {
+        let (a, b) = (mapping[edge.source().index()], mapping[edge.target().index()]);
+        if a != b {
+            *weights.entry((a.min(b), a.max(b))).or_insert(0) += edge.weight();
+        }
+    }
+    for ((a, b), weight) in weights {
+        coarse.add_edge(NodeIndex::new(a), NodeIndex::new(b), weight);
+    }
+    (coarse, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coarsening_preserves_weights_and_edges() {
+        // A 6x6 grid.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..36).map(|author| graph.add_node(author)).collect();
+        for i in 0..36 {
+            if i % 6 < 5 {
+                graph.add_edge(nodes[i], nodes[i + 1], ());
+            }
+            if i < 30 {
+                graph.add_edge(nodes[i], nodes[i + 6], ());
+            }
+        }
+
+        let coarsening = coarsen(&graph, 5, 3);
+        let coarsest = coarsening.coarsest();
+        assert!(coarsening.levels.len() > 2);
+        assert!(coarsest.node_count() < 12);
+        // Every author is accounted for exactly once.
+        let authors: usize = coarsest.node_weights().sum();
+        assert_eq!(authors, 36);
+
+        // Each collaboration between different coarse nodes survives as edge weight.
+        let membership = coarsening.membership();
+        let crossing = graph
+            .edge_references()
+            .filter(|e| membership[e.source().index()] != membership[e.target().index()])
+            .count();
+        assert_eq!(coarsest.edge_weights().sum::<usize>(), crossing);
+    }
+
+    #[test]
+    fn test_coarsened_layout_keeps_neighbours_close() {
+        // A 10x10 grid: collaborators should end up nearer each other than authors picked
+        // at random.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..100).map(|author| graph.add_node(author)).collect();
+        for i in 0..100 {
+            if i % 10 < 9 {
+                graph.add_edge(nodes[i], nodes[i + 1], ());
+            }
+            if i < 90 {
+                graph.add_edge(nodes[i], nodes[i + 10], ());
+            }
+        }
+        let layout = coarsened_layout(&graph, 10, 1, crate::layout::spectral_layout);
+        assert_eq!(layout.len(), 100);
+        assert!(layout.iter().all(|&(x, y)| x.abs() <= 1.0 && y.abs() <= 1.0));
+
+        let distance = |a: usize, b: usize| (layout[a].0 - layout[b].0).hypot(layout[a].1 - layout[b].1);
+        let edges = graph.edge_references().map(|e| distance(e.source().index(), e.target().index())).sum::<f64>();
+        let pairs = (0..100).flat_map(|a| (0..a).map(move |b| (a, b))).map(|(a, b)| distance(a, b)).sum::<f64>();
+        assert!(edges / (graph.edge_count() as f64) < 0.5 * pairs / 4950.0);
+    }
+}
@@ -0,0 +1,891 @@
+use crate::output::ExistingOutput;
+use crate::{line_fields, AuthorGraph};
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Undirected;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Community id of each author.
+pub type Partition = HashMap<usize, usize>;
+
+/// Number of communities having each size (in authors), ordered by size.
+pub fn community_sizes(partition: &Partition) -> BTreeMap<usize, usize> {
+    let mut members: HashMap<usize, usize> = HashMap::new();
+    for &community in partition.values() {
+        *members.entry(community).or_insert(0) += 1;
+    }
+
+    let mut sizes = BTreeMap::new();
+    for size in members.into_values() {
+        *sizes.entry(size).or_insert(0) += 1;
+    }
+    sizes
+}
+
+/// Number of distinct communities in a partition.
+pub fn community_count(partition: &Partition) -> usize {
+    let mut ids: Vec<usize> = partition.values().copied().collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids.len()
+}
+
+/// Newman modularity of `partition` with the given resolution (1.0 is the classic definition).
+///
+/// Authors missing from the partition are treated as singleton communities.
+pub fn modularity(graph: &AuthorGraph, partition: &Partition, resolution: f64) -> f64 {
+    weighted_modularity(graph, |_| 1.0, partition, resolution)
+}
+
+/// Modularity of `partition` in a graph with weighted collaborations, such as a coarsened
+/// graph whose edges count merged collaborations: degrees become strengths (sums of
+/// `weight` over an author's edges) and edge counts total weights.
+pub fn weighted_modularity<E>(
+    graph: &Graph<usize, E, Undirected>,
+    weight: impl Fn(&E) -> f64,
+    partition: &Partition,
+    resolution: f64,
+) -> f64 {
+    let two_m = 2.0 * graph.edge_weights().map(&weight).sum::<f64>();
+    if two_m == 0.0 {
+        return 0.0;
+    }
+
+    let mut internal: HashMap<usize, f64> = HashMap::new();
+    let mut degree_sum: HashMap<usize, f64> = HashMap::new();
+    let mut singleton_penalty = 0.0;
+    for node in graph.node_indices() {
+        let degree: f64 = graph.edges(node).map(|edge| weight(edge.weight())).sum();
+        match partition.get(&graph[node]) {
+            Some(&community) => *degree_sum.entry(community).or_insert(0.0) += degree,
+            None => singleton_penalty += (degree / two_m).powi(2),
+        }
+    }
+    for edge in graph.edge_references() {
+        let source = partition.get(&graph[edge.source()]);
+        let target = partition.get(&graph[edge.target()]);
+        if let (Some(&a), Some(&b)) = (source, target) {
+            if a == b {
+                *internal.entry(a).or_insert(0.0) += weight(edge.weight());
+            }
+        }
+    }
+
+    let mut q = -resolution * singleton_penalty;
+    for (community, total) in degree_sum {
+        let links = internal.get(&community).copied().unwrap_or(0.0);
+        q += 2.0 * links / two_m - resolution * (total / two_m).powi(2);
+    }
+    q
+}
+
+/// Louvain community detection maximizing modularity at the given resolution.
+///
+/// Higher resolutions favour more, smaller communities. The seed fixes the order in
+/// which nodes are visited, so equal seeds give equal partitions.
+pub fn louvain(graph: &AuthorGraph, resolution: f64, seed: u64) -> Partition {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let membership = louvain_levels(LevelGraph::from_graph(graph), resolution, &mut rng);
+    to_partition(graph, &membership)
+}
+
+/// Run local moving and aggregation until nothing moves; returns the community of
+/// every node of the starting level.
+fn louvain_levels(mut level: LevelGraph, resolution: f64, rng: &mut StdRng) -> Vec<usize> {
+    // Level node that each original node currently belongs to.
+    let mut assignment: Vec<usize> = (0..level.adjacency.len()).collect();
+
+    loop {
+        let (membership, moved) = local_moving(&level, resolution, rng);
+        if !moved {
+            break;
+        }
+        let (membership, count) = renumber(&membership);
+        for community in assignment.iter_mut() {
+            *community = membership[*community];
+        }
+        level = level.aggregate(&membership, count);
+    }
+
+    renumber(&assignment).0
+}
+
+/// Map per-node-index community labels back to author ids.
+fn to_partition(graph: &AuthorGraph, membership: &[usize]) -> Partition {
+    membership
+        .iter()
+        .enumerate()
+        .map(|(index, &community)| (graph[NodeIndex::new(index)], community))
+        .collect()
+}
+
+/// Weighted adjacency of a (possibly aggregated) graph. Self-loops are stored once with
+/// twice their weight, so a node's strength is always the sum of its row.
+#[derive(Clone)]
+struct LevelGraph {
+    adjacency: Vec<Vec<(usize, f64)>>,
+    strength: Vec<f64>,
+    total_strength: f64,
+}
+
+impl LevelGraph {
+    fn from_graph(graph: &AuthorGraph) -> Self {
+        let mut adjacency = vec![Vec::new(); graph.node_count()];
+        for edge in graph.edge_references() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            adjacency[a].push((b, 1.0));
+            adjacency[b].push((a, 1.0));
+        }
+        Self::from_adjacency(adjacency)
+    }
+
+    fn from_adjacency(adjacency: Vec<Vec<(usize, f64)>>) -> Self {
+        let strength: Vec<f64> = adjacency
+            .iter()
+            .map(|row| row.iter().map(|&(_, w)| w).sum())
+            .collect();
+        let total_strength = strength.iter().sum();
+        LevelGraph {
+            adjacency,
+            strength,
+            total_strength,
+        }
+    }
+
+    /// Collapse each community into a single node.
+    fn aggregate(&self, membership: &[usize], count: usize) -> Self {
+        // BTreeMap keeps neighbour order, and with it the result, independent of hashing.
+        let mut weights: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); count];
+        for (node, row) in self.adjacency.iter().enumerate() {
+            for &(other, weight) in row {
+                *weights[membership[node]].entry(membership[other]).or_insert(0.0) += weight;
+            }
+        }
+        let adjacency = weights
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect();
+        Self::from_adjacency(adjacency)
+    }
+}
+
+/// Move single nodes between communities until no move improves modularity.
+/// Returns the community of every node and whether anything moved.
+fn local_moving(level: &LevelGraph, resolution: f64, rng: &mut StdRng) -> (Vec<usize>, bool) {
+    local_moving_from(level, (0..level.adjacency.len()).collect(), resolution, rng)
+}
+
+/// [`local_moving`] starting from the given communities (labels below the node count)
+/// instead of singletons.
+fn local_moving_from(
+    level: &LevelGraph,
+    mut membership: Vec<usize>,
+    resolution: f64,
+    rng: &mut StdRng,
+) -> (Vec<usize>, bool) {
+    let n = level.adjacency.len();
+    if level.total_strength == 0.0 {
+        return (membership, false);
+    }
+
+    let mut totals = vec![0.0; n];
+    for (node, &community) in membership.iter().enumerate() {
+        totals[community] += level.strength[node];
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.shuffle(rng);
+
+    let mut links_to = vec![0.0; n];
+    let mut neighbours: Vec<usize> = Vec::new();
+    let mut moved_any = false;
+    loop {
+        let mut moved = false;
+        for &node in &order {
+            let current = membership[node];
+            let strength = level.strength[node];
+
+            for &(other, weight) in &level.adjacency[node] {
+                if other == node {
+                    continue;
+                }
+                let community = membership[other];
+                if links_to[community] == 0.0 {
+                    neighbours.push(community);
+                }
+                links_to[community] += weight;
+            }
+
+            totals[current] -= strength;
+            let gain = |community: usize, links: f64| {
+                links - resolution * totals[community] * strength / level.total_strength
+            };
+            let mut best = current;
+            let mut best_gain = gain(current, links_to[current]);
+            for &community in &neighbours {
+                let candidate = gain(community, links_to[community]);
+                if candidate > best_gain + 1e-12 {
+                    best = community;
+                    best_gain = candidate;
+                }
+            }
+            totals[best] += strength;
+            membership[node] = best;
+            if best != current {
+                moved = true;
+                moved_any = true;
+            }
+
+            for &community in &neighbours {
+                links_to[community] = 0.0;
+            }
+            neighbours.clear();
+        }
+        if !moved {
+            break;
+        }
+    }
+    (membership, moved_any)
+}
+
+/// Leiden community detection (Traag, Waltman & van Eck, 2019) maximizing modularity at
+/// the given resolution.
+///
+/// Like Louvain, but before each aggregation every community is refined: its nodes start
+/// apart and only merge, greedily, with well-connected parts of the same community, and
+/// the refined parts become the nodes of the next level. Communities therefore stay
+/// connected, where Louvain can leave ones split by a node that moved away, and the
+/// partitions found usually have higher modularity. The seed fixes the visiting order.
+pub fn leiden(graph: &AuthorGraph, resolution: f64, seed: u64) -> Partition {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let level = LevelGraph::from_graph(graph);
+    // Each pass starts from the partition of the last, until one no longer improves it.
+    let mut partition = to_partition(graph, &(0..level.adjacency.len()).collect::<Vec<_>>());
+    let mut membership = leiden_pass(level.clone(), (0..level.adjacency.len()).collect(), resolution, &mut rng);
+    let mut quality = f64::NEG_INFINITY;
+    loop {
+        let next = to_partition(graph, &membership);
+        let next_quality = modularity(graph, &next, resolution);
+        if next_quality <= quality + 1e-12 {
+            return partition;
+        }
+        (partition, quality) = (next, next_quality);
+        membership = leiden_pass(level.clone(), membership, resolution, &mut rng);
+    }
+}
+
+/// One Leiden pass from the given communities; returns the community of every node of
+/// the starting level, renumbered.
+fn leiden_pass(mut level: LevelGraph, mut membership: Vec<usize>, resolution: f64, rng: &mut StdRng) -> Vec<usize> {
+    let mut assignment: Vec<usize> = (0..level.adjacency.len()).collect();
+    loop {
+        let (moved, _) = local_moving_from(&level, membership, resolution, rng);
+        let (communities, count) = renumber(&moved);
+        let refined = refine(&level, &communities, resolution, rng);
+        let (refined, refined_count) = renumber(&refined);
+        // Done once every community is a single node, or refinement merged nothing.
+        if count == level.adjacency.len() || refined_count == level.adjacency.len() {
+            let final_membership: Vec<usize> = assignment.iter().map(|&node| communities[node]).collect();
+            return renumber(&final_membership).0;
+        }
+        for node in assignment.iter_mut() {
+            *node = refined[*node];
+        }
+        // Each refined part starts the next level in the community it was refined from.
+        membership = vec![0; refined_count];
+        for (node, &part) in refined.iter().enumerate() {
+            membership[part] = communities[node];
+        }
+        level = level.aggregate(&refined, refined_count);
+    }
+}
+
+/// Community detection algorithm, as chosen on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Louvain,
+    Leiden,
+}
+
+impl Method {
+    pub fn detect(self, graph: &AuthorGraph, resolution: f64, seed: u64) -> Partition {
+        match self {
+            Method::Louvain => louvain(graph, resolution, seed),
+            Method::Leiden => leiden(graph, resolution, seed),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Method::Louvain => "louvain",
+            Method::Leiden => "leiden",
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Method, String> {
+        match name {
+            "louvain" => Ok(Method::Louvain),
+            "leiden" => Ok(Method::Leiden),
+            _ => Err(format!("unknown community detection method: {} (expected louvain or leiden)", name)),
+        }
+    }
+}
+
+/// Leiden refinement of `communities`: within each community, nodes visited in random
+/// order leave their singleton for the part giving the largest modularity gain, if it is
+/// not negative. Only nodes, and parts, with at least the expected number of links to the
+/// rest of their community take part.
+fn refine(level: &LevelGraph, communities: &[usize], resolution: f64, rng: &mut StdRng) -> Vec<usize> {
+    let n = level.adjacency.len();
+    let mut refined: Vec<usize> = (0..n).collect();
+    if level.total_strength == 0.0 {
+        return refined;
+    }
+    let mut community_strength = vec![0.0; n];
+    for node in 0..n {
+        community_strength[communities[node]] += level.strength[node];
+    }
+    // Strength of each part, and its link weight to the rest of its community.
+    let mut strength = level.strength.clone();
+    let mut external: Vec<f64> = (0..n)
+        .map(|node| {
+            let row = &level.adjacency[node];
+            row.iter().filter(|&&(other, _)| other != node && communities[other] == communities[node]).map(|&(_, w)| w).sum()
+        })
+        .collect();
+    let well_connected = |links: f64, part: f64, whole: f64| links >= resolution * part * (whole - part) / level.total_strength;
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.shuffle(rng);
+    let mut links_to = vec![0.0; n];
+    let mut parts: Vec<usize> = Vec::new();
+    for node in order {
+        let whole = community_strength[communities[node]];
+        if refined[node] != node || strength[node] != level.strength[node] {
+            continue; // no longer a singleton
+        }
+        if !well_connected(external[node], strength[node], whole) {
+            continue;
+        }
+        for &(other, weight) in &level.adjacency[node] {
+            if other == node || communities[other] != communities[node] {
+                continue;
+            }
+            let part = refined[other];
+            if links_to[part] == 0.0 {
+                parts.push(part);
+            }
+            links_to[part] += weight;
+        }
+        let mut best = None;
+        let mut best_gain = 0.0;
+        for &part in &parts {
+            if !well_connected(external[part], strength[part], whole) {
+                continue;
+            }
+            let gain = links_to[part] - resolution * strength[part] * level.strength[node] / level.total_strength;
+            if gain >= best_gain {
+                best = Some(part);
+                best_gain = gain;
+            }
+        }
+        if let Some(part) = best {
+            refined[node] = part;
+            external[part] += external[node] - 2.0 * links_to[part];
+            strength[part] += level.strength[node];
+            strength[node] = 0.0;
+        }
+        for &part in &parts {
+            links_to[part] = 0.0;
+        }
+        parts.clear();
+    }
+    refined
+}
+
+/// Relabel communities as 0, 1, 2, ... in order of first appearance.
+fn renumber(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut ids = HashMap::new();
+    let relabeled = labels
+        .iter()
+        .map(|label| {
+            let next = ids.len();
+            *ids.entry(*label).or_insert(next)
+        })
+        .collect();
+    (relabeled, ids.len())
+}
+
+/// Outcome of running community detection at one resolution.
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepPoint {
+    pub resolution: f64,
+    pub communities: usize,
+    /// Classic (resolution 1.0) modularity, comparable across the sweep.
+    pub modularity: f64,
+}
+
+/// `steps` evenly spaced resolutions from `min` to `max` inclusive.
+pub fn resolution_range(min: f64, max: f64, steps: usize) -> Vec<f64> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![min],
+        _ => (0..steps)
+            .map(|i| min + (max - min) * i as f64 / (steps - 1) as f64)
+            .collect(),
+    }
+}
+
+/// Run `method` at each resolution and report community count and modularity.
+pub fn resolution_sweep(graph: &AuthorGraph, method: Method, resolutions: &[f64], seed: u64) -> Vec<SweepPoint> {
+    resolutions
+        .iter()
+        .map(|&resolution| {
+            let partition = method.detect(graph, resolution, seed);
+            SweepPoint {
+                resolution,
+                communities: community_count(&partition),
+                modularity: modularity(graph, &partition, 1.0),
+            }
+        })
+        .collect()
+}
+
+/// Contingency counts between two partitions over the authors they share.
+struct Contingency {
+    joint: HashMap<(usize, usize), usize>,
+    left: HashMap<usize, usize>,
+    right: HashMap<usize, usize>,
+    total: usize,
+}
+
+impl Contingency {
+    fn new(a: &Partition, b: &Partition) -> Self {
+        let mut contingency = Contingency {
+            joint: HashMap::new(),
+            left: HashMap::new(),
+            right: HashMap::new(),
+            total: 0,
+        };
+        for (author, &ca) in a {
+            if let Some(&cb) = b.get(author) {
+                *contingency.joint.entry((ca, cb)).or_insert(0) += 1;
+                *contingency.left.entry(ca).or_insert(0) += 1;
+                *contingency.right.entry(cb).or_insert(0) += 1;
+                contingency.total += 1;
+            }
+        }
+        contingency
+    }
+}
+
+fn entropy(counts: &HashMap<usize, usize>, total: f64) -> f64 {
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.ln()
+        })
+        .sum()
+}
+
+/// Normalized mutual information (arithmetic-mean normalization) between two partitions,
+/// computed over the authors present in both. 1.0 means identical up to relabeling.
+pub fn normalized_mutual_information(a: &Partition, b: &Partition) -> f64 {
+    let contingency = Contingency::new(a, b);
+    let n = contingency.total as f64;
+    if contingency.total == 0 {
+        return 1.0;
+    }
+
+    let (h_a, h_b) = (entropy(&contingency.left, n), entropy(&contingency.right, n));
+    if h_a + h_b == 0.0 {
+        return 1.0;
+    }
+    let mutual: f64 = contingency
+        .joint
+        .iter()
+        .map(|(&(ca, cb), &count)| {
+            let count = count as f64;
+            let expected = contingency.left[&ca] as f64 * contingency.right[&cb] as f64;
+            count / n * (n * count / expected).ln()
+        })
+        .sum();
+    (2.0 * mutual / (h_a + h_b)).clamp(0.0, 1.0)
+}
+
+/// Adjusted Rand index between two partitions over the authors present in both.
+/// 1.0 means identical, values around 0.0 mean chance-level agreement.
+pub fn adjusted_rand_index(a: &Partition, b: &Partition) -> f64 {
+    let pairs = |count: usize| (count * count.saturating_sub(1)) as f64 / 2.0;
+    let contingency = Contingency::new(a, b);
+
+    let index: f64 = contingency.joint.values().map(|&c| pairs(c)).sum();
+    let sum_left: f64 = contingency.left.values().map(|&c| pairs(c)).sum();
+    let sum_right: f64 = contingency.right.values().map(|&c| pairs(c)).sum();
+    let total_pairs = pairs(contingency.total);
+    if total_pairs == 0.0 {
+        return 1.0;
+    }
+
+    let expected = sum_left * sum_right / total_pairs;
+    let maximum = (sum_left + sum_right) / 2.0;
+    if maximum == expected {
+        return 1.0;
+    }
+    (index - expected) / (maximum - expected)
+}
+
+/// Agreement between repeated community detection runs.
+#[derive(Debug, Clone)]
+pub struct Stability {
+    pub mean_nmi: f64,
+    pub min_nmi: f64,
+    pub mean_ari: f64,
+    pub min_ari: f64,
+}
+
+/// Pairwise NMI/ARI statistics over all pairs of runs.
+pub fn stability(runs: &[Partition]) -> Stability {
+    let mut nmi = Vec::new();
+    let mut ari = Vec::new();
+    for (i, a) in runs.iter().enumerate() {
+        for b in &runs[i + 1..] {
+            nmi.push(normalized_mutual_information(a, b));
+            ari.push(adjusted_rand_index(a, b));
+        }
+    }
+    let mean = |values: &[f64]| {
+        if values.is_empty() {
+            1.0
+        } else {
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+    };
+    let min = |values: &[f64]| values.iter().copied().fold(1.0, f64::min);
+    Stability {
+        mean_nmi: mean(&nmi),
+        min_nmi: min(&nmi),
+        mean_ari: mean(&ari),
+        min_ari: min(&ari),
+    }
+}
+
+/// Consensus partition over several seeded detection runs.
+#[derive(Debug, Clone)]
+pub struct Consensus {
+    pub partition: Partition,
+    pub runs: Vec<Partition>,
+    pub stability: Stability,
+}
+
+/// Run `method` `runs` times with seeds `seed, seed + 1, ...` and combine the results.
+///
+/// Each edge is weighted by the fraction of runs that put its endpoints in the same
+/// community; edges below `threshold` are dropped and Louvain is run once more on the
+/// weighted co-clustering graph to obtain the consensus partition.
+pub fn consensus_communities(
+    graph: &AuthorGraph,
+    method: Method,
+    runs: usize,
+    resolution: f64,
+    seed: u64,
+    threshold: f64,
+) -> Consensus {
+    let partitions: Vec<Partition> = (0..runs as u64)
+        .map(|run| method.detect(graph, resolution, seed.wrapping_add(run)))
+        .collect();
+
+    let mut adjacency = vec![Vec::new(); graph.node_count()];
+    for edge in graph.edge_references() {
+        let (a, b) = (graph[edge.source()], graph[edge.target()]);
+        let together = partitions.iter().filter(|p| p[&a] == p[&b]).count();
+        let weight = together as f64 / runs.max(1) as f64;
+        if weight > 0.0 && weight >= threshold {
+            let (i, j) = (edge.source().index(), edge.target().index());
+            adjacency[i].push((j, weight));
+            adjacency[j].push((i, weight));
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let membership = louvain_levels(LevelGraph::from_adjacency(adjacency), resolution, &mut rng);
+    Consensus {
+        partition: to_partition(graph, &membership),
+        stability: stability(&partitions),
+        runs: partitions,
+    }
+}
+
+/// Load a SNAP ground-truth community file: one community per line, listing its
+/// members separated by whitespace. Lines starting with `#` are comments.
+pub fn load_ground_truth<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<usize>>> {
+    let file = File::open(path)?;
+    let mut communities = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Some(fields) = line_fields(&line, false) else {
+            continue;
+        };
+        let members: Vec<usize> = fields
+            .filter_map(|x| x.parse::<usize>().ok())
+            .collect();
+        if !members.is_empty() {
+            communities.push(members);
+        }
+    }
+    Ok(communities)
+}
+
+/// Load a partition written by this or another tool: one `author community` pair per line,
+/// separated by commas or whitespace, e.g. `author,community` CSV from `write_partition`.
+/// Community labels may be any strings and are numbered in order of first appearance;
+/// lines whose author is not an id, such as a header, are skipped. An author listed
+/// twice keeps their first community.
+pub fn load_partition<P: AsRef<Path>>(path: P) -> io::Result<Partition> {
+    let file = File::open(path)?;
+    let mut labels: HashMap<String, usize> = HashMap::new();
+    let mut partition = Partition::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Some(mut fields) = line_fields(&line, true) else {
+            continue;
+        };
+        let (Some(Ok(author)), Some(label)) = (fields.next().map(str::parse::<usize>), fields.next()) else {
+            continue;
+        };
+        let next = labels.len();
+        let community = *labels.entry(label.to_string()).or_insert(next);
+        partition.entry(author).or_insert(community);
+    }
+    Ok(partition)
+}
+
+/// Flatten (possibly overlapping) ground-truth communities into a partition.
+///
+/// An author listed in several communities is assigned to the first one, so that
+/// partition comparison metrics apply; `overlapping_authors` counts how often this happens.
+pub fn ground_truth_partition(communities: &[Vec<usize>]) -> Partition {
+    let mut partition = Partition::new();
+    for (community, members) in communities.iter().enumerate() {
+        for &author in members {
+            partition.entry(author).or_insert(community);
+        }
+    }
+    partition
+}
+
+/// Number of authors appearing in more than one ground-truth community.
+pub fn overlapping_authors(communities: &[Vec<usize>]) -> usize {
+    let mut memberships: HashMap<usize, usize> = HashMap::new();
+    for members in communities {
+        for &author in members {
+            *memberships.entry(author).or_insert(0) += 1;
+        }
+    }
+    memberships.values().filter(|&&count| count > 1).count()
+}
+
+/// Agreement between detected communities and a ground-truth partition.
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub nmi: f64,
+    pub ari: f64,
+    /// Authors present in both partitions; the metrics are computed over these only.
+    pub shared_authors: usize,
+    pub detected_communities: usize,
+    pub true_communities: usize,
+}
+
+/// Compare detected communities against ground truth with NMI and ARI.
+pub fn evaluate_partition(detected: &Partition, truth: &Partition) -> Evaluation {
+    let shared: Partition = detected
+        .iter()
+        .filter(|(author, _)| truth.contains_key(author))
+        .map(|(&author, &community)| (author, community))
+        .collect();
+    let shared_truth: Partition = truth
+        .iter()
+        .filter(|(author, _)| detected.contains_key(author))
+        .map(|(&author, &community)| (author, community))
+        .collect();
+    Evaluation {
+        nmi: normalized_mutual_information(&shared, &shared_truth),
+        ari: adjusted_rand_index(&shared, &shared_truth),
+        shared_authors: shared.len(),
+        detected_communities: community_count(&shared),
+        true_communities: community_count(&shared_truth),
+    }
+}
+
+/// Write a partition as `author,community` CSV rows, sorted by author.
+pub fn write_partition<P: AsRef<Path>>(partition: &Partition, path: P) -> io::Result<()> {
+    write_partition_with(partition, path, ExistingOutput::Overwrite)
+}
+
+/// Write the partition, applying `existing` if the file is already there.
+pub fn write_partition_with<P: AsRef<Path>>(partition: &Partition, path: P, existing: ExistingOutput) -> io::Result<()> {
+    let mut rows: Vec<_> = partition.iter().collect();
+    rows.sort();
+    let mut writer = existing.csv_writer(path)?;
+    writer.write_record(["author", "community"])?;
+    for (author, community) in rows {
+        writer.write_record([author.to_string(), community.to_string()])?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two triangles joined by a single bridge edge.
+    fn two_triangles() -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (1..=6).map(|author| graph.add_node(author)).collect();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_community_sizes() {
+        let partition: Partition = [(1, 7), (2, 7), (3, 7), (4, 8), (5, 9), (6, 9)].into_iter().collect();
+        let sizes = community_sizes(&partition);
+        assert_eq!(sizes.into_iter().collect::<Vec<_>>(), vec![(1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn test_modularity_of_two_triangles() {
+        let graph = two_triangles();
+        let partition: Partition = (1..=6).map(|author| (author, usize::from(author > 3))).collect();
+        assert!((modularity(&graph, &partition, 1.0) - 5.0 / 14.0).abs() < 1e-12);
+
+        let single: Partition = (1..=6).map(|author| (author, 0)).collect();
+        assert!(modularity(&graph, &single, 1.0).abs() < 1e-12);
+
+        // With unit weights the weighted variant agrees; weighting the bridge 3-4 by 8
+        // gives 2m = 28 and strengths 2, 2, 10 per triangle: Q = 2 (6/28 - (14/28)^2).
+        let unit = graph.map(|_, &author| author, |_, _| 1usize);
+        assert!((weighted_modularity(&unit, |&w| w as f64, &partition, 1.0) - 5.0 / 14.0).abs() < 1e-12);
+        let bridged = graph.map(|_, &author| author, |edge, _| if edge.index() == 6 { 8.0 } else { 1.0 });
+        assert!((weighted_modularity(&bridged, |&w| w, &partition, 1.0) - (12.0 / 28.0 - 0.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_load_partition() {
+        let path = std::env::temp_dir().join("load_partition_test.csv");
+        std::fs::write(&path, "author,community\n1,a\n2,a\n3,a\n4 b\n5,b\n6,b\n1,b\n").unwrap();
+        let partition = load_partition(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(partition.len(), 6);
+        assert_eq!((partition[&1], partition[&3], partition[&4], partition[&6]), (0, 0, 1, 1));
+        assert!((modularity(&two_triangles(), &partition, 1.0) - 5.0 / 14.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_louvain_finds_triangles() {
+        let graph = two_triangles();
+        let partition = louvain(&graph, 1.0, 7);
+        assert_eq!(community_count(&partition), 2);
+        assert_eq!(partition[&1], partition[&3]);
+        assert_ne!(partition[&1], partition[&4]);
+        assert_eq!(partition[&4], partition[&6]);
+    }
+
+    #[test]
+    fn test_leiden_finds_connected_communities() {
+        let partition = leiden(&two_triangles(), 1.0, 7);
+        assert_eq!(community_count(&partition), 2);
+        assert!(partition[&1] == partition[&3] && partition[&4] == partition[&6] && partition[&1] != partition[&4]);
+
+        // A ring of eight 5-cliques, neighbours joined by one collaboration: each clique
+        // is a community, at least as good as Louvain's partition.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..40).map(|author| graph.add_node(author)).collect();
+        for clique in 0..8 {
+            for a in 0..5 {
+                for b in a + 1..5 {
+                    graph.add_edge(nodes[clique * 5 + a], nodes[clique * 5 + b], ());
+                }
+            }
+            graph.add_edge(nodes[clique * 5 + 4], nodes[(clique * 5 + 5) % 40], ());
+        }
+        for seed in 0..5 {
+            let partition = leiden(&graph, 1.0, seed);
+            assert_eq!(community_count(&partition), 8);
+            assert!((0..40).all(|author| partition[&author] == partition[&(author / 5 * 5)]));
+            let quality = modularity(&graph, &partition, 1.0);
+            assert!(quality >= modularity(&graph, &louvain(&graph, 1.0, seed), 1.0) - 1e-12);
+        }
+        assert_eq!("leiden".parse::<Method>(), Ok(Method::Leiden));
+    }
+
+    #[test]
+    fn test_partition_agreement() {
+        let a: Partition = [(1, 0), (2, 0), (3, 1), (4, 1), (5, 2), (6, 2)].into_iter().collect();
+        let relabeled: Partition = a.iter().map(|(&author, &c)| (author, 10 - c)).collect();
+        assert!((normalized_mutual_information(&a, &relabeled) - 1.0).abs() < 1e-12);
+        assert!((adjusted_rand_index(&a, &relabeled) - 1.0).abs() < 1e-12);
+
+        let b: Partition = [(1, 0), (2, 0), (3, 0), (4, 1), (5, 1), (6, 1)].into_iter().collect();
+        assert!((normalized_mutual_information(&a, &b) - 0.5158037429793889).abs() < 1e-9);
+        assert!((adjusted_rand_index(&a, &b) - 0.24242424242424243).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_consensus_is_stable_on_clear_structure() {
+        let graph = two_triangles();
+        for method in [Method::Louvain, Method::Leiden] {
+            let consensus = consensus_communities(&graph, method, 5, 1.0, 3, 0.5);
+            assert_eq!(consensus.runs.len(), 5);
+            assert_eq!(community_count(&consensus.partition), 2);
+            assert!((consensus.stability.mean_nmi - 1.0).abs() < 1e-12);
+            assert!((consensus.stability.min_ari - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ground_truth_evaluation() {
+        let path = "ground_truth_test.cmty.txt";
+        std::fs::write(path, "# SNAP communities\n1\t2\t3\n4\t5\t6\t3\n").unwrap();
+        let communities = load_ground_truth(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(communities, vec![vec![1, 2, 3], vec![4, 5, 6, 3]]);
+        assert_eq!(overlapping_authors(&communities), 1);
+        let truth = ground_truth_partition(&communities);
+        assert_eq!(truth[&3], 0);
+
+        let detected = louvain(&two_triangles(), 1.0, 7);
+        let evaluation = evaluate_partition(&detected, &truth);
+        assert_eq!(evaluation.shared_authors, 6);
+        assert_eq!(evaluation.true_communities, 2);
+        assert!((evaluation.nmi - 1.0).abs() < 1e-12);
+        assert!((evaluation.ari - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_resolution_sweep() {
+        let graph = two_triangles();
+        for method in [Method::Louvain, Method::Leiden] {
+            let sweep = resolution_sweep(&graph, method, &resolution_range(0.01, 1.0, 3), 1);
+            assert_eq!(sweep.len(), 3);
+            assert_eq!(sweep[0].communities, 1);
+            assert_eq!(sweep[2].communities, 2);
+            assert!(sweep[2].modularity > sweep[0].modularity);
+        }
+    }
+}
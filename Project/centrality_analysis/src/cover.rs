@@ -0,0 +1,130 @@
+use crate::AuthorGraph;
+use petgraph::algo;
+use petgraph::graph::NodeIndex;
+use std::collections::BTreeSet;
+
+/// An approximate solution together with a bound on the optimum.
+#[derive(Debug, Clone)]
+pub struct Approximation {
+    /// Chosen authors in ascending order.
+    pub members: Vec<usize>,
+    /// Lower bound on the optimum for a minimization problem, upper bound for a maximization.
+    pub bound: usize,
+}
+
+/// Approximate a minimum vertex cover: a set of authors touching every collaboration.
+///
+/// Takes the smaller of the pruned endpoints of a maximum matching (at most twice the
+/// optimum) and the complement of a greedy independent set. The maximum matching size is
+/// a lower bound, since every matched pair needs its own cover vertex.
+pub fn vertex_cover(graph: &AuthorGraph) -> Approximation {
+    let matching = algo::maximum_matching(graph);
+    let mut in_cover: Vec<bool> = graph.node_indices().map(|node| matching.contains_node(node)).collect();
+    // Drop cover vertices whose neighbours are all covered anyway, low degree first.
+    let mut by_degree: Vec<NodeIndex> = graph.node_indices().filter(|node| in_cover[node.index()]).collect();
+    by_degree.sort_by_key(|&node| graph.neighbors(node).count());
+    for node in by_degree {
+        if graph.neighbors(node).all(|other| in_cover[other.index()]) {
+            in_cover[node.index()] = false;
+        }
+    }
+
+    let independent = greedy_independent_set(graph);
+    let complement: Vec<bool> = independent.iter().map(|&chosen| !chosen).collect();
+    let count = |set: &[bool]| set.iter().filter(|&&v| v).count();
+    let best = if count(&complement) < count(&in_cover) { complement } else { in_cover };
+    Approximation { members: authors(graph, &best), bound: matching.len() }
+}
+
+/// Approximate a maximum independent set: authors of whom no two have collaborated.
+///
+/// Greedily picks a minimum-degree author and discards its neighbours, which guarantees
+/// at least the Caro–Wei bound of sum 1 / (degree + 1). The optimum is at most the number
+/// of authors minus the maximum matching size.
+pub fn independent_set(graph: &AuthorGraph) -> Approximation {
+    let matching = algo::maximum_matching(graph);
+    Approximation {
+        members: authors(graph, &greedy_independent_set(graph)),
+        bound: graph.node_count() - matching.len(),
+    }
+}
+
+/// Membership flags of a greedy minimum-degree independent set.
+fn greedy_independent_set(graph: &AuthorGraph) -> Vec<bool> {
+    let n = graph.node_count();
+    let mut degree: Vec<usize> = graph.node_indices().map(|node| graph.neighbors(node).count()).collect();
+    let mut queue: BTreeSet<(usize, usize)> = degree.iter().enumerate().map(|(i, &d)| (d, i)).collect();
+    let mut removed = vec![false; n];
+    let mut chosen = vec![false; n];
+    while let Some((_, i)) = queue.pop_first() {
+        chosen[i] = true;
+        removed[i] = true;
+        for neighbour in graph.neighbors(NodeIndex::new(i)) {
+            let j = neighbour.index();
+            if removed[j] {
+                continue;
+            }
+            removed[j] = true;
+            queue.remove(&(degree[j], j));
+            // Authors two steps away lose a neighbour.
+            for second in graph.neighbors(neighbour) {
+                let k = second.index();
+                if !removed[k] && queue.remove(&(degree[k], k)) {
+                    degree[k] -= 1;
+                    queue.insert((degree[k], k));
+                }
+            }
+        }
+    }
+    chosen
+}
+
+fn authors(graph: &AuthorGraph, flags: &[bool]) -> Vec<usize> {
+    let mut members: Vec<usize> = flags
+        .iter()
+        .enumerate()
+        .filter(|&(_, &chosen)| chosen)
+        .map(|(i, _)| graph[NodeIndex::new(i)])
+        .collect();
+    members.sort_unstable();
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::visit::EdgeRef;
+
+    fn path(n: usize) -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..n).map(|author| graph.add_node(author)).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_vertex_cover_covers_every_edge() {
+        let graph = path(5);
+        let cover = vertex_cover(&graph);
+        assert!(graph
+            .edge_references()
+            .all(|e| cover.members.contains(&graph[e.source()]) || cover.members.contains(&graph[e.target()])));
+        // The optimum for a 5-path is 2, and matching gives the same lower bound.
+        assert_eq!(cover.members.len(), 2);
+        assert_eq!(cover.bound, 2);
+    }
+
+    #[test]
+    fn test_independent_set_within_bound() {
+        let graph = path(5);
+        let independent = independent_set(&graph);
+        assert_eq!(independent.members, vec![0, 2, 4]);
+        assert_eq!(independent.bound, 3);
+        for edge in graph.edge_references() {
+            let both = [edge.source(), edge.target()].iter().all(|&n| independent.members.contains(&graph[n]));
+            assert!(!both);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{largest_component, AuthorGraph, EdgeWeights, WeightedAuthorGraph};
+use petgraph::algo::dijkstra;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+/// Diameter and radius of the largest connected component, in hops or, for
+/// [`weighted_extent`], in total edge length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent<D = usize> {
+    /// Largest eccentricity; a lower bound when not exact.
+    pub diameter: D,
+    /// Smallest eccentricity; an upper bound when not exact.
+    pub radius: D,
+    /// Whether every eccentricity was computed rather than estimated by a double sweep.
+    pub exact: bool,
+}
+
+/// Eccentricity of every author of the largest component: their distance to the author
+/// farthest from them. Eccentricities in other components would not be comparable, so
+/// those authors are left out. One BFS per author, in O(nodes * edges) time.
+pub fn eccentricities(graph: &AuthorGraph) -> HashMap<usize, usize> {
+    largest_component(graph)
+        .into_iter()
+        .map(|node| (graph[node], farthest(graph, node).1))
+        .collect()
+}
+
+/// Diameter and radius of the largest component, exactly from [`eccentricities`] or,
+/// for inputs too big for a BFS per author, by a double sweep: a BFS from the author
+/// with most collaborators finds a far author `u`, whose eccentricity bounds the
+/// diameter from below (and is usually equal to it on real networks), and the author
+/// halfway along the path from `u` to the farthest author from it bounds the radius from
+/// above. Three BFS in total.
+pub fn extent(graph: &AuthorGraph, exact: bool) -> Extent {
+    if exact {
+        let eccentricity = eccentricities(graph);
+        return Extent {
+            diameter: eccentricity.values().copied().max().unwrap_or(0),
+            radius: eccentricity.values().copied().min().unwrap_or(0),
+            exact,
+        };
+    }
+    let component = largest_component(graph);
+    let Some(&start) = component.iter().max_by_key(|&&node| (graph.neighbors(node).count(), Reverse(node))) else {
+        return Extent { diameter: 0, radius: 0, exact };
+    };
+    let (first, start_eccentricity, _) = farthest(graph, start);
+    let (far, diameter, parent) = farthest(graph, first);
+    let mut middle = far;
+    for _ in 0..diameter / 2 {
+        middle = parent[middle.index()];
+    }
+    let radius = farthest(graph, middle).1.min(start_eccentricity).min(diameter);
+    Extent { diameter, radius, exact }
+}
+
+/// Eccentricities of [`eccentricities`] over edge lengths, the weights read as `weights`
+/// says, by Dijkstra's algorithm from every author of the largest component.
+pub fn weighted_eccentricities(graph: &WeightedAuthorGraph, weights: EdgeWeights) -> HashMap<usize, f64> {
+    let graph = &weights.lengths(graph);
+    largest_component(graph)
+        .into_iter()
+        .map(|node| (graph[node], farthest_weighted(graph, node).1))
+        .collect()
+}
+
+/// [`extent`] over edge lengths: exactly from [`weighted_eccentricities`], or by the same
+/// double sweep of three Dijkstra searches, the radius bound taken from the author of the
+/// sweep's longest shortest path closest to its midpoint.
+pub fn weighted_extent(graph: &WeightedAuthorGraph, weights: EdgeWeights, exact: bool) -> Extent<f64> {
+    if exact {
+        let eccentricity = weighted_eccentricities(graph, weights);
+        return Extent {
+            diameter: eccentricity.values().copied().reduce(f64::max).unwrap_or(0.0),
+            radius: eccentricity.values().copied().reduce(f64::min).unwrap_or(0.0),
+            exact,
+        };
+    }
+    let graph = &weights.lengths(graph);
+    let component = largest_component(graph);
+    let Some(&start) = component.iter().max_by_key(|&&node| (graph.neighbors(node).count(), Reverse(node))) else {
+        return Extent { diameter: 0.0, radius: 0.0, exact };
+    };
+    let (first, start_eccentricity, _) = farthest_weighted(graph, start);
+    let (far, diameter, distance) = farthest_weighted(graph, first);
+    // Walk back along a shortest path while that brings the author closer to its midpoint.
+    let mut middle = far;
+    while distance[middle.index()] > diameter / 2.0 {
+        let reached = distance[middle.index()];
+        let previous = graph.edges(middle).find_map(|edge| {
+            let other = if edge.source() == middle { edge.target() } else { edge.source() };
+            let through = distance[other.index()] + edge.weight();
+            ((through - reached).abs() <= 1e-12 * reached && distance[other.index()] < reached).then_some(other)
+        });
+        match previous {
+            Some(previous) if diameter - distance[previous.index()] <= reached => middle = previous,
+            _ => break,
+        }
+    }
+    let radius = farthest_weighted(graph, middle).1.min(start_eccentricity).min(diameter);
+    Extent { diameter, radius, exact }
+}
+
+/// The author farthest from `source` over edge lengths (the lowest index among ties),
+/// their distance and the distance of every author, infinite for those not reached.
+fn farthest_weighted(graph: &WeightedAuthorGraph, source: NodeIndex) -> (NodeIndex, f64, Vec<f64>) {
+    let mut distance = vec![f64::INFINITY; graph.node_count()];
+    for (node, length) in dijkstra(graph, source, None, |edge| *edge.weight()) {
+        distance[node.index()] = length;
+    }
+    let mut far = (source, 0.0);
+    for node in graph.node_indices() {
+        let d = distance[node.index()];
+        if d.is_finite() && d > far.1 {
+            far = (node, d);
+        }
+    }
+    (far.0, far.1, distance)
+}
+
+/// The author farthest from `source` (the lowest index among ties), their distance and
+/// the BFS parent of every reached author.
+fn farthest(graph: &AuthorGraph, source: NodeIndex) -> (NodeIndex, usize, Vec<NodeIndex>) {
+    let mut distance = vec![usize::MAX; graph.node_count()];
+    let mut parent = vec![source; graph.node_count()];
+    distance[source.index()] = 0;
+    let mut queue = VecDeque::from([source]);
+    let mut far = (source, 0);
+    while let Some(node) = queue.pop_front() {
+        let d = distance[node.index()];
+        if d > far.1 || (d == far.1 && node < far.0) {
+            far = (node, d);
+        }
+        for next in graph.neighbors(node) {
+            if distance[next.index()] == usize::MAX {
+                distance[next.index()] = d + 1;
+                parent[next.index()] = node;
+                queue.push_back(next);
+            }
+        }
+    }
+    (far.0, far.1, parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_graph_from_reader, load_weighted_graph_from_reader_audited};
+
+    #[test]
+    fn test_eccentricities_of_a_path_and_a_triangle() {
+        // The path 0-1-2-3-4 with a pendant 5 on 3, and a separate triangle outside the
+        // largest component.
+        let graph = load_graph_from_reader("0 1\n1 2\n2 3\n3 4\n3 5\n6 7\n7 8\n8 6\n".as_bytes()).unwrap();
+        let eccentricity = eccentricities(&graph);
+        assert_eq!(eccentricity.len(), 6);
+        let expected = [(0, 4), (1, 3), (2, 2), (3, 3), (4, 4), (5, 4)];
+        assert!(expected.iter().all(|(author, value)| eccentricity[author] == *value));
+        let exact = extent(&graph, true);
+        assert_eq!((exact.diameter, exact.radius, exact.exact), (4, 2, true));
+        // The double sweep is exact on trees.
+        let swept = extent(&graph, false);
+        assert_eq!((swept.diameter, swept.radius, swept.exact), (4, 2, false));
+    }
+
+    #[test]
+    fn test_double_sweep_bounds() {
+        // A 10-cycle with the chord 0-3 (networkx: diameter 5, radius 4); the estimates must
+        // bound the exact values from the right sides.
+        let mut edges = String::new();
+        for i in 0..10 {
+            edges.push_str(&format!("{} {}\n", i, (i + 1) % 10));
+        }
+        edges.push_str("0 3\n");
+        let graph = load_graph_from_reader(edges.as_bytes()).unwrap();
+        let (exact, swept) = (extent(&graph, true), extent(&graph, false));
+        assert_eq!((exact.diameter, exact.radius), (5, 4));
+        assert!(swept.diameter <= exact.diameter && swept.radius >= exact.radius);
+        assert!(swept.diameter >= exact.radius && swept.radius <= exact.diameter);
+    }
+
+    #[test]
+    fn test_weighted_extent() {
+        // The path 0-1-2-3 with 4 joint papers on 1-2: lengths 1, 1/4 and 1 as
+        // similarities (networkx with distance 1/w: diameter 2.25, radius 1.25).
+        let input = "0 1\n1 2 4\n2 3\n";
+        let graph = load_weighted_graph_from_reader_audited(input.as_bytes(), &Default::default(), &mut Default::default());
+        let graph = graph.unwrap();
+        let eccentricity = weighted_eccentricities(&graph, EdgeWeights::Similarity);
+        assert_eq!((eccentricity[&0], eccentricity[&1]), (2.25, 1.25));
+        for exact in [true, false] {
+            let similarity = weighted_extent(&graph, EdgeWeights::Similarity, exact);
+            assert_eq!((similarity.diameter, similarity.radius), (2.25, 1.25));
+            let distance = weighted_extent(&graph, EdgeWeights::Distance, exact);
+            assert_eq!((distance.diameter, distance.radius), (6.0, 5.0));
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use centrality_analysis::bootstrap::ranks;
+use centrality_analysis::figure::FigureMetadata;
+use centrality_analysis::output::ExistingOutput;
+use centrality_analysis::plots::bar_chart;
+use centrality_analysis::{author_index, top_k, AuthorGraph, Centralities};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Row, Table, TableState, Tabs, Wrap};
+use ratatui::Frame;
+use std::collections::HashMap;
+
+/// What the explorer is showing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum View {
+    /// Every author ranked by the selected measure.
+    Ranking,
+    /// One author's scores and collaborators.
+    Author(usize),
+}
+
+/// State of the interactive explorer, kept apart from the terminal so that the key
+/// handling can be exercised without one.
+pub struct Explorer<'a> {
+    file_path: &'a str,
+    graph: &'a AuthorGraph,
+    measures: Vec<(&'static str, &'a HashMap<usize, f64>)>,
+    /// Authors by descending score and each author's rank, per measure.
+    rankings: Vec<Vec<(usize, f64)>>,
+    ranks: Vec<HashMap<usize, usize>>,
+    measure: usize,
+    view: View,
+    /// Authors viewed before the current one, for going back.
+    history: Vec<View>,
+    table: TableState,
+    /// Author id being typed after `/`, if searching.
+    search: Option<String>,
+    status: String,
+    /// Rows of the bar chart exported with `b`.
+    top: usize,
+    quit: bool,
+}
+
+impl<'a> Explorer<'a> {
+    pub fn new(file_path: &'a str, graph: &'a AuthorGraph, centralities: &'a Centralities, top: usize) -> Explorer<'a> {
+        let measures = centralities.measures();
+        let rankings = measures.iter().map(|(_, scores)| top_k(scores, scores.len())).collect();
+        let ranks = measures.iter().map(|(_, scores)| ranks(scores)).collect();
+        Explorer {
+            file_path,
+            graph,
+            measures,
+            rankings,
+            ranks,
+            measure: 0,
+            view: View::Ranking,
+            history: Vec::new(),
+            table: TableState::default().with_selected(Some(0)),
+            search: None,
+            status: "/ search  Enter open  Backspace back  Left/Right measure  e export CSV  b bar chart  q quit".to_string(),
+            top,
+            quit: false,
+        }
+    }
+
+    /// Authors listed in the current view: the ranking, or the author's collaborators
+    /// ordered by the selected measure.
+    fn rows(&self) -> Vec<(usize, f64)> {
+        match self.view {
+            View::Ranking => self.rankings[self.measure].clone(),
+            View::Author(author) => {
+                let scores = self.measures[self.measure].1;
+                let mut neighbours: Vec<(usize, f64)> = author_index(self.graph, author)
+                    .map(|node| self.graph.neighbors(node).map(|other| self.graph[other]).collect::<Vec<_>>())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|other| (other, scores.get(&other).copied().unwrap_or(0.0)))
+                    .collect();
+                neighbours.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+                neighbours
+            }
+        }
+    }
+
+    fn open(&mut self, author: usize) {
+        if author_index(self.graph, author).is_none() {
+            self.status = format!("Author {} is not in the graph", author);
+            return;
+        }
+        self.history.push(self.view.clone());
+        self.view = View::Author(author);
+        self.table.select(Some(0));
+        self.status = format!("Author {}", author);
+    }
+
+    fn back(&mut self) {
+        if let Some(view) = self.history.pop() {
+            self.view = view;
+            self.table.select(Some(0));
+        }
+    }
+
+    /// React to a key press.
+    pub fn handle(&mut self, key: KeyCode) {
+        if let Some(query) = self.search.as_mut() {
+            match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => query.push(c),
+                KeyCode::Backspace => {
+                    query.pop();
+                }
+                KeyCode::Esc => self.search = None,
+                KeyCode::Enter => {
+                    let query = self.search.take().unwrap_or_default();
+                    match query.parse() {
+                        Ok(author) => self.open(author),
+                        Err(_) => self.status = format!("Invalid author id: {}", query),
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+        let rows = self.rows().len();
+        let selected = self.table.selected().unwrap_or(0);
+        match key {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('/') => self.search = Some(String::new()),
+            KeyCode::Down => self.table.select(Some((selected + 1).min(rows.saturating_sub(1)))),
+            KeyCode::Up => self.table.select(Some(selected.saturating_sub(1))),
+            KeyCode::PageDown => self.table.select(Some((selected + 20).min(rows.saturating_sub(1)))),
+            KeyCode::PageUp => self.table.select(Some(selected.saturating_sub(20))),
+            KeyCode::Right => self.measure = (self.measure + 1) % self.measures.len(),
+            KeyCode::Left => self.measure = (self.measure + self.measures.len() - 1) % self.measures.len(),
+            KeyCode::Enter => {
+                if let Some(&(author, _)) = self.rows().get(selected) {
+                    self.open(author);
+                }
+            }
+            KeyCode::Backspace | KeyCode::Esc => self.back(),
+            KeyCode::Char('e') => self.status = self.export_csv().unwrap_or_else(|e| e),
+            KeyCode::Char('b') => self.status = self.export_bar_chart().unwrap_or_else(|e| e),
+            _ => {}
+        }
+    }
+
+    /// Write the rows of the current view, with every measure, as CSV. Exports replace
+    /// the previous one of the same view, so pressing the key again refreshes it.
+    fn export_csv(&self) -> Result<String, String> {
+        let (name, path) = match self.view {
+            View::Ranking => (self.measures[self.measure].0, format!("output/explore_{}.csv", self.measures[self.measure].0)),
+            View::Author(author) => ("collaborators", format!("output/explore_author_{}.csv", author)),
+        };
+        let mut writer = ExistingOutput::Overwrite.csv_writer(&path).map_err(|e| e.to_string())?;
+        let mut header = vec!["author"];
+        header.extend(self.measures.iter().map(|(measure, _)| *measure));
+        writer.write_record(&header).map_err(|e| e.to_string())?;
+        for (author, _) in self.rows() {
+            let mut record = vec![author.to_string()];
+            record.extend(self.measures.iter().map(|(_, scores)| scores.get(&author).copied().unwrap_or(0.0).to_string()));
+            writer.write_record(&record).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        Ok(format!("Wrote {} {} rows to {}", self.rows().len(), name, path))
+    }
+
+    /// Render the top authors of the current view under the selected measure.
+    fn export_bar_chart(&self) -> Result<String, String> {
+        let measure = self.measures[self.measure].0;
+        let ranking: Vec<(usize, f64)> = self.rows().into_iter().take(self.top).collect();
+        let (title, path) = match self.view {
+            View::Ranking => (
+                format!("Top {} Authors by {}", ranking.len(), measure),
+                format!("output/explore_top_{}.png", measure),
+            ),
+            View::Author(author) => (
+                format!("Top {} Collaborators of Author {} by {}", ranking.len(), author, measure),
+                format!("output/explore_author_{}_{}.png", author, measure),
+            ),
+        };
+        ExistingOutput::Overwrite.prepare(&path).map_err(|e| e.to_string())?;
+        let metadata = FigureMetadata::new(self.file_path).with_parameter("measure", measure).with_parameter("top_k", self.top);
+        bar_chart(&ranking, &title, &path, &metadata).map_err(|e| format!("Failed to render bar chart: {}", e))?;
+        Ok(format!("Bar chart written to {}", path))
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs_area, header_area, table_area, status_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(4), Constraint::Min(3), Constraint::Length(1)])
+                .areas(frame.area());
+
+        let titles: Vec<&str> = self.measures.iter().map(|(name, _)| *name).collect();
+        let tabs = Tabs::new(titles)
+            .select(self.measure)
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .block(Block::default().borders(Borders::ALL).title(format!("Explore {}", self.file_path)));
+        frame.render_widget(tabs, tabs_area);
+
+        let header = match self.view {
+            View::Ranking => format!(
+                "{} authors, {} collaborations, ranked by {}",
+                self.graph.node_count(),
+                self.graph.edge_count(),
+                self.measures[self.measure].0
+            ),
+            View::Author(author) => {
+                let scores: Vec<String> = self
+                    .measures
+                    .iter()
+                    .zip(&self.ranks)
+                    .map(|((name, scores), ranks)| {
+                        let score = scores.get(&author).copied().unwrap_or(0.0);
+                        format!("{} {:.4} (#{})", name, score, ranks.get(&author).unwrap_or(&0))
+                    })
+                    .collect();
+                format!("Author {}: {}", author, scores.join(", "))
+            }
+        };
+        let header = Paragraph::new(header).wrap(Wrap { trim: true }).block(Block::default().borders(Borders::ALL));
+        frame.render_widget(header, header_area);
+
+        let ranks = &self.ranks[self.measure];
+        let rows: Vec<Row> = self
+            .rows()
+            .into_iter()
+            .map(|(author, score)| Row::new(vec![ranks[&author].to_string(), author.to_string(), format!("{:.6}", score)]))
+            .collect();
+        let title = match self.view {
+            View::Ranking => "Ranking".to_string(),
+            View::Author(author) => format!("Collaborators of author {}", author),
+        };
+        let table = Table::new(rows, [Constraint::Length(8), Constraint::Length(10), Constraint::Min(12)])
+            .header(
+                Row::new(vec!["rank", "author", self.measures[self.measure].0])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().bg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+
+        let status = match &self.search {
+            Some(query) => format!("Author id: {}", query),
+            None => self.status.clone(),
+        };
+        frame.render_widget(Line::from(status), status_area);
+    }
+}
+
+/// Run the explorer until the user quits, restoring the terminal afterwards.
+pub fn run(explorer: &mut Explorer) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = (|| -> std::io::Result<()> {
+        while !explorer.quit {
+            terminal.draw(|frame| explorer.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    explorer.handle(key.code);
+                }
+            }
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    result.map_err(|e| format!("Terminal error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use centrality_analysis::{centrality_scores, load_graph_from_reader};
+
+    #[test]
+    fn test_explorer_navigation() {
+        // A star around author 1, with author 5 hanging off author 4.
+        let graph = load_graph_from_reader("1 2\n1 3\n1 4\n4 5\n".as_bytes()).unwrap();
+        let centralities = centrality_scores(&graph);
+        let mut explorer = Explorer::new("star.txt", &graph, &centralities, 3);
+        assert_eq!(explorer.rows()[0].0, 1);
+
+        explorer.handle(KeyCode::Enter);
+        assert_eq!(explorer.view, View::Author(1));
+        // Collaborators by degree: 4 first, then 2 and 3 by id.
+        let collaborators: Vec<usize> = explorer.rows().iter().map(|row| row.0).collect();
+        assert_eq!(collaborators, vec![4, 2, 3]);
+        explorer.handle(KeyCode::Enter);
+        assert_eq!(explorer.view, View::Author(4));
+        explorer.handle(KeyCode::Backspace);
+        explorer.handle(KeyCode::Backspace);
+        assert_eq!(explorer.view, View::Ranking);
+
+        for key in [KeyCode::Char('/'), KeyCode::Char('5'), KeyCode::Enter] {
+            explorer.handle(key);
+        }
+        assert_eq!(explorer.view, View::Author(5));
+        for key in [KeyCode::Char('/'), KeyCode::Char('9'), KeyCode::Enter] {
+            explorer.handle(key);
+        }
+        assert_eq!((explorer.view.clone(), explorer.status.as_str()), (View::Author(5), "Author 9 is not in the graph"));
+        explorer.handle(KeyCode::Char('q'));
+        assert!(explorer.quit);
+    }
+}
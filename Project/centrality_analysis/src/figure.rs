@@ -0,0 +1,251 @@
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Provenance of a generated figure: which dataset, when, and with which parameters.
+#[derive(Debug, Clone)]
+pub struct FigureMetadata {
+    pub dataset: String,
+    pub date: String,
+    pub parameters: Vec<(String, String)>,
+}
+
+impl FigureMetadata {
+    /// Create metadata for a figure built from `dataset_path`, dated today (UTC).
+    pub fn new(dataset_path: &str) -> Self {
+        let dataset = Path::new(dataset_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| dataset_path.to_string());
+        FigureMetadata {
+            dataset,
+            date: today(),
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Record an analysis parameter that influenced the figure.
+    pub fn with_parameter(mut self, key: &str, value: impl ToString) -> Self {
+        self.parameters.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// One-line caption printed at the bottom of the figure.
+    pub fn caption(&self) -> String {
+        let mut caption = format!("Dataset: {} | Generated: {}", self.dataset, self.date);
+        if !self.parameters.is_empty() {
+            caption.push_str(" | ");
+            caption.push_str(&self.parameter_string());
+        }
+        caption
+    }
+
+    fn parameter_string(&self) -> String {
+        self.parameters
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Key/value pairs stored as PNG text chunks.
+    fn text_entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = vec![
+            ("Source", self.dataset.clone()),
+            ("Creation Time", self.date.clone()),
+            ("Software", format!("centrality_analysis {}", env!("CARGO_PKG_VERSION"))),
+        ];
+        if !self.parameters.is_empty() {
+            entries.push(("Parameters", self.parameter_string()));
+        }
+        entries
+    }
+}
+
+/// Draw the metadata caption into `area` (usually a thin strip below the plot).
+pub fn draw_caption<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    metadata: &FigureMetadata,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let style = ("sans-serif", 14).into_font().color(&BLACK.mix(0.7));
+    area.draw(&Text::new(metadata.caption(), (10, 8), style))
+}
+
+/// Embed the metadata into an existing PNG file as `iTXt` chunks.
+///
+/// Chunks previously written for the same keys are replaced, so re-running is safe.
+pub fn embed_metadata<P: AsRef<Path>>(path: P, metadata: &FigureMetadata) -> io::Result<()> {
+    let bytes = fs::read(&path)?;
+    let entries = metadata.text_entries();
+
+    let mut output = PNG_SIGNATURE.to_vec();
+    for chunk in png_chunks(&bytes)? {
+        if chunk.kind == *b"IEND" {
+            for (key, value) in &entries {
+                write_chunk(&mut output, b"iTXt", &itxt_payload(key, value));
+            }
+        } else if chunk.kind == *b"iTXt" || chunk.kind == *b"tEXt" {
+            let key = chunk.data.split(|&b| b == 0).next().unwrap_or_default();
+            if entries.iter().any(|(k, _)| k.as_bytes() == key) {
+                continue;
+            }
+        }
+        output.extend_from_slice(chunk.raw);
+    }
+
+    fs::write(path, output)
+}
+
+/// Read back all `tEXt`/`iTXt` key/value pairs from a PNG file.
+pub fn read_text_chunks<P: AsRef<Path>>(path: P) -> io::Result<Vec<(String, String)>> {
+    let bytes = fs::read(path)?;
+    let mut entries = Vec::new();
+    for chunk in png_chunks(&bytes)? {
+        let mut fields = chunk.data.splitn(2, |&b| b == 0);
+        let key = String::from_utf8_lossy(fields.next().unwrap_or_default()).into_owned();
+        let rest = fields.next().unwrap_or_default();
+        match &chunk.kind {
+            b"tEXt" => entries.push((key, rest.iter().map(|&b| b as char).collect())),
+            b"iTXt" if rest.len() >= 2 && rest[0] == 0 => {
+                // Skip compression flag/method, language tag and translated keyword.
+                let text = rest[2..].splitn(3, |&b| b == 0).nth(2).unwrap_or_default();
+                entries.push((key, String::from_utf8_lossy(text).into_owned()));
+            }
+            _ => {}
+        }
+    }
+    Ok(entries)
+}
+
+/// A chunk of a PNG file: its type, payload, and the raw bytes including length and CRC.
+struct PngChunk<'a> {
+    kind: [u8; 4],
+    data: &'a [u8],
+    raw: &'a [u8],
+}
+
+/// Split a PNG byte stream into its chunks.
+fn png_chunks(bytes: &[u8]) -> io::Result<Vec<PngChunk<'_>>> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    if !bytes.starts_with(&PNG_SIGNATURE) {
+        return Err(invalid("not a PNG file"));
+    }
+
+    let mut chunks = Vec::new();
+    let mut offset = PNG_SIGNATURE.len();
+    while offset < bytes.len() {
+        if offset + 8 > bytes.len() {
+            return Err(invalid("truncated PNG chunk header"));
+        }
+        let length = u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        let end = offset + 12 + length;
+        if end > bytes.len() {
+            return Err(invalid("truncated PNG chunk"));
+        }
+        chunks.push(PngChunk {
+            kind: bytes[offset + 4..offset + 8].try_into().unwrap(),
+            data: &bytes[offset + 8..offset + 8 + length],
+            raw: &bytes[offset..end],
+        });
+        offset = end;
+    }
+    Ok(chunks)
+}
+
+fn itxt_payload(key: &str, value: &str) -> Vec<u8> {
+    // keyword NUL, uncompressed, no language tag, no translated keyword, UTF-8 text
+    let mut payload = key.as_bytes().to_vec();
+    payload.extend_from_slice(&[0, 0, 0, 0, 0]);
+    payload.extend_from_slice(value.as_bytes());
+    payload
+}
+
+fn write_chunk(output: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    output.extend_from_slice(chunk_type);
+    output.extend_from_slice(data);
+    let mut crc_input = chunk_type.to_vec();
+    crc_input.extend_from_slice(data);
+    output.extend_from_slice(&crc32(&crc_input).to_be_bytes());
+}
+
+/// CRC-32 as used by PNG (ISO 3309, reflected polynomial 0xEDB88320).
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Today's date in UTC as YYYY-MM-DD.
+fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    civil_date((secs / 86_400) as i64)
+}
+
+/// Convert days since 1970-01-01 into a calendar date (Hinnant's algorithm).
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_reference_value() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(20_740), "2026-10-14");
+    }
+
+    #[test]
+    fn test_embed_and_read_metadata() {
+        let path = "figure_metadata_test.png";
+        {
+            let root = BitMapBackend::new(path, (32, 32)).into_drawing_area();
+            root.fill(&WHITE).unwrap();
+            root.present().unwrap();
+        }
+
+        let metadata = FigureMetadata::new("data/ca-GrQc.txt").with_parameter("top_k", 10);
+        embed_metadata(path, &metadata).unwrap();
+        // Embedding twice must not duplicate the chunks.
+        embed_metadata(path, &metadata).unwrap();
+
+        let chunks = read_text_chunks(path).unwrap();
+        assert_eq!(chunks.iter().filter(|(k, _)| k == "Source").count(), 1);
+        assert!(chunks.contains(&("Source".to_string(), "ca-GrQc.txt".to_string())));
+        assert!(chunks.contains(&("Parameters".to_string(), "top_k=10".to_string())));
+        assert!(metadata.caption().contains("top_k=10"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
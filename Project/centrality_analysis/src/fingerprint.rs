@@ -0,0 +1,97 @@
+use crate::anf::mix;
+use crate::AuthorGraph;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Weisfeiler–Lehman fingerprint of a graph's structure, independent of edge order and
+/// author ids.
+///
+/// Isomorphic graphs always get the same fingerprint. Different fingerprints prove the
+/// graphs differ; equal ones make isomorphism very likely but not certain, since 1-WL
+/// cannot tell apart some graphs (e.g. regular graphs of the same size and degree).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    pub nodes: usize,
+    pub edges: usize,
+    /// Refinement rounds until the colour classes stopped splitting.
+    pub rounds: usize,
+    pub hash: u64,
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "wl1-{}-{}-{:016x}", self.nodes, self.edges, self.hash)
+    }
+}
+
+/// Order-independent hash of a multiset of labels.
+fn hash_multiset(labels: &mut [u64], seed: u64) -> u64 {
+    labels.sort_unstable();
+    labels.iter().fold(mix(seed), |hash, &label| mix(hash ^ label))
+}
+
+/// Refine node colours with the 1-dimensional Weisfeiler–Lehman test, starting from
+/// degrees, until the number of colour classes stops growing, and hash the colour
+/// histogram of every round.
+pub fn wl_fingerprint(graph: &AuthorGraph) -> Fingerprint {
+    let mut labels: Vec<u64> = graph.node_indices().map(|node| mix(graph.neighbors(node).count() as u64)).collect();
+    let classes = |labels: &[u64]| labels.iter().collect::<HashSet<_>>().len();
+    let mut histograms = vec![hash_multiset(&mut labels.clone(), 0)];
+    let mut count = classes(&labels);
+    let mut rounds = 0;
+    loop {
+        let next: Vec<u64> = graph
+            .node_indices()
+            .map(|node| {
+                let mut neighbours: Vec<u64> = graph.neighbors(node).map(|other| labels[other.index()]).collect();
+                hash_multiset(&mut neighbours, labels[node.index()])
+            })
+            .collect();
+        let next_count = classes(&next);
+        if next_count == count {
+            break;
+        }
+        labels = next;
+        count = next_count;
+        rounds += 1;
+        histograms.push(hash_multiset(&mut labels.clone(), rounds as u64));
+    }
+    let seed = mix(graph.node_count() as u64) ^ graph.edge_count() as u64;
+    let hash = histograms.iter().fold(mix(seed), |hash, &histogram| mix(hash ^ histogram));
+    Fingerprint { nodes: graph.node_count(), edges: graph.edge_count(), rounds, hash }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph_from_reader;
+
+    fn graph(edges: &str) -> AuthorGraph {
+        load_graph_from_reader(edges.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_order_and_ids() {
+        // A path 1-2-3-4 with a triangle 2-3-5, relabelled and shuffled.
+        let original = graph("1 2\n2 3\n3 4\n2 5\n3 5\n");
+        let permuted = graph("30 50\n10 20\n50 40\n20 50\n20 30\n");
+        assert_eq!(wl_fingerprint(&original), wl_fingerprint(&permuted));
+
+        // Same degree sequence, different structure.
+        let path = graph("1 2\n2 3\n3 4\n4 5\n5 6\n");
+        let triangle_and_path = graph("1 2\n2 3\n3 1\n4 5\n5 6\n");
+        assert_ne!(wl_fingerprint(&path), wl_fingerprint(&triangle_and_path));
+
+        // The known blind spot of 1-WL: a 6-cycle and two triangles are both 2-regular.
+        let cycle = graph("1 2\n2 3\n3 4\n4 5\n5 6\n6 1\n");
+        let triangles = graph("1 2\n2 3\n3 1\n4 5\n5 6\n6 4\n");
+        assert_eq!(wl_fingerprint(&cycle), wl_fingerprint(&triangles));
+    }
+
+    #[test]
+    fn test_fingerprint_display() {
+        let triangle = wl_fingerprint(&graph("1 2\n2 3\n3 1\n"));
+        assert_eq!(triangle.rounds, 0);
+        assert!(triangle.to_string().starts_with("wl1-3-3-"));
+    }
+}
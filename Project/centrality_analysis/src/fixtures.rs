@@ -0,0 +1,255 @@
+use crate::AuthorGraph;
+use std::f64::consts::FRAC_1_SQRT_2;
+
+/// A small graph of well-known structure with the exact values of the measures of this
+/// crate on it, computed with networkx and rounded to 12 decimals. Authors are numbered
+/// `0..n` in node index order, and per-author values are indexed by author id.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub graph: AuthorGraph,
+    pub degree: &'static [usize],
+    /// Normalized betweenness, in [0, 1].
+    pub betweenness: &'static [f64],
+    /// Wasserman–Faust closeness.
+    pub closeness: &'static [f64],
+    /// Harmonic closeness divided by `n - 1`.
+    pub harmonic: &'static [f64],
+    /// Principal eigenvector of unit length.
+    pub eigenvector: &'static [f64],
+    /// PageRank with damping 0.85.
+    pub pagerank: &'static [f64],
+    pub clustering: &'static [f64],
+    pub triangles: usize,
+    pub transitivity: f64,
+    /// Degree assortativity; 0 where every author has the same degree and networkx
+    /// returns NaN, as in [`degree_assortativity`](crate::stats::degree_assortativity).
+    pub assortativity: f64,
+    pub diameter: usize,
+}
+
+/// Every fixture, smallest first.
+pub fn fixtures() -> Vec<Fixture> {
+    vec![
+        // The path 0-1-2-3-4.
+        Fixture {
+            name: "path",
+            graph: path(5),
+            degree: &[1, 2, 2, 2, 1],
+            betweenness: &[0.0, 0.5, 0.666666666667, 0.5, 0.0],
+            closeness: &[0.4, 0.571428571429, 0.666666666667, 0.571428571429, 0.4],
+            harmonic: &[0.520833333333, 0.708333333333, 0.75, 0.708333333333, 0.520833333333],
+            eigenvector: &[0.288675134595, 0.5, 0.57735026919, 0.5, 0.288675134595],
+            pagerank: &[0.134527027027, 0.245945945946, 0.239054054054, 0.245945945946, 0.134527027027],
+            clustering: &[0.0, 0.0, 0.0, 0.0, 0.0],
+            triangles: 0,
+            transitivity: 0.0,
+            assortativity: -0.333333333333,
+            diameter: 4,
+        },
+        // The star with hub 0 and leaves 1 to 4.
+        Fixture {
+            name: "star",
+            graph: star(4),
+            degree: &[4, 1, 1, 1, 1],
+            betweenness: &[1.0, 0.0, 0.0, 0.0, 0.0],
+            closeness: &[1.0, 0.571428571429, 0.571428571429, 0.571428571429, 0.571428571429],
+            harmonic: &[1.0, 0.625, 0.625, 0.625, 0.625],
+            eigenvector: &[FRAC_1_SQRT_2, 0.353553390593, 0.353553390593, 0.353553390593, 0.353553390593],
+            pagerank: &[0.475675675676, 0.131081081081, 0.131081081081, 0.131081081081, 0.131081081081],
+            clustering: &[0.0, 0.0, 0.0, 0.0, 0.0],
+            triangles: 0,
+            transitivity: 0.0,
+            assortativity: -1.0,
+            diameter: 2,
+        },
+        // The complete graph on five authors.
+        Fixture {
+            name: "complete",
+            graph: complete(5),
+            degree: &[4, 4, 4, 4, 4],
+            betweenness: &[0.0, 0.0, 0.0, 0.0, 0.0],
+            closeness: &[1.0, 1.0, 1.0, 1.0, 1.0],
+            harmonic: &[1.0, 1.0, 1.0, 1.0, 1.0],
+            eigenvector: &[0.4472135955, 0.4472135955, 0.4472135955, 0.4472135955, 0.4472135955],
+            pagerank: &[0.2, 0.2, 0.2, 0.2, 0.2],
+            clustering: &[1.0, 1.0, 1.0, 1.0, 1.0],
+            triangles: 10,
+            transitivity: 1.0,
+            assortativity: 0.0,
+            diameter: 1,
+        },
+        // Two complete graphs on 0..=3 and 5..=8 joined through author 4 (networkx's
+        // `barbell_graph(4, 1)`).
+        Fixture {
+            name: "barbell",
+            graph: barbell(4, 1),
+            degree: &[3, 3, 3, 4, 2, 4, 3, 3, 3],
+            betweenness: &[0.0, 0.0, 0.0, 0.535714285714, 0.571428571429, 0.535714285714, 0.0, 0.0, 0.0],
+            closeness: &[0.4, 0.4, 0.4, 0.533333333333, 0.571428571429, 0.533333333333, 0.4, 0.4, 0.4],
+            harmonic: &[
+                0.572916666667, 0.572916666667, 0.572916666667, 0.6875, 0.625, 0.6875, 0.572916666667, 0.572916666667,
+                0.572916666667,
+            ],
+            eigenvector: &[
+                0.32752864105, 0.32752864105, 0.32752864105, 0.385635392744, 0.242735707052, 0.385635392744, 0.32752864105,
+                0.32752864105, 0.32752864105,
+            ],
+            pagerank: &[
+                0.10721963454, 0.10721963454, 0.10721963454, 0.140212588081, 0.076257016601, 0.140212588081, 0.10721963454,
+                0.10721963454, 0.10721963454,
+            ],
+            clustering: &[1.0, 1.0, 1.0, 0.5, 0.0, 0.5, 1.0, 1.0, 1.0],
+            triangles: 8,
+            transitivity: 0.774193548387,
+            assortativity: -0.606557377049,
+            diameter: 4,
+        },
+        // Zachary's karate club, 34 members and 78 friendships (networkx's unweighted
+        // `karate_club_graph`).
+        Fixture {
+            name: "karate",
+            graph: karate(),
+            degree: &[
+                16, 9, 10, 6, 3, 4, 4, 4, 5, 2, 3, 1, 2, 5, 2, 2, 2, 2, 2, 3, 2, 2, 2, 5, 3, 3, 2, 4, 3, 4, 4, 6, 12, 17,
+            ],
+            betweenness: &[
+                0.437635281385, 0.053936688312, 0.143656806157, 0.011909271284, 0.000631313131, 0.029987373737,
+                0.029987373737, 0.0, 0.055926827802, 0.000847763348, 0.000631313131, 0.0, 0.0, 0.045863395863, 0.0, 0.0, 0.0,
+                0.0, 0.0, 0.0324750481, 0.0, 0.0, 0.0, 0.017613636364, 0.00220959596, 0.003840488215, 0.0, 0.022333453583,
+                0.001794733045, 0.002922077922, 0.014411976912, 0.138275613276, 0.145247113997, 0.30407497595,
+            ],
+            closeness: &[
+                0.568965517241, 0.485294117647, 0.559322033898, 0.464788732394, 0.379310344828, 0.383720930233,
+                0.383720930233, 0.44, 0.515625, 0.434210526316, 0.379310344828, 0.366666666667, 0.370786516854, 0.515625,
+                0.370786516854, 0.370786516854, 0.284482758621, 0.375, 0.370786516854, 0.5, 0.370786516854, 0.375,
+                0.370786516854, 0.392857142857, 0.375, 0.375, 0.362637362637, 0.458333333333, 0.452054794521, 0.383720930233,
+                0.458333333333, 0.540983606557, 0.515625, 0.55,
+            ],
+            harmonic: &[
+                0.70202020202, 0.580808080808, 0.636363636364, 0.535353535354, 0.444444444444, 0.459595959596,
+                0.459595959596, 0.497474747475, 0.560606060606, 0.472222222222, 0.444444444444, 0.409090909091,
+                0.424242424242, 0.560606060606, 0.430303030303, 0.430303030303, 0.336363636364, 0.429292929293,
+                0.430303030303, 0.530303030303, 0.430303030303, 0.429292929293, 0.430303030303, 0.485858585859,
+                0.421717171717, 0.421717171717, 0.422727272727, 0.512626262626, 0.497474747475, 0.465656565657,
+                0.512626262626, 0.585858585859, 0.633838383838, 0.704545454545,
+            ],
+            eigenvector: &[
+                0.355491444525, 0.265959919552, 0.317192504486, 0.211179720378, 0.075968818183, 0.079483045117,
+                0.079483045117, 0.170959748045, 0.227403907125, 0.102674250724, 0.075968818183, 0.052855697494,
+                0.084254628717, 0.226472720142, 0.10140326219, 0.10140326219, 0.023635628105, 0.092399538196, 0.10140326219,
+                0.147912510293, 0.10140326219, 0.092399538196, 0.10140326219, 0.150118571861, 0.057052440541, 0.059206474917,
+                0.075579413488, 0.13347715338, 0.131077822984, 0.134960819262, 0.174758302314, 0.191033841407,
+                0.308644219791, 0.373363470291,
+            ],
+            pagerank: &[
+                0.096997285388, 0.052876924061, 0.057078509488, 0.035859857786, 0.021977952365, 0.029111154678,
+                0.029111154678, 0.024490497035, 0.029766056081, 0.014309397129, 0.021977952365, 0.009564745492,
+                0.014644892012, 0.029536456152, 0.014535993998, 0.014535993998, 0.016784005444, 0.014558677209,
+                0.014535993998, 0.019604636326, 0.014535993998, 0.014558677209, 0.014535993998, 0.031522514777,
+                0.021076033559, 0.021006197394, 0.015044038083, 0.025639767483, 0.019573459464, 0.026288537695,
+                0.024590155249, 0.037158087069, 0.071693226006, 0.100919182333,
+            ],
+            clustering: &[
+                0.15, 0.333333333333, 0.244444444444, 0.666666666667, 0.666666666667, 0.5, 0.5, 1.0, 0.5, 0.0,
+                0.666666666667, 0.0, 1.0, 0.6, 1.0, 1.0, 1.0, 1.0, 1.0, 0.333333333333, 1.0, 1.0, 1.0, 0.4, 0.333333333333,
+                0.333333333333, 1.0, 0.166666666667, 0.333333333333, 0.666666666667, 0.5, 0.2, 0.19696969697, 0.110294117647,
+            ],
+            triangles: 45,
+            transitivity: 0.255681818182,
+            assortativity: -0.475613097685,
+            diameter: 5,
+        },
+    ]
+}
+
+/// A graph on authors `0..n` with the given collaborations.
+fn from_edges(n: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> AuthorGraph {
+    let mut graph = AuthorGraph::new_undirected();
+    let nodes: Vec<_> = (0..n).map(|author| graph.add_node(author)).collect();
+    for (a, b) in edges {
+        graph.add_edge(nodes[a], nodes[b], ());
+    }
+    graph
+}
+
+/// The path 0-1-...-(n-1).
+pub fn path(n: usize) -> AuthorGraph {
+    from_edges(n, (1..n).map(|b| (b - 1, b)))
+}
+
+/// A star with hub 0 and leaves 1 to `leaves`.
+pub fn star(leaves: usize) -> AuthorGraph {
+    from_edges(leaves + 1, (1..=leaves).map(|leaf| (0, leaf)))
+}
+
+/// The complete graph on `n` authors.
+pub fn complete(n: usize) -> AuthorGraph {
+    from_edges(n, (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b))))
+}
+
+/// Two complete graphs on `clique` authors joined by a path through `bridge` more, numbered
+/// as by networkx's `barbell_graph`: the first clique, the path, then the second clique.
+pub fn barbell(clique: usize, bridge: usize) -> AuthorGraph {
+    let n = 2 * clique + bridge;
+    let second = clique + bridge;
+    let cliques = (0..clique).flat_map(|a| (a + 1..clique).flat_map(move |b| [(a, b), (second + a, second + b)]));
+    let path = (clique - 1..second).map(|a| (a, a + 1));
+    from_edges(n, cliques.chain(path))
+}
+
+/// Zachary's karate club.
+pub fn karate() -> AuthorGraph {
+    from_edges(34, KARATE.iter().copied())
+}
+
+/// Friendships of Zachary's karate club, members numbered from 0.
+const KARATE: [(usize, usize); 78] = [
+    (0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6), (0, 7), (0, 8), (0, 10), (0, 11), (0, 12), (0, 13), (0, 17),
+    (0, 19), (0, 21), (0, 31), (1, 2), (1, 3), (1, 7), (1, 13), (1, 17), (1, 19), (1, 21), (1, 30), (2, 3), (2, 7),
+    (2, 8), (2, 9), (2, 13), (2, 27), (2, 28), (2, 32), (3, 7), (3, 12), (3, 13), (4, 6), (4, 10), (5, 6), (5, 10),
+    (5, 16), (6, 16), (8, 30), (8, 32), (8, 33), (9, 33), (13, 33), (14, 32), (14, 33), (15, 32), (15, 33), (18, 32),
+    (18, 33), (19, 33), (20, 32), (20, 33), (22, 32), (22, 33), (23, 25), (23, 27), (23, 29), (23, 32), (23, 33),
+    (24, 25), (24, 27), (24, 31), (25, 31), (26, 29), (26, 33), (27, 33), (28, 31), (28, 33), (29, 32), (29, 33),
+    (30, 32), (30, 33), (31, 32), (31, 33), (32, 33),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::betweenness::betweenness_centrality;
+    use crate::closeness::{closeness_centrality, harmonic_centrality};
+    use crate::eccentricity::extent;
+    use crate::pagerank::pagerank;
+    use crate::stats::{degree_assortativity, local_clustering, transitivity, triangle_count};
+    use crate::{degree_centrality, eigenvector_centrality};
+    use std::collections::HashMap;
+
+    fn assert_close(fixture: &str, measure: &str, scores: &HashMap<usize, f64>, expected: &[f64], tolerance: f64) {
+        assert_eq!(scores.len(), expected.len(), "{} {}", fixture, measure);
+        for (author, &value) in expected.iter().enumerate() {
+            let score = scores[&author];
+            assert!((score - value).abs() < tolerance, "{} {} of {}: {} instead of {}", fixture, measure, author, score, value);
+        }
+    }
+
+    #[test]
+    fn test_fixtures_match_every_measure() {
+        for fixture in fixtures() {
+            let (name, graph) = (fixture.name, &fixture.graph);
+            let degree = degree_centrality(graph);
+            assert_eq!((0..graph.node_count()).map(|author| degree[&author]).collect::<Vec<_>>(), fixture.degree);
+            assert_close(name, "betweenness", &betweenness_centrality(graph, true), fixture.betweenness, 1e-11);
+            assert_close(name, "closeness", &closeness_centrality(graph), fixture.closeness, 1e-11);
+            assert_close(name, "harmonic", &harmonic_centrality(graph), fixture.harmonic, 1e-11);
+            // The two power iterations stop at their own tolerances.
+            assert_close(name, "eigenvector", &eigenvector_centrality(graph).scores, fixture.eigenvector, 1e-4);
+            assert_close(name, "pagerank", &pagerank(graph, 0.85, None), fixture.pagerank, 1e-9);
+            assert_close(name, "clustering", &local_clustering(graph), fixture.clustering, 1e-11);
+            assert_eq!(triangle_count(graph), fixture.triangles, "{}", name);
+            assert!((transitivity(graph) - fixture.transitivity).abs() < 1e-11, "{}", name);
+            assert!((degree_assortativity(graph) - fixture.assortativity).abs() < 1e-11, "{}", name);
+            assert_eq!(extent(graph, true).diameter, fixture.diameter, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::AuthorGraph;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::VecDeque;
+
+/// Minimum edge cut separating two groups of authors.
+#[derive(Debug, Clone)]
+pub struct MinCut {
+    /// Maximum number of edge-disjoint paths, equal to the number of cut edges.
+    pub flow: usize,
+    /// Cut collaborations as `(source side, sink side)` author pairs, sorted.
+    pub cut_edges: Vec<(usize, usize)>,
+    /// Authors still reachable from the sources once the cut is removed.
+    pub source_side: Vec<usize>,
+}
+
+struct Arc {
+    to: usize,
+    capacity: usize,
+    /// Index of the reverse arc in `arcs[to]`.
+    reverse: usize,
+}
+
+/// Residual network for Dinic's algorithm.
+struct FlowNetwork {
+    arcs: Vec<Vec<Arc>>,
+    level: Vec<Option<usize>>,
+    next: Vec<usize>,
+}
+
+impl FlowNetwork {
+    fn new(nodes: usize) -> FlowNetwork {
+        FlowNetwork { arcs: (0..nodes).map(|_| Vec::new()).collect(), level: Vec::new(), next: Vec::new() }
+    }
+
+    /// Add `a -> b` with `forward` capacity and `b -> a` with `backward` capacity.
+    fn add(&mut self, a: usize, b: usize, forward: usize, backward: usize) {
+        let (ra, rb) = (self.arcs[b].len(), self.arcs[a].len());
+        self.arcs[a].push(Arc { to: b, capacity: forward, reverse: ra });
+        self.arcs[b].push(Arc { to: a, capacity: backward, reverse: rb });
+    }
+
+    /// Breadth-first levels over arcs with spare capacity; distances from `source`.
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.arcs.len()];
+        level[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for arc in &self.arcs[node] {
+                if arc.capacity > 0 && level[arc.to].is_none() {
+                    level[arc.to] = level[node].map(|l| l + 1);
+                    queue.push_back(arc.to);
+                }
+            }
+        }
+        level
+    }
+
+    /// Push up to `limit` units along level-increasing arcs.
+    fn augment(&mut self, node: usize, sink: usize, limit: usize) -> usize {
+        if node == sink {
+            return limit;
+        }
+        while self.next[node] < self.arcs[node].len() {
+            let (to, capacity) = {
+                let arc = &self.arcs[node][self.next[node]];
+                (arc.to, arc.capacity)
+            };
+            let advances = matches!((self.level[node], self.level[to]), (Some(a), Some(b)) if b == a + 1);
+            if capacity > 0 && advances {
+                let pushed = self.augment(to, sink, limit.min(capacity));
+                if pushed > 0 {
+                    let index = self.next[node];
+                    let reverse = self.arcs[node][index].reverse;
+                    self.arcs[node][index].capacity -= pushed;
+                    self.arcs[to][reverse].capacity += pushed;
+                    return pushed;
+                }
+            }
+            self.next[node] += 1;
+        }
+        0
+    }
+
+    fn max_flow(&mut self, source: usize, sink: usize) -> usize {
+        let mut flow = 0;
+        loop {
+            self.level = self.levels(source);
+            if self.level[sink].is_none() {
+                return flow;
+            }
+            self.next = vec![0; self.arcs.len()];
+            loop {
+                let pushed = self.augment(source, sink, usize::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                flow += pushed;
+            }
+        }
+    }
+}
+
+/// Minimum cut between `sources` and `sinks`, counting each collaboration as one unit of
+/// capacity, computed as a maximum flow with Dinic's algorithm.
+///
+/// The two groups must be disjoint.
+pub fn min_cut(graph: &AuthorGraph, sources: &[NodeIndex], sinks: &[NodeIndex]) -> MinCut {
+    let n = graph.node_count();
+    // Super source and super sink tie the groups together.
+    let (source, sink) = (n, n + 1);
+    let mut network = FlowNetwork::new(n + 2);
+    for edge in graph.edge_references() {
+        network.add(edge.source().index(), edge.target().index(), 1, 1);
+    }
+    for node in sources {
+        network.add(source, node.index(), usize::MAX / 2, 0);
+    }
+    for node in sinks {
+        network.add(node.index(), sink, usize::MAX / 2, 0);
+    }
+    let flow = network.max_flow(source, sink);
+
+    let reachable = network.levels(source);
+    let on_source_side = |node: NodeIndex| reachable[node.index()].is_some();
+    let mut cut_edges: Vec<(usize, usize)> = graph
+        .edge_references()
+        .filter(|edge| on_source_side(edge.source()) != on_source_side(edge.target()))
+        .map(|edge| {
+            let (a, b) = if on_source_side(edge.source()) {
+                (edge.source(), edge.target())
+            } else {
+                (edge.target(), edge.source())
+            };
+            (graph[a], graph[b])
+        })
+        .collect();
+    cut_edges.sort_unstable();
+    let mut source_side: Vec<usize> = graph.node_indices().filter(|&node| on_source_side(node)).map(|node| graph[node]).collect();
+    source_side.sort_unstable();
+    MinCut { flow, cut_edges, source_side }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_cut_between_cliques() {
+        // Two 4-cliques {0..3} and {4..7} joined by the edges 2–4 and 3–5.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..8).map(|author| graph.add_node(author)).collect();
+        for group in [0, 4] {
+            for i in group..group + 4 {
+                for j in i + 1..group + 4 {
+                    graph.add_edge(nodes[i], nodes[j], ());
+                }
+            }
+        }
+        graph.add_edge(nodes[2], nodes[4], ());
+        graph.add_edge(nodes[3], nodes[5], ());
+
+        let cut = min_cut(&graph, &[nodes[0]], &[nodes[7]]);
+        assert_eq!(cut.flow, 2);
+        assert_eq!(cut.cut_edges, vec![(2, 4), (3, 5)]);
+        assert_eq!(cut.source_side, vec![0, 1, 2, 3]);
+
+        // Groups: separating {0, 1} from {2, 3} has to cut inside the first clique.
+        let cut = min_cut(&graph, &[nodes[0], nodes[1]], &[nodes[2], nodes[3]]);
+        assert_eq!(cut.flow, 4);
+        assert_eq!(cut.cut_edges.len(), 4);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::AuthorGraph;
+use petgraph::visit::EdgeRef;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Events sent per HTTP request.
+const BATCH_SIZE: usize = 1000;
+
+/// Graph events of the Gephi streaming API, one JSON object per line: an `an` (add node)
+/// event per author carrying the given attributes, then an `ae` (add edge) event per
+/// collaboration. Nodes and edges are in increasing author id order.
+pub fn gephi_events(graph: &AuthorGraph, attributes: &[(&str, &HashMap<usize, f64>)]) -> Vec<String> {
+    let mut authors: Vec<usize> = graph.node_weights().copied().collect();
+    authors.sort_unstable();
+    let mut events: Vec<String> = authors
+        .iter()
+        .map(|&author| {
+            let mut node = Map::new();
+            node.insert("label".to_string(), json!(author.to_string()));
+            for (name, scores) in attributes {
+                if let Some(&score) = scores.get(&author) {
+                    node.insert(name.to_string(), json!(score));
+                }
+            }
+            json!({ "an": { author.to_string(): Value::Object(node) } }).to_string()
+        })
+        .collect();
+    let mut edges: Vec<(usize, usize)> = graph
+        .edge_references()
+        .map(|edge| {
+            let (a, b) = (graph[edge.source()], graph[edge.target()]);
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    edges.sort_unstable();
+    events.extend(edges.into_iter().map(|(a, b)| {
+        json!({ "ae": { format!("{}-{}", a, b): { "source": a.to_string(), "target": b.to_string(), "directed": false } } })
+            .to_string()
+    }));
+    events
+}
+
+/// Host, port and request target of an `http://` URL; the Gephi `updateGraph` operation
+/// is added when the URL has no query.
+fn parse_url(url: &str) -> io::Result<(String, u16, String)> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, format!("expected an http:// URL, got {}", url));
+    let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+    let (authority, path) = match rest.find('/') {
+        Some(slash) => (&rest[..slash], &rest[slash..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse().map_err(|_| invalid())?),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    let target = if path.contains('?') { path.to_string() } else { format!("{}?operation=updateGraph", path) };
+    Ok((host.to_string(), port, target))
+}
+
+/// POST the events to a Gephi workspace URL such as `http://localhost:8080/workspace1`
+/// (the Graph Streaming plugin's master server), in batches of `BATCH_SIZE` events.
+pub fn stream_to_gephi(url: &str, events: &[String]) -> io::Result<()> {
+    let (host, port, target) = parse_url(url)?;
+    for batch in events.chunks(BATCH_SIZE) {
+        let body = batch.join("\r\n") + "\r\n";
+        let mut stream = TcpStream::connect((host.as_str(), port))?;
+        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            target,
+            host,
+            port,
+            body.len(),
+            body
+        )?;
+        stream.flush()?;
+        let mut status = String::new();
+        BufReader::new(stream).read_line(&mut status)?;
+        let code = status.split_whitespace().nth(1).unwrap_or("");
+        if !code.starts_with('2') {
+            return Err(io::Error::other(format!("Gephi answered {}", status.trim())));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph_from_reader;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_gephi_events() {
+        let graph = load_graph_from_reader("2 1\n2 3\n".as_bytes()).unwrap();
+        let degree: HashMap<usize, f64> = [(1, 1.0), (2, 2.0), (3, 1.0)].into_iter().collect();
+        let events = gephi_events(&graph, &[("degree", &degree)]);
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0], r#"{"an":{"1":{"degree":1.0,"label":"1"}}}"#);
+        assert_eq!(events[3], r#"{"ae":{"1-2":{"directed":false,"source":"1","target":"2"}}}"#);
+    }
+
+    #[test]
+    fn test_stream_to_gephi() {
+        assert!(parse_url("https://localhost/workspace1").is_err());
+        assert_eq!(parse_url("http://localhost:8080/workspace1").unwrap().2, "/workspace1?operation=updateGraph");
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !String::from_utf8_lossy(&request).ends_with("}\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        let events = vec![r#"{"an":{"1":{}}}"#.to_string()];
+        stream_to_gephi(&format!("http://127.0.0.1:{}/workspace1", port), &events).unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /workspace1?operation=updateGraph HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\n{\"an\":{\"1\":{}}}\r\n"));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::betweenness::ShortestPathDag;
+use crate::community::{modularity, Partition};
+use crate::AuthorGraph;
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// One branching of the dendrogram: removing a collaboration broke cluster `parent` into
+/// the new clusters `left` and `right`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Split {
+    /// Collaborations removed so far, including the one that caused the split.
+    pub edges_removed: usize,
+    pub parent: usize,
+    pub left: usize,
+    pub right: usize,
+    pub left_size: usize,
+    pub right_size: usize,
+    /// Number of clusters after the split, and the modularity of that partition.
+    pub communities: usize,
+    pub modularity: f64,
+}
+
+/// The divisive hierarchy found by [`girvan_newman`]. Clusters are numbered in order of
+/// appearance: the connected components of the input first, then two per split.
+#[derive(Debug, Clone)]
+pub struct Dendrogram {
+    /// Connected components of the input, the roots of the dendrogram.
+    pub roots: Partition,
+    pub splits: Vec<Split>,
+    /// Cluster of every author when the removals stopped, the leaves; together with the
+    /// splits they give the members of every cluster.
+    pub leaves: Partition,
+    /// The partition of highest modularity along the way, with cluster ids as above.
+    pub best: Partition,
+    pub best_modularity: f64,
+}
+
+/// Girvan–Newman divisive clustering: repeatedly remove the collaboration of highest edge
+/// betweenness, recomputing betweenness only in the component that lost it, and record a
+/// split whenever a component falls apart. Stops when no collaborations are left or after
+/// `max_splits` splits. Each removal costs a BFS per author of the component, so a full
+/// run is cubic; on graphs beyond a few thousand collaborations, cap the splits.
+pub fn girvan_newman(graph: &AuthorGraph, max_splits: Option<usize>) -> Dendrogram {
+    let mut working = graph.clone();
+    let mut cluster = vec![usize::MAX; graph.node_count()];
+    let mut scores = HashMap::new();
+    let mut next = 0;
+    for node in graph.node_indices() {
+        if cluster[node.index()] == usize::MAX {
+            let members = component(&working, node);
+            members.iter().for_each(|member| cluster[member.index()] = next);
+            score_component(&working, &members, &mut scores);
+            next += 1;
+        }
+    }
+    let partition_of = |cluster: &[usize]| -> Partition {
+        graph.node_indices().map(|node| (graph[node], cluster[node.index()])).collect()
+    };
+    let roots = partition_of(&cluster);
+    let (mut best, mut best_modularity) = (roots.clone(), modularity(graph, &roots, 1.0));
+
+    let mut splits = Vec::new();
+    let (mut communities, mut removed) = (next, 0);
+    while max_splits.is_none_or(|max| splits.len() < max) {
+        // Ties go to the collaboration with the smallest node indices, for reproducibility.
+        let Some((&(a, b), _)) = scores.iter().max_by(|x, y| x.1.total_cmp(y.1).then(y.0.cmp(x.0))) else {
+            break;
+        };
+        scores.remove(&(a, b));
+        if let Some(edge) = working.find_edge(a, b) {
+            working.remove_edge(edge);
+        }
+        removed += 1;
+
+        let side = component(&working, a);
+        if side.contains(&b) {
+            score_component(&working, &side, &mut scores);
+            continue;
+        }
+        let other = component(&working, b);
+        let parent = cluster[a.index()];
+        let (left, right) = (next, next + 1);
+        next += 2;
+        communities += 1;
+        side.iter().for_each(|node| cluster[node.index()] = left);
+        other.iter().for_each(|node| cluster[node.index()] = right);
+        score_component(&working, &side, &mut scores);
+        score_component(&working, &other, &mut scores);
+
+        let partition = partition_of(&cluster);
+        let q = modularity(graph, &partition, 1.0);
+        if q > best_modularity {
+            (best, best_modularity) = (partition, q);
+        }
+        splits.push(Split {
+            edges_removed: removed,
+            parent,
+            left,
+            right,
+            left_size: side.len(),
+            right_size: other.len(),
+            communities,
+            modularity: q,
+        });
+    }
+    Dendrogram { roots, splits, leaves: partition_of(&cluster), best, best_modularity }
+}
+
+/// Nodes reachable from `start`, `start` first.
+fn component(graph: &AuthorGraph, start: NodeIndex) -> Vec<NodeIndex> {
+    let mut seen = vec![false; graph.node_count()];
+    seen[start.index()] = true;
+    let mut members = vec![start];
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for next in graph.neighbors(node) {
+            if !seen[next.index()] {
+                seen[next.index()] = true;
+                members.push(next);
+                queue.push_back(next);
+            }
+        }
+    }
+    members
+}
+
+/// Replace the (unnormalized, both directions counted) edge betweenness of the
+/// collaborations of one component, keyed by their node indices, smaller first.
+fn score_component(graph: &AuthorGraph, members: &[NodeIndex], scores: &mut HashMap<(NodeIndex, NodeIndex), f64>) {
+    for &node in members {
+        for next in graph.neighbors(node).filter(|&next| node < next) {
+            scores.insert((node, next), 0.0);
+        }
+    }
+    let mut delta = vec![0.0; graph.node_count()];
+    for &source in members {
+        let dag = ShortestPathDag::from_source(graph, source);
+        dag.order.iter().for_each(|node| delta[node.index()] = 0.0);
+        for &w in dag.order.iter().rev() {
+            let coefficient = (1.0 + delta[w.index()]) / dag.sigma[w.index()];
+            for &v in &dag.predecessors[w.index()] {
+                let credit = dag.sigma[v.index()] * coefficient;
+                *scores.entry((v.min(w), v.max(w))).or_insert(0.0) += credit;
+                delta[v.index()] += credit;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::karate;
+    use crate::load_graph_from_reader;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_girvan_newman_splits_two_triangles() {
+        // Two triangles joined by the bridge 3-4, and a separate collaboration 7-8.
+        let graph = load_graph_from_reader("1 2\n2 3\n3 1\n4 5\n5 6\n6 4\n3 4\n7 8\n".as_bytes()).unwrap();
+        let dendrogram = girvan_newman(&graph, None);
+        assert_eq!(dendrogram.roots.values().collect::<HashSet<_>>().len(), 2);
+        // The bridge goes first; then each triangle needs two removals per split and the
+        // pair one, ending in eight singletons.
+        let first = &dendrogram.splits[0];
+        assert_eq!((first.edges_removed, first.left_size, first.right_size, first.communities), (1, 3, 3, 3));
+        assert_eq!(dendrogram.splits.len(), 6);
+        assert_eq!(dendrogram.splits.last().unwrap().communities, 8);
+        assert_eq!(dendrogram.leaves.values().collect::<HashSet<_>>().len(), 8);
+
+        // Best: the two triangles and the pair, Q = 2 (3/8 - (7/16)^2) + 1/8 - (2/16)^2.
+        let expected = 2.0 * (3.0 / 8.0 - 49.0 / 256.0) + 1.0 / 8.0 - 4.0 / 256.0;
+        assert!((dendrogram.best_modularity - expected).abs() < 1e-12);
+        let best = &dendrogram.best;
+        assert!(best[&1] == best[&2] && best[&2] == best[&3] && best[&4] == best[&6] && best[&3] != best[&4]);
+        assert_eq!(best[&7], dendrogram.roots[&7]);
+    }
+
+    #[test]
+    fn test_girvan_newman_on_karate() {
+        // networkx's girvan_newman peaks at five communities with Q = 0.401298...
+        let dendrogram = girvan_newman(&karate(), None);
+        assert_eq!(dendrogram.best.values().collect::<HashSet<_>>().len(), 5);
+        assert!((dendrogram.best_modularity - 0.40129848783694944).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_girvan_newman_stops_after_max_splits() {
+        let graph = load_graph_from_reader("1 2\n2 3\n3 1\n4 5\n5 6\n6 4\n3 4\n".as_bytes()).unwrap();
+        let dendrogram = girvan_newman(&graph, Some(1));
+        assert_eq!(dendrogram.splits.len(), 1);
+        let split = &dendrogram.splits[0];
+        assert_eq!((split.parent, split.left, split.right), (0, 1, 2));
+        assert_eq!(dendrogram.leaves, dendrogram.best);
+    }
+}
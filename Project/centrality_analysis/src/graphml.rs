@@ -0,0 +1,386 @@
+use crate::audit::AuditLog;
+use crate::{DuplicatePolicy, LoadOptions, SelfLoopPolicy, WeightedAuthorGraph};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The nodes and edges of a GraphML document, as written by networkx's `write_graphml`
+/// and by Gephi. Attributes are keyed by their `attr.name` (or the key id if the key
+/// declares none) and include the defaults of their keys; nested graphs, hyperedges and
+/// ports are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlData {
+    /// From `edgedefault`; directions are dropped when building the graph.
+    pub directed: bool,
+    pub nodes: Vec<GraphMlNode>,
+    pub edges: Vec<GraphMlEdge>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlNode {
+    pub id: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphMlEdge {
+    pub source: String,
+    pub target: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// A declared attribute: its name, the elements it is for and its default value.
+struct Key {
+    name: String,
+    domain: String,
+    default: Option<String>,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Parse a GraphML document. Only what GraphML files use of XML is understood: elements,
+/// attributes, comments, CDATA sections and the predefined and numeric entities.
+pub fn parse_graphml(text: &str) -> io::Result<GraphMlData> {
+    let mut data = GraphMlData::default();
+    let mut keys: HashMap<String, Key> = HashMap::new();
+    // The element whose <data> is being read: a node, an edge or neither.
+    let (mut in_node, mut in_edge) = (false, false);
+    // The open <data> (by key id) or <default> (of the last key), and the text read in it.
+    let (mut open_data, mut open_default, mut last_key) = (None::<String>, false, None::<String>);
+    let mut text_value = String::new();
+    // Nesting of <graph> elements; only the outermost graph is read.
+    let mut depth = 0;
+
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        text_value += &unescape(&rest[..start]);
+        rest = &rest[start..];
+        let skip_past = |rest: &str, end: &str| {
+            rest.find(end).map(|i| i + end.len()).ok_or_else(|| invalid(format!("unterminated markup before {}", end)))
+        };
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = skip_past(cdata, "]]>")?;
+            text_value += &cdata[..end - 3];
+            rest = &cdata[end..];
+            continue;
+        }
+        if rest.starts_with("<!--") {
+            rest = &rest[skip_past(rest, "-->")?..];
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = &rest[skip_past(rest, ">")?..];
+            continue;
+        }
+        let end = tag_end(rest).ok_or_else(|| invalid("unterminated tag".to_string()))?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let value = std::mem::take(&mut text_value).trim().to_string();
+            match local_name(name.trim()) {
+                "graph" => depth = usize::saturating_sub(depth, 1),
+                "node" => in_node = false,
+                "edge" => in_edge = false,
+                "data" => {
+                    let Some(key) = open_data.take() else {
+                        continue;
+                    };
+                    let name = keys.get(&key).map_or(key, |key| key.name.clone());
+                    let attributes = if in_node {
+                        data.nodes.last_mut().map(|node| &mut node.attributes)
+                    } else if in_edge {
+                        data.edges.last_mut().map(|edge| &mut edge.attributes)
+                    } else {
+                        None
+                    };
+                    if let Some(attributes) = attributes {
+                        attributes.insert(name, value);
+                    }
+                }
+                "default" if open_default => {
+                    open_default = false;
+                    if let Some(key) = last_key.as_ref().and_then(|id| keys.get_mut(id)) {
+                        key.default = Some(value);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        text_value.clear();
+        let self_closing = tag.ends_with('/');
+        let (name, attributes) = parse_tag(tag.trim_end_matches('/'))?;
+        let attribute = |name: &str| attributes.get(name).cloned().unwrap_or_default();
+        match local_name(name) {
+            "key" => {
+                let id = attribute("id");
+                let name = attributes.get("attr.name").cloned().unwrap_or_else(|| id.clone());
+                keys.insert(id.clone(), Key { name, domain: attribute("for"), default: None });
+                last_key = Some(id);
+            }
+            "default" => open_default = !self_closing,
+            "graph" => {
+                if depth == 0 {
+                    data.directed = attribute("edgedefault") == "directed";
+                }
+                depth += usize::from(!self_closing);
+            }
+            "node" if depth == 1 => {
+                data.nodes.push(GraphMlNode { id: attribute("id"), attributes: BTreeMap::new() });
+                in_node = !self_closing;
+            }
+            "edge" if depth == 1 => {
+                let (source, target) = (attribute("source"), attribute("target"));
+                data.edges.push(GraphMlEdge { source, target, attributes: BTreeMap::new() });
+                in_edge = !self_closing;
+            }
+            "data" if !self_closing => open_data = Some(attribute("key")),
+            _ => {}
+        }
+    }
+
+    let defaults = |domain: &str, attributes: &mut BTreeMap<String, String>| {
+        for key in keys.values().filter(|key| key.domain == domain || key.domain == "all") {
+            if let Some(default) = &key.default {
+                attributes.entry(key.name.clone()).or_insert_with(|| default.clone());
+            }
+        }
+    };
+    data.nodes.iter_mut().for_each(|node| defaults("node", &mut node.attributes));
+    data.edges.iter_mut().for_each(|edge| defaults("edge", &mut edge.attributes));
+    Ok(data)
+}
+
+/// Index of the `>` closing the tag at the start of `text`, skipping quoted values.
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The element name of a tag and its attributes, values unescaped.
+fn parse_tag(tag: &str) -> io::Result<(&str, HashMap<String, String>)> {
+    let tag = tag.trim();
+    let (name, mut rest) = tag.split_at(tag.find(char::is_whitespace).unwrap_or(tag.len()));
+    let mut attributes = HashMap::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok((name, attributes));
+        }
+        let malformed = || invalid(format!("malformed attributes in <{}>", tag));
+        let (attribute, value) = rest.split_once('=').ok_or_else(malformed)?;
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|&c| c == '"' || c == '\'').ok_or_else(malformed)?;
+        let (value, after) = value[1..].split_once(quote).ok_or_else(malformed)?;
+        attributes.insert(local_name(attribute.trim()).to_string(), unescape(value));
+        rest = after;
+    }
+}
+
+/// A name without its namespace prefix.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Text with the predefined and numeric character entities replaced.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped += &rest[..start];
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end + 1));
+        let character = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => name
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| name.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        });
+        match (character, entity) {
+            (Some(character), Some((_, length))) => {
+                unescaped.push(character);
+                rest = &rest[length..];
+            }
+            _ => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped + rest
+}
+
+/// Author id of a GraphML id: a non-negative integer, possibly written as a float such
+/// as Gephi's `12.0`.
+fn author_id(id: &str) -> io::Result<usize> {
+    let id = id.trim();
+    let parsed = id.parse().ok().or_else(|| {
+        id.parse::<f64>().ok().filter(|x| x.fract() == 0.0 && *x >= 0.0 && *x < usize::MAX as f64).map(|x| x as usize)
+    });
+    parsed.ok_or_else(|| invalid(format!("node id {} is not an author id", id)))
+}
+
+/// Build a weighted collaboration graph from GraphML data, the weight of an edge coming
+/// from its `weight` attribute (1 without one). As with node-link data, listed nodes are
+/// added even without edges and directions are dropped; self-loops and repeated edges
+/// are handled as `options` says and counted in `log`.
+pub fn graph_from_graphml(data: &GraphMlData, options: &LoadOptions, log: &mut AuditLog) -> io::Result<WeightedAuthorGraph> {
+    let limit_error = |what: &str, limit: usize| invalid(format!("graph has more than {} {} (the configured limit)", limit, what));
+    let mut graph = WeightedAuthorGraph::new_undirected();
+    let mut node_map = HashMap::new();
+    let mut add_node = |graph: &mut WeightedAuthorGraph, author: usize| {
+        let index = *node_map.entry(author).or_insert_with(|| graph.add_node(author));
+        match options.max_nodes {
+            Some(max) if graph.node_count() > max => Err(limit_error("nodes", max)),
+            _ => Ok(index),
+        }
+    };
+    for node in &data.nodes {
+        add_node(&mut graph, author_id(&node.id)?)?;
+    }
+    let mut seen = HashMap::new();
+    let (mut repeated, mut self_loops) = (0, 0);
+    for edge in &data.edges {
+        let (from, to) = (author_id(&edge.source)?, author_id(&edge.target)?);
+        let weight = match edge.attributes.get("weight") {
+            None => 1.0,
+            Some(weight) => weight.parse().ok().filter(|&w: &f64| w > 0.0 && w.is_finite()).ok_or_else(|| {
+                invalid(format!("edge {}-{} has weight {}; weights must be positive numbers", from, to, weight))
+            })?,
+        };
+        if from == to {
+            self_loops += 1;
+            if options.self_loops == SelfLoopPolicy::Drop {
+                continue;
+            }
+        }
+        let key = (from.min(to), from.max(to));
+        if let Some(&index) = seen.get(&key) {
+            repeated += 1;
+            match options.duplicates {
+                DuplicatePolicy::Drop => continue,
+                DuplicatePolicy::Aggregate => {
+                    graph[index] += weight;
+                    continue;
+                }
+                DuplicatePolicy::Keep => {}
+            }
+        }
+        if let Some(max) = options.max_edges.filter(|&max| graph.edge_count() >= max) {
+            return Err(limit_error("edges", max));
+        }
+        let (a, b) = (add_node(&mut graph, from)?, add_node(&mut graph, to)?);
+        let index = graph.add_edge(a, b, weight);
+        seen.entry(key).or_insert(index);
+    }
+    log.record(options.duplicates.operation(), repeated);
+    log.record(options.self_loops.operation(), self_loops);
+    options.weight_transform.apply(&mut graph);
+    Ok(graph)
+}
+
+/// Load a GraphML file, keeping the edge weights.
+pub fn load_graphml<P: AsRef<Path>>(path: P, options: &LoadOptions, log: &mut AuditLog) -> io::Result<WeightedAuthorGraph> {
+    graph_from_graphml(&parse_graphml(&fs::read_to_string(path)?)?, options, log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WeightTransform;
+    use petgraph::visit::EdgeRef;
+
+    /// As written by `nx.write_graphml` for a weighted graph with a node attribute.
+    const NETWORKX: &str = r#"<?xml version='1.0' encoding='utf-8'?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <key id="d1" for="edge" attr.name="weight" attr.type="double" />
+  <key id="d0" for="node" attr.name="name" attr.type="string" />
+  <graph edgedefault="undirected">
+    <node id="1">
+      <data key="d0">A. &amp; B. &#x4C;ee</data>
+    </node>
+    <node id="2" />
+    <node id="3" />
+    <node id="9" />
+    <edge source="1" target="2">
+      <data key="d1">2.5</data>
+    </edge>
+    <edge source="2" target="3" />
+    <!-- <edge source="3" target="9" /> -->
+    <edge source="3" target="1">
+      <data key="d1">0.5</data>
+    </edge>
+    <edge source="2" target="1" />
+    <edge source="3" target="3" />
+  </graph>
+</graphml>
+"#;
+
+    #[test]
+    fn test_parse_graphml() {
+        let data = parse_graphml(NETWORKX).unwrap();
+        assert!(!data.directed);
+        assert_eq!(data.nodes.len(), 4);
+        assert_eq!(data.nodes[0].attributes["name"], "A. & B. Lee");
+        assert_eq!(data.edges.len(), 5);
+        assert_eq!(data.edges[0].attributes["weight"], "2.5");
+        assert!(data.edges[1].attributes.is_empty());
+
+        let mut log = AuditLog::default();
+        let graph = graph_from_graphml(&data, &LoadOptions::default(), &mut log).unwrap();
+        let edges: Vec<(usize, usize, f64)> =
+            graph.edge_references().map(|e| (graph[e.source()], graph[e.target()], *e.weight())).collect();
+        assert_eq!(edges, vec![(1, 2, 2.5), (2, 3, 1.0), (3, 1, 0.5)]);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(log.to_string(), "repeated edges merged: 1, self-loops dropped: 1");
+        let aggregate = LoadOptions { duplicates: DuplicatePolicy::Aggregate, ..LoadOptions::default() };
+        let graph = graph_from_graphml(&data, &aggregate, &mut AuditLog::default()).unwrap();
+        assert_eq!(graph.edge_weights().copied().collect::<Vec<_>>(), vec![3.5, 1.0, 0.5]);
+        let inverse = LoadOptions { weight_transform: WeightTransform::Inverse, ..aggregate };
+        let graph = graph_from_graphml(&data, &inverse, &mut AuditLog::default()).unwrap();
+        assert_eq!(graph.edge_weights().copied().collect::<Vec<_>>(), vec![1.0 / 3.5, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_read_gephi_output() {
+        // Gephi writes float ids, prefixed names and key defaults.
+        let gephi = r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+            <key attr.name="label" attr.type="string" for="node" id="label"/>
+            <key attr.name="weight" attr.type="double" for="edge" id="weight"><default>4.0</default></key>
+            <graph edgedefault="directed"><node id="12.0"><data key="label"><![CDATA[<Smith>]]></data></node>
+            <node id="7.0"/><edge id="0" source="12.0" target="7.0"/>
+            <edge id="1" source="7.0" target="12.0"><data key="weight">2.0</data></edge></graph></graphml>"#;
+        let data = parse_graphml(gephi).unwrap();
+        assert!(data.directed);
+        assert_eq!(data.nodes[0].attributes["label"], "<Smith>");
+        assert_eq!(data.edges[0].attributes["weight"], "4.0");
+        let graph = graph_from_graphml(&data, &LoadOptions::default(), &mut AuditLog::default()).unwrap();
+        assert_eq!(graph.node_weights().copied().collect::<Vec<_>>(), vec![12, 7]);
+        assert_eq!(graph.edge_weights().copied().collect::<Vec<_>>(), vec![4.0]);
+
+        let named = parse_graphml(r#"<graphml><graph><node id="alice"/></graph></graphml>"#).unwrap();
+        assert!(graph_from_graphml(&named, &LoadOptions::default(), &mut AuditLog::default()).is_err());
+        let zero = parse_graphml(r#"<graphml><graph><edge source="1" target="2"><data key="weight">0</data></edge></graph></graphml>"#);
+        assert!(graph_from_graphml(&zero.unwrap(), &LoadOptions::default(), &mut AuditLog::default()).is_err());
+        assert!(parse_graphml("<graphml><graph><node id=\"1\"").is_err());
+    }
+}
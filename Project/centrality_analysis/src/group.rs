@@ -0,0 +1,143 @@
+use crate::{line_fields, AuthorGraph};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Centrality of a set of authors taken as one unit (Everett & Borgatti, 1999), such as
+/// all members of a lab or institution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupCentrality {
+    /// Members found in the graph.
+    pub size: usize,
+    /// Fraction of non-members with a collaborator in the group.
+    pub degree: f64,
+    /// Non-members the group reaches over their summed distance to the nearest member;
+    /// unreachable authors are left out, as for closeness within a component.
+    pub closeness: f64,
+    /// Fraction of pairs of non-members whose shortest paths pass through the group,
+    /// summed over pairs and normalized by their number like networkx.
+    pub betweenness: f64,
+}
+
+/// Load a group as author ids separated by whitespace, commas or newlines; comments are
+/// skipped.
+pub fn load_group<P: AsRef<Path>>(path: P) -> io::Result<HashSet<usize>> {
+    let mut group = HashSet::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let Some(fields) = line_fields(&line, true) else {
+            continue;
+        };
+        group.extend(fields.filter_map(|field| field.parse::<usize>().ok()));
+    }
+    Ok(group)
+}
+
+/// Distances (`usize::MAX` if unreachable) and shortest-path counts from `sources`,
+/// skipping the nodes in `blocked`.
+fn bfs(graph: &AuthorGraph, sources: &[NodeIndex], blocked: &[bool]) -> (Vec<usize>, Vec<f64>) {
+    let mut distance = vec![usize::MAX; graph.node_count()];
+    let mut sigma = vec![0.0; graph.node_count()];
+    let mut queue = VecDeque::new();
+    for &source in sources {
+        distance[source.index()] = 0;
+        sigma[source.index()] = 1.0;
+        queue.push_back(source);
+    }
+    while let Some(node) = queue.pop_front() {
+        for next in graph.neighbors(node) {
+            if blocked[next.index()] {
+                continue;
+            }
+            if distance[next.index()] == usize::MAX {
+                distance[next.index()] = distance[node.index()] + 1;
+                queue.push_back(next);
+            }
+            if distance[next.index()] == distance[node.index()] + 1 {
+                sigma[next.index()] += sigma[node.index()];
+            }
+        }
+    }
+    (distance, sigma)
+}
+
+/// Group degree, closeness and betweenness of the members present in the graph. A pair
+/// of non-members has shortest paths avoiding the group exactly when the graph without
+/// the group still connects them at the same distance, so betweenness takes two
+/// breadth-first searches per non-member.
+pub fn group_centrality(graph: &AuthorGraph, group: &HashSet<usize>) -> GroupCentrality {
+    let n = graph.node_count();
+    let is_member: Vec<bool> = graph.node_indices().map(|node| group.contains(&graph[node])).collect();
+    let members: Vec<NodeIndex> = graph.node_indices().filter(|node| is_member[node.index()]).collect();
+    let others = n - members.len();
+    if members.is_empty() || others == 0 {
+        return GroupCentrality { size: members.len(), degree: 0.0, closeness: 0.0, betweenness: 0.0 };
+    }
+
+    let neighbours: HashSet<NodeIndex> = members
+        .iter()
+        .flat_map(|&member| graph.neighbors(member))
+        .filter(|node| !is_member[node.index()])
+        .collect();
+    let degree = neighbours.len() as f64 / others as f64;
+
+    let unblocked = vec![false; n];
+    let (distance, _) = bfs(graph, &members, &unblocked);
+    let reached: Vec<usize> = distance.iter().copied().filter(|&d| d != 0 && d != usize::MAX).collect();
+    let total: usize = reached.iter().sum();
+    let closeness = if total == 0 { 0.0 } else { reached.len() as f64 / total as f64 };
+
+    let mut through = 0.0;
+    for source in graph.node_indices().filter(|node| !is_member[node.index()]) {
+        let (distance, sigma) = bfs(graph, &[source], &unblocked);
+        let (avoiding_distance, avoiding_sigma) = bfs(graph, &[source], &is_member);
+        for target in graph.node_indices() {
+            let t = target.index();
+            if t <= source.index() || is_member[t] || distance[t] == usize::MAX {
+                continue;
+            }
+            let avoiding = if avoiding_distance[t] == distance[t] { avoiding_sigma[t] } else { 0.0 };
+            through += 1.0 - avoiding / sigma[t];
+        }
+    }
+    let pairs = others * others.saturating_sub(1) / 2;
+    let betweenness = if pairs == 0 { 0.0 } else { through / pairs as f64 };
+
+    GroupCentrality { size: members.len(), degree, closeness, betweenness }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph_from_reader;
+
+    fn bridged_triangles() -> AuthorGraph {
+        load_graph_from_reader("0 1\n0 2\n1 2\n2 3\n3 4\n3 5\n4 5\n5 6\n".as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_group_centrality_matches_networkx() {
+        let graph = bridged_triangles();
+        // nx.group_degree_centrality, group_closeness_centrality, group_betweenness_centrality
+        let bridge = group_centrality(&graph, &[2, 3].into_iter().collect());
+        assert_eq!(bridge.size, 2);
+        assert!((bridge.degree - 0.8).abs() < 1e-12);
+        assert!((bridge.closeness - 5.0 / 6.0).abs() < 1e-12);
+        assert!((bridge.betweenness - 0.6).abs() < 1e-12);
+
+        // Authors 1 and 4 lie on no shortest path: each triangle routes around them.
+        let corners = group_centrality(&graph, &[1, 4, 99].into_iter().collect());
+        assert_eq!(corners.size, 2);
+        assert_eq!(corners.betweenness, 0.0);
+    }
+
+    #[test]
+    fn test_load_group() {
+        let path = "group_test.txt";
+        std::fs::write(path, "# lab members\n1, 2\n3\n\n2 4\n").unwrap();
+        assert_eq!(load_group(path).unwrap(), [1, 2, 3, 4].into_iter().collect());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+use crate::DirectedAuthorGraph;
+use petgraph::Direction;
+use std::collections::HashMap;
+
+/// Power iterations after which HITS stops even without converging.
+pub const HITS_ITERATIONS: usize = 1000;
+/// Convergence threshold on the L1 change of the hub scores between iterations.
+pub const HITS_TOLERANCE: f64 = 1e-8;
+
+/// Hub and authority scores, each summing to one, and how the power iteration ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hits {
+    pub hubs: HashMap<usize, f64>,
+    pub authorities: HashMap<usize, f64>,
+    pub iterations: usize,
+    /// L1 change of the max-normalised hub scores in the last iteration; below
+    /// `HITS_TOLERANCE` if the iteration converged.
+    pub residual: f64,
+}
+
+impl Hits {
+    pub fn converged(&self) -> bool {
+        self.residual < HITS_TOLERANCE
+    }
+}
+
+/// HITS (Kleinberg, 1999): a node's authority is the sum of the hub scores of the nodes
+/// linking to it, and its hub score the sum of the authorities it links to. In a
+/// citation graph, good hubs cite many good authorities and good authorities are cited
+/// by many good hubs. The power iteration follows networkx's `hits`, including its
+/// stopping rule, and running out of iterations is reported in the result.
+pub fn hits(graph: &DirectedAuthorGraph) -> Hits {
+    let n = graph.node_count();
+    let mut hubs = vec![1.0 / n.max(1) as f64; n];
+    let mut authorities = vec![0.0; n];
+    let (mut iterations, mut residual) = (0, f64::INFINITY);
+    while iterations < HITS_ITERATIONS && residual >= HITS_TOLERANCE {
+        authorities = graph
+            .node_indices()
+            .map(|node| graph.neighbors_directed(node, Direction::Incoming).map(|other| hubs[other.index()]).sum())
+            .collect();
+        let mut next: Vec<f64> = graph
+            .node_indices()
+            .map(|node| graph.neighbors_directed(node, Direction::Outgoing).map(|other| authorities[other.index()]).sum())
+            .collect();
+        scale_to_max(&mut next);
+        scale_to_max(&mut authorities);
+        residual = next.iter().zip(&hubs).map(|(a, b)| (a - b).abs()).sum();
+        hubs = next;
+        iterations += 1;
+    }
+    let normalized = |scores: &[f64]| {
+        let total: f64 = scores.iter().sum();
+        let total = if total > 0.0 { total } else { 1.0 };
+        graph.node_indices().map(|node| (graph[node], scores[node.index()] / total)).collect()
+    };
+    Hits {
+        hubs: normalized(&hubs),
+        authorities: normalized(&authorities),
+        iterations,
+        residual: if n == 0 { 0.0 } else { residual },
+    }
+}
+
+/// Divide by the largest value, unless all are zero.
+fn scale_to_max(values: &mut [f64]) {
+    let max = values.iter().copied().fold(0.0, f64::max);
+    if max > 0.0 {
+        values.iter_mut().for_each(|value| *value /= max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_directed_graph_from_reader_audited, LoadOptions};
+
+    #[test]
+    fn test_hits_matches_networkx() {
+        let input = "0 1\n0 2\n1 2\n2 0\n3 2\n3 1\n4 3\n";
+        let graph = load_directed_graph_from_reader_audited(input.as_bytes(), &LoadOptions::default(), &mut Default::default());
+        let result = hits(&graph.unwrap());
+        assert!(result.converged() && result.iterations > 1);
+        // networkx hits (its pure-Python version, tol=1e-12).
+        let hubs = [0.3903882032021894, 0.21922359359557467, 0.0, 0.3903882032021894, 0.0];
+        let authorities = [0.0, 0.43844718719111764, 0.5615528128087636, 0.0, 0.0];
+        for author in 0..5 {
+            assert!((result.hubs[&author] - hubs[author]).abs() < 1e-6, "hub {}: {}", author, result.hubs[&author]);
+            assert!((result.authorities[&author] - authorities[author]).abs() < 1e-6, "authority {}", author);
+        }
+    }
+}
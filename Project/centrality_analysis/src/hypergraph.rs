@@ -0,0 +1,126 @@
+use crate::{line_fields, AuthorGraph};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Papers as author lists, the raw form of co-authorship data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hypergraph {
+    pub hyperedges: Vec<Vec<usize>>,
+}
+
+/// How hyperedges become ordinary edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expansion {
+    /// Link every pair of authors sharing a paper.
+    Clique,
+    /// Bipartite graph linking each paper node to its authors.
+    Star,
+}
+
+impl FromStr for Expansion {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Expansion, String> {
+        match name {
+            "clique" => Ok(Expansion::Clique),
+            "star" => Ok(Expansion::Star),
+            _ => Err(format!("unknown expansion: {} (expected clique or star)", name)),
+        }
+    }
+}
+
+/// Load one hyperedge per line, listing its author ids separated by whitespace or commas.
+/// Comments are skipped, and repeated authors within a line count once.
+pub fn load_hypergraph<P: AsRef<Path>>(path: P) -> io::Result<Hypergraph> {
+    let mut hyperedges = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let Some(fields) = line_fields(&line, true) else {
+            continue;
+        };
+        let mut seen = HashSet::new();
+        let authors: Vec<usize> = fields
+            .filter_map(|field| field.parse().ok())
+            .filter(|author| seen.insert(*author))
+            .collect();
+        if !authors.is_empty() {
+            hyperedges.push(authors);
+        }
+    }
+    Ok(Hypergraph { hyperedges })
+}
+
+impl Hypergraph {
+    /// Id given to the node of the first paper by the star expansion: one past the largest
+    /// author id, so paper `i` becomes node `first_paper_id() + i`.
+    pub fn first_paper_id(&self) -> usize {
+        self.hyperedges.iter().flatten().max().map_or(0, |&max| max + 1)
+    }
+
+    /// Expand into a simple graph. Single-author papers add isolated authors under the
+    /// clique expansion; papers shared by the same pair of authors yield one edge.
+    pub fn expand(&self, expansion: Expansion) -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let mut index: HashMap<usize, NodeIndex> = HashMap::new();
+        let mut node = |graph: &mut AuthorGraph, id: usize| *index.entry(id).or_insert_with(|| graph.add_node(id));
+        let mut seen = HashSet::new();
+        let first_paper = self.first_paper_id();
+        for (paper, authors) in self.hyperedges.iter().enumerate() {
+            match expansion {
+                Expansion::Clique => {
+                    let nodes: Vec<NodeIndex> = authors.iter().map(|&author| node(&mut graph, author)).collect();
+                    for (i, &a) in nodes.iter().enumerate() {
+                        for &b in &nodes[i + 1..] {
+                            if seen.insert((a.min(b), a.max(b))) {
+                                graph.add_edge(a, b, ());
+                            }
+                        }
+                    }
+                }
+                Expansion::Star => {
+                    let centre = node(&mut graph, first_paper + paper);
+                    for &author in authors {
+                        let author = node(&mut graph, author);
+                        graph.add_edge(centre, author, ());
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansions() {
+        let hypergraph = Hypergraph { hyperedges: vec![vec![1, 2, 3], vec![2, 3], vec![7]] };
+        let clique = hypergraph.expand(Expansion::Clique);
+        assert_eq!(clique.node_count(), 4);
+        // Pair 2-3 co-wrote twice but is linked once.
+        assert_eq!(clique.edge_count(), 3);
+
+        let star = hypergraph.expand(Expansion::Star);
+        assert_eq!(hypergraph.first_paper_id(), 8);
+        // Four authors and three papers, one edge per authorship.
+        assert_eq!(star.node_count(), 7);
+        assert_eq!(star.edge_count(), 6);
+        let paper = star.node_indices().find(|&n| star[n] == 8).unwrap();
+        assert_eq!(star.neighbors(paper).count(), 3);
+    }
+
+    #[test]
+    fn test_load_hypergraph() {
+        let path = "hypergraph_test.txt";
+        std::fs::write(path, "# one paper per line\n1 2 3\n4,5\n\n6 6\n").unwrap();
+        let hypergraph = load_hypergraph(path).unwrap();
+        assert_eq!(hypergraph.hyperedges, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
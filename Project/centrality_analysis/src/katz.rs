@@ -0,0 +1,97 @@
+use crate::AuthorGraph;
+use std::collections::HashMap;
+
+/// Power iterations after which Katz centrality and the spectral radius stop.
+const MAX_ITERATIONS: usize = 1000;
+/// Convergence threshold: the L1 change for Katz, the relative gap between the bounds for
+/// the spectral radius.
+const TOLERANCE: f64 = 1e-10;
+
+/// Largest adjacency eigenvalue, from above: the Collatz–Wielandt bound `max (Ax)_i / x_i`
+/// of the power iteration vector, which never underestimates it. The iteration runs on
+/// `A + I` so that bipartite graphs, whose spectrum is symmetric, still converge.
+pub fn spectral_radius(graph: &AuthorGraph) -> f64 {
+    if graph.edge_count() == 0 {
+        return 0.0;
+    }
+    let mut x = vec![1.0; graph.node_count()];
+    let mut upper = f64::INFINITY;
+    for _ in 0..MAX_ITERATIONS {
+        let y: Vec<f64> = graph
+            .node_indices()
+            .map(|node| x[node.index()] + graph.neighbors(node).map(|other| x[other.index()]).sum::<f64>())
+            .collect();
+        let lower = x.iter().zip(&y).map(|(a, b)| a * b).sum::<f64>() / x.iter().map(|a| a * a).sum::<f64>();
+        upper = x.iter().zip(&y).filter(|(a, _)| **a > 0.0).map(|(a, b)| b / a).fold(0.0, f64::max);
+        if upper - lower <= TOLERANCE * upper {
+            break;
+        }
+        let largest = y.iter().copied().fold(0.0, f64::max);
+        x = y.into_iter().map(|value| value / largest).collect();
+    }
+    upper - 1.0
+}
+
+/// Katz centrality `x = alpha A x + beta`, scaled to unit Euclidean norm as in networkx:
+/// every walk ending at an author counts, attenuated by `alpha` per step, so hubs that
+/// sit in small or barely connected parts of the graph are not zeroed out as they are by
+/// eigenvector centrality. The series only converges for `alpha` below the reciprocal of
+/// the spectral radius, which is checked first.
+pub fn katz_centrality(graph: &AuthorGraph, alpha: f64, beta: f64) -> Result<HashMap<usize, f64>, String> {
+    if alpha <= 0.0 {
+        return Err(format!("alpha must be positive, got {}", alpha));
+    }
+    let radius = spectral_radius(graph);
+    if alpha * radius >= 1.0 {
+        return Err(format!(
+            "alpha {} is not below 1 / spectral radius = {:.6}, so Katz centrality diverges",
+            alpha,
+            1.0 / radius
+        ));
+    }
+    let mut x = vec![0.0; graph.node_count()];
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<f64> = graph
+            .node_indices()
+            .map(|node| alpha * graph.neighbors(node).map(|other| x[other.index()]).sum::<f64>() + beta)
+            .collect();
+        let change: f64 = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum();
+        x = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let norm = if norm > 0.0 { norm } else { 1.0 };
+    Ok(graph.node_indices().map(|node| (graph[node], x[node.index()] / norm)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph_from_reader;
+
+    #[test]
+    fn test_spectral_radius() {
+        let star = load_graph_from_reader("0 1\n0 2\n0 3\n".as_bytes()).unwrap();
+        assert!((spectral_radius(&star) - 3f64.sqrt()).abs() < 1e-8);
+        // The 4-cycle is bipartite, with eigenvalues 2 and -2.
+        let cycle = load_graph_from_reader("0 1\n1 2\n2 3\n3 0\n".as_bytes()).unwrap();
+        let radius = spectral_radius(&cycle);
+        assert!(radius >= 2.0 && radius - 2.0 < 1e-8, "{}", radius);
+    }
+
+    #[test]
+    fn test_katz_matches_networkx() {
+        let graph = load_graph_from_reader("0 1\n0 2\n0 3\n3 4\n".as_bytes()).unwrap();
+        // networkx.katz_centrality(G, alpha=0.2, beta=1.0), which stops at a looser tolerance.
+        let expected = [0.5475068, 0.4009520, 0.4009520, 0.4783772, 0.3871261];
+        let katz = katz_centrality(&graph, 0.2, 1.0).unwrap();
+        for (author, value) in expected.iter().enumerate() {
+            assert!((katz[&author] - value).abs() < 1e-6, "{}: {}", author, katz[&author]);
+        }
+        // The spectral radius is about 1.848, so alpha must stay below about 0.541.
+        assert!(katz_centrality(&graph, 0.6, 1.0).is_err());
+        assert!(katz_centrality(&graph, 0.0, 1.0).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{largest_component, AuthorGraph, EdgeWeights, WeightedAuthorGraph};
+use petgraph::algo::dijkstra;
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::Undirected;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+/// How landmarks are chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// The highest-degree authors, which sit on many shortest paths.
+    Degree,
+    /// Farthest-first traversal from a random author of the largest component, which
+    /// spreads landmarks across it.
+    Farthest,
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Selection, String> {
+        match name {
+            "degree" => Ok(Selection::Degree),
+            "farthest" => Ok(Selection::Farthest),
+            _ => Err(format!("unknown landmark selection: {} (expected degree or farthest)", name)),
+        }
+    }
+}
+
+/// Lower and upper bounds on a shortest-path distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceBounds {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Precomputed BFS (or Dijkstra) distances from a few landmark authors.
+#[derive(Debug, Clone)]
+pub struct Landmarks {
+    pub nodes: Vec<NodeIndex>,
+    /// `distances[i][v]`: distance from landmark `i` to node `v`, infinite if unreached.
+    distances: Vec<Vec<f64>>,
+    /// Per landmark: sum of distances to, and number of, the nodes it reaches (itself included).
+    totals: Vec<(f64, usize)>,
+    /// Shortest edge length, the least distance between two different authors.
+    shortest_edge: f64,
+}
+
+impl Landmarks {
+    /// Run one BFS per landmark; `seed` only matters for `Selection::Farthest`.
+    pub fn select(graph: &AuthorGraph, count: usize, selection: Selection, seed: u64) -> Landmarks {
+        Landmarks::from_searches(graph, count, selection, seed, 1.0, |source| bfs(graph, source))
+    }
+
+    /// Like `select`, with Dijkstra distances over edge lengths, the weights read as
+    /// `weights` says.
+    pub fn select_weighted(
+        graph: &WeightedAuthorGraph,
+        weights: EdgeWeights,
+        count: usize,
+        selection: Selection,
+        seed: u64,
+    ) -> Landmarks {
+        let lengths = weights.lengths(graph);
+        let shortest_edge = lengths.edge_weights().copied().fold(f64::INFINITY, f64::min);
+        Landmarks::from_searches(&lengths, count, selection, seed, shortest_edge, |source| {
+            let mut distance = vec![f64::INFINITY; lengths.node_count()];
+            for (node, length) in dijkstra(&lengths, source, None, |edge| *edge.weight()) {
+                distance[node.index()] = length;
+            }
+            distance
+        })
+    }
+
+    /// Choose the landmarks, `search` giving the distances from one of them.
+    fn from_searches<E>(
+        graph: &Graph<usize, E, Undirected>,
+        count: usize,
+        selection: Selection,
+        seed: u64,
+        shortest_edge: f64,
+        search: impl Fn(NodeIndex) -> Vec<f64>,
+    ) -> Landmarks {
+        let count = count.min(graph.node_count());
+        let mut nodes = Vec::with_capacity(count);
+        let mut distances = Vec::with_capacity(count);
+        match selection {
+            Selection::Degree => {
+                let mut by_degree: Vec<NodeIndex> = graph.node_indices().collect();
+                by_degree.sort_by_key(|&node| (std::cmp::Reverse(graph.neighbors(node).count()), node));
+                for node in by_degree.into_iter().take(count) {
+                    nodes.push(node);
+                    distances.push(search(node));
+                }
+            }
+            Selection::Farthest => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let component = largest_component(graph);
+                // Distance to the nearest chosen landmark.
+                let mut nearest = vec![f64::INFINITY; graph.node_count()];
+                let Some(&start) = component.get(rng.gen_range(0..component.len().max(1))) else {
+                    return Landmarks { nodes, distances, totals: Vec::new(), shortest_edge };
+                };
+                let mut next = start;
+                while nodes.len() < count {
+                    let row = search(next);
+                    for (best, &d) in nearest.iter_mut().zip(&row) {
+                        *best = best.min(d);
+                    }
+                    nodes.push(next);
+                    distances.push(row);
+                    let farthest = (0..nearest.len())
+                        .filter(|&v| nearest[v].is_finite())
+                        .max_by(|&a, &b| nearest[a].total_cmp(&nearest[b]).then(b.cmp(&a)));
+                    match farthest {
+                        Some(v) if nearest[v] > 0.0 => next = NodeIndex::new(v),
+                        _ => break,
+                    }
+                }
+            }
+        }
+        let totals = distances
+            .iter()
+            .map(|row| {
+                let reached = row.iter().filter(|d| d.is_finite());
+                (reached.clone().sum(), reached.count())
+            })
+            .collect();
+        Landmarks { nodes, distances, totals, shortest_edge }
+    }
+
+    /// Triangle-inequality bounds on the distance between `a` and `b`, or `None` when no
+    /// landmark reaches both (they may still be connected through unsampled parts).
+    pub fn distance_bounds(&self, a: NodeIndex, b: NodeIndex) -> Option<DistanceBounds> {
+        if a == b {
+            return Some(DistanceBounds { lower: 0.0, upper: 0.0 });
+        }
+        let mut bounds: Option<DistanceBounds> = None;
+        for row in &self.distances {
+            let (da, db) = (row[a.index()], row[b.index()]);
+            if !da.is_finite() || !db.is_finite() {
+                continue;
+            }
+            let lower = (da - db).abs();
+            let upper = da + db;
+            bounds = Some(match bounds {
+                None => DistanceBounds { lower: lower.max(self.shortest_edge), upper },
+                Some(b) => DistanceBounds { lower: b.lower.max(lower), upper: b.upper.min(upper) },
+            });
+        }
+        bounds
+    }
+
+    /// Lower bound on the closeness centrality of `node` within its component,
+    /// (reachable - 1) / sum of distances, via its best landmark, in O(landmarks).
+    ///
+    /// For a landmark `l` reaching `r` nodes with distance sum `S`, the distance sum of
+    /// `node` is at most `(r - 2) * d(node, l) + S`.
+    pub fn approximate_closeness(&self, node: NodeIndex) -> Option<f64> {
+        self.distances
+            .iter()
+            .zip(&self.totals)
+            .filter(|(row, _)| row[node.index()].is_finite())
+            .map(|(row, &(sum, reached))| {
+                let d = row[node.index()];
+                let bound = reached.saturating_sub(2) as f64 * d + sum;
+                if bound == 0.0 {
+                    0.0
+                } else {
+                    (reached - 1) as f64 / bound
+                }
+            })
+            .fold(None, |best: Option<f64>, c| Some(best.map_or(c, |b| b.max(c))))
+    }
+}
+
+/// Hop distances from `source` to every node.
+fn bfs(graph: &AuthorGraph, source: NodeIndex) -> Vec<f64> {
+    let mut distance = vec![f64::INFINITY; graph.node_count()];
+    distance[source.index()] = 0.0;
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        for next in graph.neighbors(node) {
+            if distance[next.index()].is_infinite() {
+                distance[next.index()] = distance[node.index()] + 1.0;
+                queue.push_back(next);
+            }
+        }
+    }
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(n: usize) -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..n).map(|author| graph.add_node(author)).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_bounds_contain_true_distance() {
+        let graph = path(10);
+        let landmarks = Landmarks::select(&graph, 3, Selection::Farthest, 1);
+        for a in graph.node_indices() {
+            let exact = bfs(&graph, a);
+            for b in graph.node_indices() {
+                let bounds = landmarks.distance_bounds(a, b).unwrap();
+                let d = exact[b.index()];
+                assert!(bounds.lower <= d && d <= bounds.upper, "{:?} vs {}", bounds, d);
+            }
+        }
+        // On a path, an endpoint landmark pins every distance exactly.
+        let ends = Landmarks::select(&graph, 2, Selection::Farthest, 4);
+        assert!(ends.nodes.contains(&NodeIndex::new(0)) || ends.nodes.contains(&NodeIndex::new(9)));
+        let bounds = ends.distance_bounds(NodeIndex::new(2), NodeIndex::new(7)).unwrap();
+        assert_eq!(bounds.lower, 5.0);
+    }
+
+    #[test]
+    fn test_weighted_bounds_contain_true_distance() {
+        // Edge i has length i + 1 read as distances, 1 / (i + 1) as similarities.
+        let graph = path(8).map(|_, &author| author, |edge, _| (edge.index() + 1) as f64);
+        for weights in [EdgeWeights::Distance, EdgeWeights::Similarity] {
+            let lengths = weights.lengths(&graph);
+            let landmarks = Landmarks::select_weighted(&graph, weights, 2, Selection::Farthest, 3);
+            for a in graph.node_indices() {
+                let exact = dijkstra(&lengths, a, None, |edge| *edge.weight());
+                for b in graph.node_indices() {
+                    let bounds = landmarks.distance_bounds(a, b).unwrap();
+                    let d = exact[&b];
+                    assert!(bounds.lower <= d + 1e-12 && d <= bounds.upper + 1e-12, "{:?} vs {}", bounds, d);
+                }
+            }
+        }
+        // Author 1 is 2 from author 2 and 14 from author 5, which are 3 + 4 + 5 apart.
+        let single = Landmarks::select_weighted(&graph, EdgeWeights::Distance, 1, Selection::Degree, 0);
+        assert_eq!(single.nodes, vec![NodeIndex::new(1)]);
+        let bounds = single.distance_bounds(NodeIndex::new(2), NodeIndex::new(5)).unwrap();
+        assert_eq!(bounds, DistanceBounds { lower: 12.0, upper: 16.0 });
+    }
+
+    #[test]
+    fn test_approximate_closeness_is_exact_at_landmark() {
+        let graph = path(5);
+        let landmarks = Landmarks::select(&graph, 1, Selection::Degree, 0);
+        let landmark = landmarks.nodes[0];
+        let exact: f64 = bfs(&graph, landmark).iter().sum();
+        let estimate = landmarks.approximate_closeness(landmark).unwrap();
+        assert!((estimate - 4.0 / exact).abs() < 1e-12);
+        // Elsewhere the estimate never exceeds the true closeness.
+        let end = NodeIndex::new(0);
+        let true_closeness = 4.0 / bfs(&graph, end).iter().sum::<f64>();
+        assert!(landmarks.approximate_closeness(end).unwrap() <= true_closeness + 1e-12);
+    }
+}
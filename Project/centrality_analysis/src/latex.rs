@@ -0,0 +1,159 @@
+use crate::{top_k, Centralities};
+use std::str::FromStr;
+
+/// A column of the LaTeX ranking table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Rank,
+    Author,
+    /// Scores under one of the measures of [`Centralities::measures`].
+    Measure(String),
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Column, String> {
+        match name.trim() {
+            "" => Err("empty column name".to_string()),
+            "rank" => Ok(Column::Rank),
+            "author" => Ok(Column::Author),
+            measure => Ok(Column::Measure(measure.to_string())),
+        }
+    }
+}
+
+/// Parse a comma-separated column list such as `rank,author,degree`.
+pub fn parse_columns(list: &str) -> Result<Vec<Column>, String> {
+    list.split(',').map(str::parse).collect()
+}
+
+/// Escape the characters LaTeX treats specially in running text.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A booktabs `table` of the top `k` authors by `rank_by`, with the given columns, ready
+/// to paste into a document that loads the booktabs package.
+pub fn ranking_table(
+    centralities: &Centralities,
+    rank_by: &str,
+    columns: &[Column],
+    k: usize,
+    dataset: &str,
+) -> Result<String, String> {
+    if columns.is_empty() {
+        return Err("the table needs at least one column".to_string());
+    }
+    let measures = centralities.measures();
+    let names: Vec<&str> = measures.iter().map(|(name, _)| *name).collect();
+    let lookup = |name: &str| {
+        let found = measures.iter().find(|(measure, _)| *measure == name);
+        found.map(|(_, scores)| *scores).ok_or_else(|| format!("unknown measure: {} (expected {})", name, names.join(", ")))
+    };
+    let ranking = top_k(lookup(rank_by)?, k);
+    for column in columns {
+        if let Column::Measure(name) = column {
+            lookup(name)?;
+        }
+    }
+
+    let header: Vec<String> = columns
+        .iter()
+        .map(|column| match column {
+            Column::Rank => "Rank".to_string(),
+            Column::Author => "Author".to_string(),
+            Column::Measure(name) => escape(&capitalize(name)),
+        })
+        .collect();
+    let mut table = String::new();
+    table.push_str("% Requires \\usepackage{booktabs}\n");
+    table.push_str("\\begin{table}[htbp]\n\\centering\n");
+    table.push_str(&format!(
+        "\\caption{{Top {} authors by {} centrality ({})}}\n",
+        ranking.len(),
+        escape(rank_by),
+        escape(dataset)
+    ));
+    table.push_str(&format!("\\label{{tab:top-{}}}\n", rank_by));
+    table.push_str(&format!("\\begin{{tabular}}{{{}}}\n", "r".repeat(columns.len())));
+    table.push_str("\\toprule\n");
+    table.push_str(&format!("{} \\\\\n", header.join(" & ")));
+    table.push_str("\\midrule\n");
+    for (position, (author, _)) in ranking.iter().enumerate() {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                Column::Rank => (position + 1).to_string(),
+                Column::Author => author.to_string(),
+                Column::Measure(name) => lookup(name).map(|scores| format_score(scores[author])).unwrap_or_default(),
+            })
+            .collect();
+        table.push_str(&format!("{} \\\\\n", cells.join(" & ")));
+    }
+    table.push_str("\\bottomrule\n\\end{tabular}\n\\end{table}\n");
+    Ok(table)
+}
+
+/// Whole numbers as integers, other scores to four decimal places.
+fn format_score(score: f64) -> String {
+    if score.fract() == 0.0 {
+        format!("{}", score)
+    } else {
+        format!("{:.4}", score)
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn centralities() -> Centralities {
+        let scores = |values: &[(usize, f64)]| values.iter().copied().collect::<HashMap<_, _>>();
+        Centralities {
+            degree: scores(&[(7, 3.0), (8, 1.0), (9, 2.0)]),
+            betweenness: scores(&[(7, 0.25), (8, 0.6), (9, 0.123456)]),
+            closeness: scores(&[(7, 0.5), (8, 0.3), (9, 0.4)]),
+            harmonic: scores(&[(7, 0.6), (8, 0.35), (9, 0.45)]),
+            eigenvector: scores(&[(7, 0.5), (8, 0.2), (9, 0.4)]),
+            eigenvector_iterations: 12,
+            eigenvector_residual: 1e-7,
+        }
+    }
+
+    #[test]
+    fn test_ranking_table() {
+        let columns = parse_columns("rank,author,degree,betweenness").unwrap();
+        let table = ranking_table(&centralities(), "degree", &columns, 2, "ca_test.txt").unwrap();
+        assert!(table.contains("\\caption{Top 2 authors by degree centrality (ca\\_test.txt)}"));
+        assert!(table.contains("\\begin{tabular}{rrrr}\n\\toprule\nRank & Author & Degree & Betweenness \\\\\n\\midrule\n"));
+        assert!(table.contains("\\midrule\n1 & 7 & 3 & 0.2500 \\\\\n2 & 9 & 2 & 0.1235 \\\\\n\\bottomrule\n"));
+
+        assert!(ranking_table(&centralities(), "katz", &columns, 2, "x").is_err());
+        let unknown = parse_columns("rank,pagerank").unwrap();
+        assert!(ranking_table(&centralities(), "degree", &unknown, 2, "x").is_err());
+        assert!(parse_columns("rank,,author").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::AuthorGraph;
+use petgraph::algo::kosaraju_scc;
+use petgraph::graph::NodeIndex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+/// Subspace iterations used for spectral coordinates.
+const SPECTRAL_ITERATIONS: usize = 500;
+
+/// Node positions indexed by `NodeIndex::index()`, inside the square [-1, 1]².
+pub type Layout = Vec<(f64, f64)>;
+
+/// Place nodes evenly on a circle, keeping the members of each connected component
+/// next to each other (largest component first).
+pub fn circular_layout(graph: &AuthorGraph) -> Layout {
+    let mut components = kosaraju_scc(graph);
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+
+    let count = graph.node_count().max(1) as f64;
+    let mut layout = vec![(0.0, 0.0); graph.node_count()];
+    let ordered = components.iter().flat_map(|component| component.iter());
+    for (position, node) in ordered.enumerate() {
+        let angle = TAU * position as f64 / count;
+        layout[node.index()] = (angle.cos(), angle.sin());
+    }
+    layout
+}
+
+/// Spectral coordinates of every node: the `dims` leading non-trivial eigenvectors of
+/// the regularized normalized adjacency matrix.
+///
+/// Rows are indexed by `NodeIndex::index()`. Coordinates are computed per connected
+/// component, so they are only comparable within a component; components too small to
+/// have `dims` non-trivial directions sit at the origin.
+pub fn spectral_embedding(graph: &AuthorGraph, dims: usize) -> Vec<Vec<f64>> {
+    let mut embedding = vec![vec![0.0; dims]; graph.node_count()];
+    for component in kosaraju_scc(graph) {
+        if component.len() <= dims + 1 {
+            continue;
+        }
+        let coordinates = component_coordinates(graph, &component, dims);
+        for (node, row) in component.iter().zip(coordinates) {
+            embedding[node.index()] = row;
+        }
+    }
+    embedding
+}
+
+/// Spectral coordinates of one connected component, in the order of `members`.
+fn component_coordinates(graph: &AuthorGraph, members: &[NodeIndex], dims: usize) -> Vec<Vec<f64>> {
+    let local: HashMap<NodeIndex, usize> = members.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+    let adjacency: Vec<Vec<usize>> = members
+        .iter()
+        .map(|&node| graph.neighbors(node).map(|other| local[&other]).collect())
+        .collect();
+    spectral_coordinates(&adjacency, dims)
+}
+
+/// Orthogonal iteration for the `dims + 1` leading eigenvectors of a connected graph's
+/// normalized adjacency, dropping the first (trivial) one.
+///
+/// Degrees are regularized by the mean degree (Qin & Rohe), which keeps the eigenvectors
+/// from localizing on dangling chains of low-degree authors.
+fn spectral_coordinates(adjacency: &[Vec<usize>], dims: usize) -> Vec<Vec<f64>> {
+    let n = adjacency.len();
+    let tau = adjacency.iter().map(|row| row.len()).sum::<usize>() as f64 / n.max(1) as f64;
+    let inv_sqrt_degree: Vec<f64> = adjacency
+        .iter()
+        .map(|row| 1.0 / (row.len() as f64 + tau).sqrt().max(f64::MIN_POSITIVE))
+        .collect();
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut block: Vec<Vec<f64>> = (0..=dims)
+        .map(|_| (0..n).map(|_| rng.gen_range(-1.0..1.0)).collect())
+        .collect();
+    for _ in 0..SPECTRAL_ITERATIONS {
+        for column in block.iter_mut() {
+            // (I + N) / 2 has the same eigenvectors as N but a non-negative spectrum.
+            let mut next: Vec<f64> = column.iter().map(|v| 0.5 * v).collect();
+            for (i, row) in adjacency.iter().enumerate() {
+                for &j in row {
+                    next[i] += 0.5 * inv_sqrt_degree[i] * inv_sqrt_degree[j] * column[j];
+                }
+            }
+            *column = next;
+        }
+        orthonormalize(&mut block);
+    }
+
+    (0..n)
+        .map(|i| block[1..].iter().map(|column| column[i]).collect())
+        .collect()
+}
+
+/// Modified Gram-Schmidt on the columns of `block`.
+fn orthonormalize(block: &mut [Vec<f64>]) {
+    for k in 0..block.len() {
+        let (done, rest) = block.split_at_mut(k);
+        let column = &mut rest[0];
+        for previous in done.iter() {
+            let dot: f64 = column.iter().zip(previous).map(|(a, b)| a * b).sum();
+            for (value, p) in column.iter_mut().zip(previous) {
+                *value -= dot * p;
+            }
+        }
+        let norm = column.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            column.iter_mut().for_each(|v| *v /= norm);
+        }
+    }
+}
+
+/// Spectral layout: the largest component is placed by its two leading spectral
+/// coordinates, and the remaining components are packed on a ring around it.
+pub fn spectral_layout(graph: &AuthorGraph) -> Layout {
+    let mut components = kosaraju_scc(graph);
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    let mut layout = vec![(0.0, 0.0); graph.node_count()];
+    let Some((giant, rest)) = components.split_first() else {
+        return layout;
+    };
+
+    let coordinates = component_coordinates(graph, giant, 2);
+
+    // Keeps the corners of the square inside the ring of small components.
+    let extent = if rest.is_empty() { 1.0 } else { 0.6 };
+    // Spectral coordinates of collaboration networks concentrate on a few dense cliques,
+    // so each axis is spread by rank; this keeps the ordering but uses the whole area.
+    let xs = spread_by_rank(coordinates.iter().map(|c| c[0]).collect());
+    let ys = spread_by_rank(coordinates.iter().map(|c| c[1]).collect());
+    for ((&node, x), y) in giant.iter().zip(xs).zip(ys) {
+        layout[node.index()] = (extent * x, extent * y);
+    }
+
+    // Small components: each gets an arc of the outer ring proportional to its size.
+    let ring_nodes: usize = rest.iter().map(|component| component.len()).sum();
+    let mut offset = 0.0;
+    for component in rest {
+        let share = component.len() as f64 / ring_nodes as f64;
+        let angle = TAU * (offset + share / 2.0);
+        offset += share;
+        let center = (0.92 * angle.cos(), 0.92 * angle.sin());
+        let radius = (0.5 * TAU * share * 0.92).min(0.05);
+        for (position, node) in component.iter().enumerate() {
+            let theta = TAU * position as f64 / component.len() as f64;
+            layout[node.index()] = (center.0 + radius * theta.cos(), center.1 + radius * theta.sin());
+        }
+    }
+    layout
+}
+
+/// Replace each value by its rank, mapped evenly onto [-1, 1].
+fn spread_by_rank(values: Vec<f64>) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let last = values.len().max(2) as f64 - 1.0;
+    let mut spread = vec![0.0; values.len()];
+    for (rank, &i) in order.iter().enumerate() {
+        spread[i] = 2.0 * rank as f64 / last - 1.0;
+    }
+    spread
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circular_layout_on_unit_circle() {
+        let mut graph = AuthorGraph::new_undirected();
+        let n1 = graph.add_node(1);
+        let n2 = graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(n1, n2, ());
+
+        let layout = circular_layout(&graph);
+        assert_eq!(layout.len(), 3);
+        for (x, y) in layout {
+            assert!(((x * x + y * y) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_spectral_embedding_splits_path_in_half() {
+        // The leading non-trivial vector of a path separates its two halves.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|author| graph.add_node(author)).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+
+        let embedding = spectral_embedding(&graph, 1);
+        let first: Vec<f64> = embedding.iter().map(|row| row[0]).collect();
+        assert!(first[..3].iter().all(|&v| v * first[0] > 0.0), "{:?}", first);
+        assert!(first[3..].iter().all(|&v| v * first[0] < 0.0), "{:?}", first);
+    }
+
+    #[test]
+    fn test_spectral_layout_stays_in_bounds() {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..7).map(|author| graph.add_node(author)).collect();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (4, 5)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+
+        let layout = spectral_layout(&graph);
+        assert_eq!(layout.len(), 7);
+        assert!(layout.iter().all(|&(x, y)| x.abs() <= 1.0 && y.abs() <= 1.0));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::AuthorGraph;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+/// Line graph of a collaboration graph: one node per collaboration, linked when two
+/// collaborations share an author. Node weights index into `edges`, so any node measure
+/// on `graph` becomes an edge measure on the original.
+#[derive(Debug, Clone)]
+pub struct LineGraph {
+    pub graph: AuthorGraph,
+    /// Author pair of every original edge, smaller id first.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl LineGraph {
+    /// Author pair behind a line-graph node weight.
+    pub fn collaboration(&self, id: usize) -> (usize, usize) {
+        self.edges[id]
+    }
+}
+
+/// Build the line graph; an author of degree d contributes d(d-1)/2 edges.
+pub fn line_graph(graph: &AuthorGraph) -> LineGraph {
+    let mut line = AuthorGraph::new_undirected();
+    let mut edges = Vec::with_capacity(graph.edge_count());
+    for edge in graph.edge_references() {
+        let (a, b) = (graph[edge.source()], graph[edge.target()]);
+        line.add_node(edge.id().index());
+        edges.push((a.min(b), a.max(b)));
+    }
+    for node in graph.node_indices() {
+        let incident: Vec<_> = graph.edges(node).map(|edge| edge.id()).collect();
+        for (i, &a) in incident.iter().enumerate() {
+            for &b in &incident[i + 1..] {
+                // Edge and line-graph node indices coincide.
+                line.add_edge(NodeIndex::new(a.index()), NodeIndex::new(b.index()), ());
+            }
+        }
+    }
+    LineGraph { graph: line, edges }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::degree_centrality;
+
+    #[test]
+    fn test_line_graph_of_star_and_tail() {
+        // Star 0-1, 0-2, 0-3 plus 3-4: the star's edges form a triangle in the line graph.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for &(a, b) in &[(0, 1), (0, 2), (0, 3), (3, 4)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        let line = line_graph(&graph);
+        assert_eq!(line.graph.node_count(), 4);
+        assert_eq!(line.graph.edge_count(), 3 + 1);
+
+        let degree = degree_centrality(&line.graph);
+        let busiest = (0..4).max_by_key(|id| degree[id]).unwrap();
+        assert_eq!(line.collaboration(busiest), (0, 3));
+        assert_eq!(line.collaboration(3), (3, 4));
+    }
+}
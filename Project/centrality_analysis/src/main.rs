@@ -1,269 +1,2581 @@
-use petgraph::{graph::Graph, Undirected};
-use petgraph::algo::{connected_components, dijkstra};
-use petgraph::dot::{Dot, Config};
-use petgraph::visit::{EdgeRef, IntoNodeReferences};
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+mod cli;
+mod explore;
+
+use centrality_analysis::community::{
+    community_count, consensus_communities, evaluate_partition, ground_truth_partition, load_ground_truth, louvain,
+    modularity, overlapping_authors, resolution_range, resolution_sweep, load_partition, write_partition_with, Method,
+};
+use centrality_analysis::anf::{exact_neighbourhood_function, hyper_anf};
+use centrality_analysis::audit::AuditLog;
+use centrality_analysis::betweenness::{
+    approximate_betweenness, betweenness_centrality, edge_betweenness_centrality, path_centrality, percolation_centrality, top_k_betweenness,
+    weighted_betweenness_centrality, PathCentrality,
+};
+use centrality_analysis::bootstrap::bootstrap_ranks;
+use centrality_analysis::closeness::{
+    approximate_closeness, closeness_centrality, harmonic_centrality, weighted_closeness_centrality,
+};
+use centrality_analysis::cliques::{clique_number, clique_size_distribution, maximal_cliques, write_clique};
+use centrality_analysis::coarsen::{coarsen, coarsened_layout};
+use centrality_analysis::community::community_sizes;
+use centrality_analysis::cover::{independent_set, vertex_cover};
+use centrality_analysis::densest::densest_subgraph;
+use centrality_analysis::eccentricity::{extent, weighted_extent, Extent};
+use centrality_analysis::fingerprint::wl_fingerprint;
+use centrality_analysis::flow::min_cut;
+use centrality_analysis::gephi::{gephi_events, stream_to_gephi};
+use centrality_analysis::girvan_newman::girvan_newman;
+use centrality_analysis::group::{group_centrality, load_group};
+use centrality_analysis::hits::hits;
+use centrality_analysis::hypergraph::{load_hypergraph, Expansion};
+use centrality_analysis::current_flow::current_flow_betweenness;
+use centrality_analysis::katz::{katz_centrality, spectral_radius};
+use centrality_analysis::line_graph::line_graph;
+use centrality_analysis::landmarks::{Landmarks, Selection};
+use centrality_analysis::latex::{parse_columns, ranking_table};
+use centrality_analysis::matching::maximum_matching;
+use centrality_analysis::multilayer::Multilayer;
+use centrality_analysis::graphml::load_graphml;
+use centrality_analysis::nodelink::{load_node_link, write_node_link};
+use centrality_analysis::noise::{rank_stability, rewire};
+use centrality_analysis::normalize::{canonical_edges, write_canonical};
+use centrality_analysis::output::{sanitize_file_name, ExistingOutput, OutputFile};
+use centrality_analysis::pagerank::{pagerank, pagerank_with, PageRankOptions};
+use centrality_analysis::paths::{ego_subgraph, k_shortest_paths, simple_paths, weighted_k_shortest_paths, weighted_simple_paths};
+use centrality_analysis::rank_diff::{rank_diff, RankDiff};
+use centrality_analysis::results::{read_results, write_results, AnalysisResults, ResultFormat};
+use centrality_analysis::rich_club::{random_rich_club, rich_club, SWAPS_PER_EDGE};
+use centrality_analysis::sbm::fit_sbm;
+use centrality_analysis::signed::{global_signed_clustering, load_signed_graph, signed_clustering, triad_census};
+use centrality_analysis::signal::{heat_kernel, load_signal, neighbourhood_average};
+use centrality_analysis::stats::{
+    graph_stats, local_clustering, log_binned_strengths, strength_degree_correlation, strength_distribution, transitivity,
+    triangles_per_author, weighted_clustering, weighted_stats, WeightedClustering, EXACT_LIMIT,
+};
+use centrality_analysis::tiles::render_tiles;
+use centrality_analysis::verify::{compare, reference_for, self_loop_authors, REFERENCES};
+use centrality_analysis::figure::FigureMetadata;
+use centrality_analysis::plots::{
+    bar_chart, community_size_plot, composite_figure, degree_distribution_plot, distance_plot, path_plot, projection_plot, resolution_curve_plot,
+    rich_club_plot, weighted_network_plot,
+    EdgeScaling, NetworkStyle, Viewport,
+};
+use centrality_analysis::layout::{circular_layout, spectral_embedding, spectral_layout, Layout};
+use centrality_analysis::profile::{enable_timings, stage_totals};
+use centrality_analysis::projection::{load_embedding, tsne};
+use centrality_analysis::{
+    attribute_weighted_degree, author_index, average_weighted_distance, centrality_scores, compute_centralities, compute_weighted_centralities, degree_centrality, degree_distribution, distance_distribution,
+    eigenvector_centrality,
+    component_sizes, has_weights, largest_component, largest_component_graph, load_directed_graph_audited, load_weighted_graph_audited, log_binned_degrees, print_top, render_network, render_network_region, stage, strength, top_k, unweighted, weighted_distances, write_edge_list,
+    directed_degree_centrality, AuthorGraph, Centralities, Delimiter, DirectedAuthorGraph, DuplicatePolicy, EdgeWeights, LoadOptions,
+    SelfLoopPolicy, WeightTransform, WeightedAuthorGraph, NETWORK_PLOT,
+};
+use cli::Args;
+use petgraph::algo::connected_components;
+use petgraph::graph::Graph;
+use petgraph::{Direction, EdgeType};
+use petgraph::visit::EdgeRef;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
-use plotters::prelude::*;
-use std::fs;
-
-// Define a type alias for easier graph representation
-type AuthorGraph = Graph<usize, (), Undirected>;
-
-/// Load the dataset and build the graph.
-pub fn load_graph(file_path: &str) -> io::Result<AuthorGraph> {
-    let mut graph = AuthorGraph::new_undirected();
-    let mut node_map = HashMap::new();
-    let mut edges = HashSet::new();
-
-    if let Ok(lines) = read_lines(file_path) {
-        for line in lines {
-            let line = line?;
-            if line.starts_with('#') || line.trim().is_empty() {
-                continue;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// Main function to tie everything together.
+fn main() {
+    let raw: Vec<String> = env::args().collect();
+    let args = Args::parse(&raw[1..]);
+    let positional: Vec<&str> = args.positional.iter().map(String::as_str).collect();
+    if args.has("timings") {
+        enable_timings();
+    }
+    let _trace = match start_tracing(&args) {
+        Ok(guard) => guard,
+        Err(message) => {
+            eprintln!("{}", message);
+            return;
+        }
+    };
+
+    let result = match positional.as_slice() {
+        ["tiles", file_path] => run_tiles(file_path, &args),
+        ["figure", file_path] => run_figure(file_path, "output/figure.png", &args),
+        ["figure", file_path, output_path] => run_figure(file_path, output_path, &args),
+        ["communities", file_path] => run_communities(file_path, "output/communities.csv", &args),
+        ["communities", file_path, output_path] => run_communities(file_path, output_path, &args),
+        ["girvan-newman", file_path] => run_girvan_newman(file_path, "output/girvan_newman.csv", &args),
+        ["girvan-newman", file_path, output_path] => run_girvan_newman(file_path, output_path, &args),
+        ["modularity", file_path, specs @ ..] if !specs.is_empty() => run_modularity(file_path, specs, &args),
+        ["sweep", file_path] => run_sweep(file_path, &args),
+        ["consensus", file_path] => run_consensus(file_path, &args),
+        ["evaluate", file_path, truth_path] => run_evaluate(file_path, truth_path, &args),
+        ["sbm", file_path] => run_sbm(file_path, &args),
+        ["tsne", file_path] => run_tsne(file_path, &args),
+        ["cliques", file_path] => run_cliques(file_path, &args),
+        ["densest", file_path] => run_densest(file_path, &args),
+        ["matching", file_path] => run_matching(file_path, &args),
+        ["cover", file_path] => run_cover(file_path, &args),
+        ["cut", file_path, sources, sinks] => run_cut(file_path, sources, sinks, &args),
+        ["coarsen", file_path] => run_coarsen(file_path, &args),
+        ["stats", file_paths @ ..] if !file_paths.is_empty() => run_stats(file_paths, &args),
+        ["components", file_path] => run_components(file_path, "output/components.csv", &args),
+        ["components", file_path, output_path] => run_components(file_path, output_path, &args),
+        ["degree-distribution", file_path] => {
+            run_degree_distribution(file_path, "output/degree_distribution.csv", &args)
+        }
+        ["degree-distribution", file_path, output_path] => run_degree_distribution(file_path, output_path, &args),
+        ["triangles", file_path] => run_triangles(file_path, None, &args),
+        ["triangles", file_path, output_path] => run_triangles(file_path, Some(output_path), &args),
+        ["rich-club", file_path] => run_rich_club(file_path, "output/rich_club.csv", &args),
+        ["rich-club", file_path, output_path] => run_rich_club(file_path, output_path, &args),
+        ["paths", file_path, source, target] => run_paths(file_path, source, target, &args),
+        ["distances-from", file_path, author] => run_distances_from(file_path, author, None, &args),
+        ["distances-from", file_path, author, output_path] => {
+            run_distances_from(file_path, author, Some(output_path), &args)
+        }
+        ["bootstrap", file_path] => run_bootstrap(file_path, &args),
+        ["noise", file_path] => run_noise(file_path, "output/noise_stability.csv", &args),
+        ["noise", file_path, output_path] => run_noise(file_path, output_path, &args),
+        ["verify", file_path] => run_verify(file_path, &args),
+        ["fingerprint", file_paths @ ..] if !file_paths.is_empty() => run_fingerprint(file_paths, &args),
+        ["export-json", file_path] => run_export_json(file_path, "output/graph.json", &args),
+        ["export-json", file_path, output_path] => run_export_json(file_path, output_path, &args),
+        ["explore", file_path] => run_explore(file_path, &args),
+        ["rank-diff", first_path, second_path] => run_rank_diff_results(first_path, second_path, &args),
+        ["rank-diff", file_path, first, second] => run_rank_diff_measures(file_path, first, second, &args),
+        ["results-page", results_path] => run_results_page(results_path, &args),
+        ["normalize", file_path] => run_normalize(file_path, "output/normalized.txt", &args),
+        ["normalize", file_path, output_path] => run_normalize(file_path, output_path, &args),
+        ["smooth", file_path, values_path] => run_smooth(file_path, values_path, &args),
+        ["line-graph", file_path] => run_line_graph(file_path, &args),
+        ["hypergraph", file_path] => run_hypergraph(file_path, &args),
+        ["signed", file_path] => run_signed(file_path, &args),
+        ["multilayer", specs @ ..] if !specs.is_empty() => run_multilayer(specs, &args),
+        ["group", file_path, specs @ ..] if !specs.is_empty() => run_group(file_path, specs, &args),
+        ["betweenness", file_path] => run_betweenness(file_path, &args),
+        ["edge-betweenness", file_path] => run_edge_betweenness(file_path, "output/edge_betweenness.csv", &args),
+        ["edge-betweenness", file_path, output_path] => run_edge_betweenness(file_path, output_path, &args),
+        ["current-flow", file_path] => run_current_flow(file_path, &args),
+        ["katz", file_path] => run_katz(file_path, &args),
+        ["pagerank", file_path] => run_pagerank(file_path, &args),
+        ["closeness", file_path] => run_closeness(file_path, &args),
+        ["path-centrality", file_path] => run_path_centrality(file_path, &args),
+        ["percolation", file_path, states_path] => run_percolation(file_path, states_path, &args),
+        ["attributes", file_path, values_path] => run_attributes(file_path, values_path, &args),
+        ["anf", file_path] => run_anf(file_path, &args),
+        ["path-lengths", file_path] => run_path_lengths(file_path, "output/path_lengths.csv", &args),
+        ["path-lengths", file_path, output_path] => run_path_lengths(file_path, output_path, &args),
+        ["landmarks", file_path, author] => run_landmarks(file_path, author, None, &args),
+        ["landmarks", file_path, author, other] => run_landmarks(file_path, author, Some(other), &args),
+        [file_path] => run_analysis(file_path, &args),
+        _ => {
+            print_usage(&raw[0]);
+            Ok(())
+        }
+    };
+    if let Err(message) = &result {
+        eprintln!("{}", message);
+    }
+    if args.has("timings") {
+        print_timings();
+    }
+    if result.is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// Total time per stage recorded with --timings.
+fn print_timings() {
+    println!("\nStage timings:");
+    println!("stage\truns\ttotal");
+    for (name, total, runs) in stage_totals() {
+        println!("{}\t{}\t{:.1?}", name, runs, total);
+    }
+}
+
+/// With --trace <file>, write tracing spans of every stage in the folded format of
+/// `tracing-flame`, for `inferno-flamegraph`. The guard flushes the file when dropped.
+#[cfg(feature = "tracing")]
+fn start_tracing(args: &Args) -> Result<Option<tracing_flame::FlushGuard<std::io::BufWriter<std::fs::File>>>, String> {
+    use tracing_subscriber::prelude::*;
+    let Some(path) = args.value("trace") else {
+        return Ok(None);
+    };
+    let (layer, guard) = tracing_flame::FlameLayer::with_file(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    tracing_subscriber::registry().with(layer).init();
+    Ok(Some(guard))
+}
+
+#[cfg(not(feature = "tracing"))]
+fn start_tracing(args: &Args) -> Result<Option<()>, String> {
+    match args.value("trace") {
+        Some(_) => Err("--trace needs a build with tracing support (cargo run --features tracing)".to_string()),
+        None => Ok(None),
+    }
+}
+
+fn print_usage(program: &str) {
+    eprintln!(
+        "Usage: {} <path_to_dataset> [--bar-chart] [--top-k <k>] [--estimate] [--layout circular|spectral] [--coarsen <n>] [--viewport <x0,y0,x1,y1>] [--width <px>] [--results <file.json|.msgpack|.cbor>] [--gephi <url>] [--latex <table.tex>] [--rank-by <measure>] [--columns <c>,...] [--weights similarity|distance] [--edge-scaling linear|log]",
+        program
+    );
+    eprintln!("       {} <path_to_dataset> --directed [--top-k <k>]", program);
+    eprintln!("       {} figure <path_to_dataset> [output.png]", program);
+    eprintln!(
+        "       {} tiles <path_to_dataset> [--layout circular|spectral] [--max-zoom <z>] [--tile-size <px>]",
+        program
+    );
+    eprintln!("       {} communities <path_to_dataset> [output.csv] [--method louvain|leiden] [--resolution <r>] [--seed <s>] [--top-k <k>]", program);
+    eprintln!("       {} girvan-newman <path_to_dataset> [output.csv] [--max-splits <n>]", program);
+    eprintln!(
+        "       {} modularity <path_to_dataset> <partition.csv|louvain|leiden>... [--resolution <r>] [--seed <s>]",
+        program
+    );
+    eprintln!(
+        "       {} sweep <path_to_dataset> [--method louvain|leiden] [--min <r>] [--max <r>] [--steps <n>] [--seed <s>]",
+        program
+    );
+    eprintln!(
+        "       {} consensus <path_to_dataset> [--method louvain|leiden] [--runs <n>] [--resolution <r>] [--threshold <t>] [--seed <s>]",
+        program
+    );
+    eprintln!(
+        "       {} evaluate <path_to_dataset> <ground_truth.cmty.txt> [--method louvain|leiden] [--resolution <r>] [--seed <s>]",
+        program
+    );
+    eprintln!("       {} sbm <path_to_dataset> [--blocks <k>] [--uncorrected] [--seed <s>]", program);
+    eprintln!("       {} cliques <path_to_dataset> [--min-size <k>] [--export <n>]", program);
+    eprintln!("       {} densest <path_to_dataset>", program);
+    eprintln!("       {} matching <path_to_dataset>", program);
+    eprintln!("       {} cover <path_to_dataset>", program);
+    eprintln!("       {} cut <path_to_dataset> <src>[,<src>...] <dst>[,<dst>...]", program);
+    eprintln!("       {} coarsen <path_to_dataset> [--target <n>] [--seed <s>] [--edge-scaling linear|log]", program);
+    eprintln!("       {} stats <path_to_dataset>... [--sources <name>,...] [--estimate] [--seed <s>]", program);
+    eprintln!("       {} components <path_to_dataset> [output.csv] [--directed] [--top-k <n>]", program);
+    eprintln!("       {} degree-distribution <path_to_dataset> [output.csv] [--log-bins <per decade>] [--weighted]", program);
+    eprintln!("       {} triangles <path_to_dataset> [output.csv] [--weighted [--clustering barrat|onnela]] [--top-k <k>]", program);
+    eprintln!("       {} rich-club <path_to_dataset> [output.csv] [--random <n>] [--seed <s>]", program);
+    eprintln!(
+        "       {} paths <path_to_dataset> <source> <target> [--max-length <l>] [--limit <n>] [--k <k>] [--exclude <a>,...] [--plot <file.png>] [--plot-scope ego|full] [--weights similarity|distance]",
+        program
+    );
+    eprintln!(
+        "       {} path-lengths <path_to_dataset> [output.csv] [--estimate] [--log2-registers <b>] [--seed <s>] [--samples <k>] [--weights similarity|distance]",
+        program
+    );
+    eprintln!("       {} explore <path_to_dataset> [--top-k <k>]", program);
+    eprintln!("       {} rank-diff <first.json|.msgpack|.cbor> <second> [--measure <m>] [--top-k <k>]", program);
+    eprintln!("       {} rank-diff <path_to_dataset> <measure> <measure> [--top-k <k>]", program);
+    eprintln!("       {} distances-from <path_to_dataset> <author> [output.csv] [--weights similarity|distance]", program);
+    eprintln!(
+        "       {} bootstrap <path_to_dataset> [--measure <m>] [--resamples <n>] [--confidence <c>] [--seed <s>] [--top-k <k>]",
+        program
+    );
+    eprintln!(
+        "       {} noise <path_to_dataset> [output.csv] [--fraction <f>] [--trials <n>] [--seed <s>] [--top-k <k>]",
+        program
+    );
+    eprintln!("       {} fingerprint <path_to_dataset> [<other_dataset>...]", program);
+    eprintln!("       {} export-json <path_to_dataset> [output.json]", program);
+    eprintln!("       {} results-page <results.json|.msgpack|.cbor> [--offset <i>] [--limit <n>]", program);
+    eprintln!("       {} normalize <path_to_dataset> [output.txt] [--dense]", program);
+    eprintln!("       {} verify <path_to_dataset> [--reference <snap-name>]", program);
+    eprintln!(
+        "       {} smooth <path_to_dataset> <values.csv> [--method average|heat] [--time <t>]",
+        program
+    );
+    eprintln!("       {} line-graph <path_to_dataset> [--damping <d>] [--top-k <k>]", program);
+    eprintln!("       {} hypergraph <papers.txt> [--expansion clique|star]", program);
+    eprintln!("       {} signed <path_to_signed_dataset> [--top-k <k>]", program);
+    eprintln!("       {} multilayer [<name>=]<layer.txt>... [--damping <d>] [--top-k <k>]", program);
+    eprintln!("       {} group <path_to_dataset> [<name>=]<members.txt>...", program);
+    eprintln!(
+        "       {} betweenness <path_to_dataset> [--exact | --weighted [--weights similarity|distance] | --epsilon <e> --delta <d> --seed <s>] [--top-k <k>]",
+        program
+    );
+    eprintln!(
+        "       {} pagerank <path_to_dataset> [--directed] [--damping <d>] [--max-iterations <n>] [--tolerance <t>] [--top-k <k>]",
+        program
+    );
+    eprintln!("       {} edge-betweenness <path_to_dataset> [output.csv] [--top-k <k>]", program);
+    eprintln!("       {} current-flow <path_to_dataset> [--top-k <k>]", program);
+    eprintln!("       {} katz <path_to_dataset> [--alpha <a>] [--beta <b>] [--top-k <k>]", program);
+    eprintln!(
+        "       {} closeness <path_to_dataset> [--weighted [--weights similarity|distance] | --samples <k> --confidence <c> --seed <s>] [--top-k <k>]",
+        program
+    );
+    eprintln!("       {} path-centrality <path_to_dataset> [--measure stress|load] [--top-k <k>]", program);
+    eprintln!("       {} percolation <path_to_dataset> <states.csv> [--top-k <k>]", program);
+    eprintln!(
+        "       {} attributes <path_to_dataset> <values.csv> [--damping <d>] [--top-k <k>]",
+        program
+    );
+    eprintln!("       {} anf <path_to_dataset> [--log2-registers <b>] [--seed <s>]", program);
+    eprintln!(
+        "       {} landmarks <path_to_dataset> <author> [<other>] [--landmarks <k>] [--selection degree|farthest] [--seed <s>] [--weights similarity|distance]",
+        program
+    );
+    eprintln!(
+        "       {} tsne <path_to_dataset> [--embedding <file>] [--dims <d>] [--perplexity <p>] [--iterations <n>] [--seed <s>]",
+        program
+    );
+    eprintln!("Every command that loads an edge list accepts [--max-nodes <n>] [--max-edges <m>] [--self-loops drop|keep] [--duplicates drop|keep|aggregate] [--delimiter auto|whitespace|<char>] [--weight-transform none|log|inverse|minmax] [--largest-component] and reads node-link JSON from .json files and GraphML from .graphml files.");
+    eprintln!("Every command that writes outputs refuses to replace files named on the command line unless given [--overwrite|--append].");
+    eprintln!("Every command accepts [--timings] and, in builds with --features tracing, [--trace <file.folded>].");
+}
+
+/// Say how the shortest-path measures read the edge weights.
+fn print_weight_reading(weights: EdgeWeights) {
+    let reading = if weights == EdgeWeights::Similarity { "similarities (length 1 / weight)" } else { "distances" };
+    println!("Shortest paths read the edge weights as {}.", reading);
+}
+
+/// A path length: a whole number of hops, or a length over edge weights to 4 decimals.
+fn format_length(length: f64, weighted: bool) -> String {
+    if weighted {
+        format!("{:.4}", length)
+    } else {
+        length.to_string()
+    }
+}
+
+/// Print the diameter and radius of the largest component and how they were found.
+fn print_extent<D: std::fmt::Display>(extent: &Extent<D>, unit: &str) {
+    println!(
+        "Largest component: diameter{} {}{}, radius {}{} ({})",
+        unit,
+        if extent.exact { "" } else { "at least " },
+        extent.diameter,
+        if extent.exact { "" } else { "at most " },
+        extent.radius,
+        if extent.exact { "exact" } else { "double sweep" }
+    );
+}
+
+/// Load the dataset and print its size.
+fn load_or_report(file_path: &str, args: &Args) -> Result<AuthorGraph, String> {
+    load_audited(file_path, args).map(|(graph, _)| graph)
+}
+
+/// Like `load_or_report`, also returning the log of the input dropped while loading.
+/// Says so when the edges carry weights, which the caller ignores.
+fn load_audited(file_path: &str, args: &Args) -> Result<(AuthorGraph, AuditLog), String> {
+    if !file_path.ends_with(".json") {
+        let (graph, log) = load_weighted_audited(file_path, args)?;
+        if has_weights(&graph) {
+            println!("Note: this command ignores the edge weights.");
+        }
+        return Ok((unweighted(&graph), log));
+    }
+    reject_directed(args)?;
+    let options = load_options(args)?;
+    let mut log = AuditLog::default();
+    let graph = stage!("load", load_node_link(file_path, &options, &mut log));
+    let graph = graph.map_err(|e| format!("Failed to load graph: {}", e))?;
+    Ok((report_loaded(graph, &mut log, args), log))
+}
+
+/// Like `load_or_report`, keeping the weight column of an edge list or the weight
+/// attribute of GraphML edges.
+fn load_weighted_or_report(file_path: &str, args: &Args) -> Result<WeightedAuthorGraph, String> {
+    load_weighted_audited(file_path, args).map(|(graph, _)| graph)
+}
+
+/// Like `load_weighted_or_report`, also returning the log of the input dropped while
+/// loading.
+fn load_weighted_audited(file_path: &str, args: &Args) -> Result<(WeightedAuthorGraph, AuditLog), String> {
+    if file_path.ends_with(".json") {
+        return Err("--weighted needs an edge list with a weight column; node-link JSON has none".to_string());
+    }
+    reject_directed(args)?;
+    let options = load_options(args)?;
+    let mut log = AuditLog::default();
+    let graph = stage!(
+        "load",
+        if file_path.ends_with(".graphml") {
+            load_graphml(file_path, &options, &mut log)
+        } else {
+            load_weighted_graph_audited(file_path, &options, &mut log)
+        }
+    );
+    let graph = graph.map_err(|e| format!("Failed to load graph: {}", e))?;
+    Ok((report_loaded(graph, &mut log, args), log))
+}
+
+/// Like `load_audited`, also returning the weighted graph when the edge list has a
+/// weight column (or the GraphML edges a weight attribute) with weights other than 1.
+fn load_with_weights(file_path: &str, args: &Args) -> Result<(AuthorGraph, Option<WeightedAuthorGraph>, AuditLog), String> {
+    if file_path.ends_with(".json") {
+        let (graph, log) = load_audited(file_path, args)?;
+        return Ok((graph, None, log));
+    }
+    let (graph, log) = load_weighted_audited(file_path, args)?;
+    Ok((unweighted(&graph), has_weights(&graph).then_some(graph), log))
+}
+
+/// Fail on `--directed` in the commands that only handle undirected graphs.
+fn reject_directed(args: &Args) -> Result<(), String> {
+    if args.has("directed") {
+        return Err("--directed is supported by the default analysis, components and pagerank".to_string());
+    }
+    Ok(())
+}
+
+/// Like `load_or_report`, keeping the direction of each edge.
+fn load_directed_or_report(file_path: &str, args: &Args) -> Result<DirectedAuthorGraph, String> {
+    if file_path.ends_with(".json") || file_path.ends_with(".graphml") {
+        return Err("--directed reads edge lists, not node-link JSON or GraphML".to_string());
+    }
+    let options = load_options(args)?;
+    let mut log = AuditLog::default();
+    let graph = stage!("load", load_directed_graph_audited(file_path, &options, &mut log));
+    let graph = graph.map_err(|e| format!("Failed to load graph: {}", e))?;
+    Ok(report_loaded(graph, &mut log, args))
+}
+
+/// Print the size of a loaded graph and what was dropped on the way, and apply
+/// `--largest-component`, recording what it removes in `log`.
+fn report_loaded<E: Clone, Ty: EdgeType>(graph: Graph<usize, E, Ty>, log: &mut AuditLog, args: &Args) -> Graph<usize, E, Ty> {
+    println!("Graph loaded with {} nodes and {} edges.", graph.node_count(), graph.edge_count());
+    if !log.is_empty() {
+        println!("Preprocessing: {}", log);
+    }
+    if !args.has("largest-component") {
+        return graph;
+    }
+    let component = largest_component_graph(&graph);
+    println!(
+        "Kept the largest component: {} of {} nodes, {} edges.",
+        component.node_count(),
+        graph.node_count(),
+        component.edge_count()
+    );
+    log.record("authors outside the largest component dropped", graph.node_count() - component.node_count());
+    log.record("edges outside the largest component dropped", graph.edge_count() - component.edge_count());
+    component
+}
+
+/// Loading limits from `--max-nodes` and `--max-edges`, the `--self-loops` and
+/// `--duplicates` policies, the `--delimiter` of the fields and the `--weight-transform`.
+fn load_options(args: &Args) -> Result<LoadOptions, String> {
+    Ok(LoadOptions {
+        max_nodes: args.parse_optional("max-nodes")?,
+        max_edges: args.parse_optional("max-edges")?,
+        self_loops: args.parse_or("self-loops", SelfLoopPolicy::Drop)?,
+        duplicates: args.parse_or("duplicates", DuplicatePolicy::Drop)?,
+        delimiter: args.parse_or("delimiter", Delimiter::Detect)?,
+        weight_transform: args.parse_or("weight-transform", WeightTransform::None)?,
+    })
+}
+
+/// What writing `path` does when the file exists: `--overwrite` or `--append` if given,
+/// otherwise regenerate outputs at their default paths but refuse to replace a file named
+/// on the command line.
+fn existing_output<P: AsRef<Path>>(path: P, args: &Args) -> Result<ExistingOutput, String> {
+    match (args.has("overwrite"), args.has("append")) {
+        (true, true) => Err("--overwrite and --append cannot be combined".to_string()),
+        (true, false) => Ok(ExistingOutput::Overwrite),
+        (false, true) => Ok(ExistingOutput::Append),
+        (false, false) if path.as_ref().to_str().is_some_and(|path| args.names(path)) => Ok(ExistingOutput::Refuse),
+        (false, false) => Ok(ExistingOutput::Overwrite),
+    }
+}
+
+fn output_error(error: io::Error) -> String {
+    match error.kind() {
+        io::ErrorKind::AlreadyExists => format!("{}; pass --overwrite to replace it or --append to add to it", error),
+        io::ErrorKind::InvalidInput => format!("{}; pass --overwrite to replace it", error),
+        _ => error.to_string(),
+    }
+}
+
+/// Check an output that a library function writes whole, such as a plot, and create its
+/// directory.
+fn claim_output<P: AsRef<Path>>(path: P, args: &Args) -> Result<(), String> {
+    existing_output(&path, args)?.prepare_whole(path).map_err(output_error)
+}
+
+/// A CSV writer for `path`; appending adds rows under the existing header.
+fn csv_output<P: AsRef<Path>>(path: P, args: &Args) -> Result<csv::Writer<OutputFile>, String> {
+    existing_output(&path, args)?.csv_writer(path).map_err(output_error)
+}
+
+/// Write a text output; appending adds `contents` after the existing lines.
+fn write_output<P: AsRef<Path>>(path: P, contents: &str, args: &Args) -> Result<(), String> {
+    let mut file = existing_output(&path, args)?.create(path).map_err(output_error)?;
+    file.write_all(contents.as_bytes()).map_err(|e| e.to_string())
+}
+
+/// Default analysis: summary, centralities and the network plot.
+fn run_analysis(file_path: &str, args: &Args) -> Result<(), String> {
+    let top = args.parse_or("top-k", 10)?;
+    if args.has("directed") {
+        return run_directed_analysis(file_path, top, args);
+    }
+    let layout_name: String = args.parse_or("layout", "circular".to_string())?;
+    if layout_name != "circular" && layout_name != "spectral" {
+        return Err(format!("Unknown layout: {} (expected circular or spectral)", layout_name));
+    }
+    let viewport: Option<Viewport> = args.parse_optional("viewport")?;
+    let width: u32 = args.parse_or("width", 4096)?;
+    let coarsen_target: Option<usize> = args.parse_optional("coarsen")?;
+    let results_path = args.value("results");
+    if let Some(path) = results_path {
+        ResultFormat::from_path(path)?;
+        // Refuse a named results file before the analysis rather than after it.
+        claim_output(path, args)?;
+    }
+    if let Some(latex_path) = args.value("latex") {
+        existing_output(latex_path, args)?.prepare(latex_path).map_err(output_error)?;
+    }
+    let rank_by: String = args.parse_or("rank-by", "degree".to_string())?;
+    let columns = parse_columns(args.value("columns").unwrap_or("rank,author,degree,betweenness,closeness,harmonic,eigenvector"))?;
+    let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+    let scaling = args.parse_or("edge-scaling", EdgeScaling::Linear)?;
+    let (graph, weighted, audit) = load_with_weights(file_path, args)?;
+    if weighted.is_some() {
+        print_weight_reading(weights);
+        println!("Note: degree, clustering, eigenvector centrality, the layout and the colouring ignore the edge weights.");
+    }
+    let exact = !args.has("estimate") && largest_component(&graph).len() <= EXACT_LIMIT;
+    match &weighted {
+        Some(weighted) => print_extent(&stage!("eccentricity", weighted_extent(weighted, weights, exact)), " by edge length"),
+        None => print_extent(&stage!("eccentricity", extent(&graph, exact)), ""),
+    }
+
+    let components = stage!("components", connected_components(&graph));
+    println!("Number of connected components: {}", components);
+    let clustering = stage!("clustering", local_clustering(&graph));
+    let transitivity = transitivity(&graph);
+    println!(
+        "Average clustering coefficient: {:.4}, transitivity: {:.4}",
+        clustering.values().sum::<f64>() / clustering.len().max(1) as f64,
+        transitivity
+    );
+
+    let centralities = match &weighted {
+        Some(weighted) => {
+            let centralities = stage!("centralities", compute_weighted_centralities(weighted, weights));
+            print_strength(weighted, 10);
+            centralities
+        }
+        None => stage!("centralities", compute_centralities(&graph)),
+    };
+    let mut metadata = FigureMetadata::new(file_path).with_parameter("layout", &layout_name);
+    if weighted.is_some() {
+        metadata = metadata.with_parameter("weights", args.value("weights").unwrap_or("similarity"));
+    }
+    if !audit.is_empty() {
+        metadata = metadata.with_parameter("preprocessing", &audit);
+    }
+    let base_layout: fn(&AuthorGraph) -> Layout = if layout_name == "spectral" { spectral_layout } else { circular_layout };
+    let layout = match coarsen_target {
+        Some(target) => {
+            metadata = metadata.with_parameter("coarsen", target);
+            stage!("layout", coarsened_layout(&graph, target, 42, base_layout))
+        }
+        None => stage!("layout", base_layout(&graph)),
+    };
+    let communities = if layout_name == "spectral" {
+        // Spectral coordinates separate communities well, so colour nodes by them.
+        let seed = 42;
+        metadata = metadata.with_parameter("colouring", format!("louvain seed {}", seed));
+        Some(stage!("louvain", louvain(&graph, 1.0, seed)))
+    } else {
+        None
+    };
+    let edge_weights: Option<Vec<f64>> = weighted.as_ref().map(|weighted| weighted.edge_weights().copied().collect());
+    let style = NetworkStyle {
+        communities: communities.as_ref(),
+        edge_weights: edge_weights.as_deref().map(|edge_weights| (edge_weights, scaling)),
+    };
+    claim_output(NETWORK_PLOT, args)?;
+    stage!("render", render_network(&graph, &layout, style, &metadata, NETWORK_PLOT))
+        .map_err(|e| format!("Failed to render network: {}", e))?;
+    if let Some(viewport) = viewport {
+        let output_path = "output/network_viewport.png";
+        claim_output(output_path, args)?;
+        let metadata = metadata
+            .with_parameter("viewport", args.value("viewport").unwrap_or_default())
+            .with_parameter("width", width);
+        render_network_region(&graph, &layout, style, &metadata, &viewport, width, output_path)
+            .map_err(|e| format!("Failed to render viewport: {}", e))?;
+        println!("Viewport written to {}", output_path);
+    }
+
+    if args.has("bar-chart") {
+        write_bar_charts(file_path, &centralities, top, args)?;
+    }
+    if let Some(url) = args.value("gephi") {
+        stream_centralities(url, &graph, &centralities)?;
+    }
+    if let Some(path) = results_path {
+        let dataset = FigureMetadata::new(file_path).dataset;
+        let results = AnalysisResults::new(&dataset, &graph, components, &centralities)
+            .with_clustering(&clustering, transitivity)
+            .with_audit(audit);
+        write_results(&results, path).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        println!("Results written to {}", path);
+    }
+    if let Some(latex_path) = args.value("latex") {
+        let dataset = FigureMetadata::new(file_path).dataset;
+        let table = ranking_table(&centralities, &rank_by, &columns, top, &dataset)?;
+        write_output(latex_path, &table, args)?;
+        println!("LaTeX table written to {}", latex_path);
+    }
+    Ok(())
+}
+
+/// Send the graph with every measure as node attributes to a Gephi streaming workspace.
+fn stream_centralities(url: &str, graph: &AuthorGraph, centralities: &Centralities) -> Result<(), String> {
+    stream_to_gephi(url, &gephi_events(graph, &centralities.measures()))
+        .map_err(|e| format!("Failed to stream to Gephi at {}: {}", url, e))?;
+    println!("Streamed {} nodes and {} edges to Gephi at {}", graph.node_count(), graph.edge_count(), url);
+    Ok(())
+}
+
+/// The default analysis of a directed graph: its components, and the top nodes by in- and
+/// out-degree and by PageRank following the edge directions.
+fn run_directed_analysis(file_path: &str, top: usize, args: &Args) -> Result<(), String> {
+    let graph = load_directed_or_report(file_path, args)?;
+    let sizes = stage!("components", component_sizes(&graph));
+    println!(
+        "Strongly connected components: {} (largest {} nodes); weakly connected components: {}",
+        sizes.len(),
+        sizes.first().copied().unwrap_or(0),
+        connected_components(&graph)
+    );
+    let reciprocated = graph.edge_references().filter(|edge| graph.contains_edge(edge.target(), edge.source())).count();
+    println!("Reciprocity: {:.4}", reciprocated as f64 / graph.edge_count().max(1) as f64);
+
+    println!("\nTop {} nodes by in-degree:", top);
+    print_top(&directed_degree_centrality(&graph, Direction::Incoming), top);
+    println!("\nTop {} nodes by out-degree:", top);
+    print_top(&directed_degree_centrality(&graph, Direction::Outgoing), top);
+    let rank = stage!("pagerank", pagerank_with(&graph, &PageRankOptions::default(), None));
+    println!("\nTop {} nodes by PageRank:", top);
+    print_top(&rank.scores, top);
+    let hits = stage!("hits", hits(&graph));
+    println!("\nTop {} hubs (HITS):", top);
+    print_top(&hits.hubs, top);
+    println!("\nTop {} authorities (HITS):", top);
+    print_top(&hits.authorities, top);
+    println!(
+        "HITS power iteration {} after {} iterations, L1 residual {:.3e}.",
+        if hits.converged() { "converged" } else { "did not converge" },
+        hits.iterations,
+        hits.residual
+    );
+    Ok(())
+}
+
+/// One bar chart per measure, as an alternative to the textual top-k tables.
+fn write_bar_charts(file_path: &str, centralities: &Centralities, k: usize, args: &Args) -> Result<(), String> {
+    for (name, scores) in centralities.measures() {
+        let ranking = top_k(scores, k);
+        let output_path = format!("output/top_{}.png", name);
+        claim_output(&output_path, args)?;
+        let metadata = FigureMetadata::new(file_path)
+            .with_parameter("measure", name)
+            .with_parameter("top_k", k);
+        let title = format!("Top {} Authors by {} Centrality", k, capitalize(name));
+        bar_chart(&ranking, &title, &output_path, &metadata)
+            .map_err(|e| format!("Failed to render bar chart: {}", e))?;
+        println!("Bar chart written to {}", output_path);
+    }
+    Ok(())
+}
+
+/// Browse the rankings and authors interactively in the terminal.
+fn run_explore(file_path: &str, args: &Args) -> Result<(), String> {
+    let top = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let centralities = stage!("centralities", compute_centralities(&graph));
+    explore::run(&mut explore::Explorer::new(file_path, &graph, &centralities, top))
+}
+
+/// Tile pyramid of the network layout with an HTML viewer, for graphs too big for one image.
+fn run_tiles(file_path: &str, args: &Args) -> Result<(), String> {
+    let layout_name: String = args.parse_or("layout", "circular".to_string())?;
+    if layout_name != "circular" && layout_name != "spectral" {
+        return Err(format!("Unknown layout: {} (expected circular or spectral)", layout_name));
+    }
+    let max_zoom: u32 = args.parse_or("max-zoom", 4)?;
+    if max_zoom > 10 {
+        return Err(format!("--max-zoom {} would write over a million tiles (at most 10)", max_zoom));
+    }
+    let tile_size: u32 = args.parse_or("tile-size", 256)?;
+    if tile_size == 0 {
+        return Err("--tile-size must be positive".to_string());
+    }
+    let graph = load_or_report(file_path, args)?;
+    let (layout, communities) = if layout_name == "spectral" {
+        (spectral_layout(&graph), Some(louvain(&graph, 1.0, 42)))
+    } else {
+        (circular_layout(&graph), None)
+    };
+    let dir = "output/tiles";
+    claim_output(Path::new(dir).join("index.html"), args)?;
+    let written = render_tiles(&graph, &layout, communities.as_ref(), max_zoom, tile_size, dir)
+        .map_err(|e| format!("Failed to render tiles: {}", e))?;
+    println!("{} tiles written to {}; open {}/index.html to pan and zoom.", written, dir, dir);
+    Ok(())
+}
+
+/// Composite figure for reports: network, degree distribution and top-10 panels.
+fn run_figure(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    let metadata = FigureMetadata::new(file_path)
+        .with_parameter("layout", "circular")
+        .with_parameter("ranking", "degree")
+        .with_parameter("top_k", 10);
+    claim_output(output_path, args)?;
+    composite_figure(&graph, output_path, &metadata)
+        .map_err(|e| format!("Failed to render figure: {}", e))?;
+    println!("Figure written to {}", output_path);
+    Ok(())
+}
+
+/// Louvain (or Leiden) communities at one resolution: count, modularity and the largest
+/// communities, with every author's community id written as CSV.
+fn run_communities(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let method: Method = args.parse_or("method", Method::Louvain)?;
+    let resolution = args.parse_or("resolution", 1.0)?;
+    let seed = args.parse_or("seed", 42)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let partition = stage!("communities", method.detect(&graph, resolution, seed));
+    println!(
+        "\n{} (resolution {}): {} communities, modularity {:.4}",
+        capitalize(method.name()),
+        resolution,
+        community_count(&partition),
+        modularity(&graph, &partition, resolution)
+    );
+
+    // Members of each community, largest first, with their best-connected author.
+    let degree = degree_centrality(&graph);
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (&author, &community) in &partition {
+        members.entry(community).or_default().push(author);
+    }
+    let mut communities: Vec<(usize, Vec<usize>)> = members.into_iter().collect();
+    communities.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+    println!("\n{:>9}  {:>7}  {:>8}  {:>10}", "community", "authors", "share", "top author");
+    for (community, authors) in communities.iter().take(k) {
+        let hub = authors.iter().max_by_key(|&&author| (degree[&author], std::cmp::Reverse(author))).unwrap();
+        let share = authors.len() as f64 / graph.node_count().max(1) as f64;
+        println!("{:>9}  {:>7}  {:>7.1}%  {:>10}", community, authors.len(), share * 100.0, hub);
+    }
+
+    write_partition_with(&partition, output_path, existing_output(output_path, args)?).map_err(output_error)?;
+    println!("\nCommunities written to {}", output_path);
+    Ok(())
+}
+
+/// Girvan–Newman divisive clustering: the partition of best modularity as CSV, with the
+/// dendrogram of splits in a second CSV beside it.
+fn run_girvan_newman(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let max_splits: Option<usize> = args.parse_optional("max-splits")?;
+    let graph = load_or_report(file_path, args)?;
+    let dendrogram = stage!("girvan-newman", girvan_newman(&graph, max_splits));
+    let removed = dendrogram.splits.last().map_or(0, |split| split.edges_removed);
+    println!(
+        "\n{} splits after removing {} of {} collaborations{}.",
+        dendrogram.splits.len(),
+        removed,
+        graph.edge_count(),
+        if max_splits.is_some_and(|max| dendrogram.splits.len() == max) { " (--max-splits reached)" } else { "" }
+    );
+    println!(
+        "Best partition: {} communities, modularity {:.4}",
+        community_count(&dendrogram.best),
+        dendrogram.best_modularity
+    );
+    let mut writer = csv_output(output_path, args)?;
+    writer.write_record(["author", "community", "leaf"]).map_err(|e| e.to_string())?;
+    let mut authors: Vec<usize> = dendrogram.best.keys().copied().collect();
+    authors.sort_unstable();
+    for author in authors {
+        writer.serialize((author, dendrogram.best[&author], dendrogram.leaves[&author])).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Communities and dendrogram leaves written to {}", output_path);
+
+    let stem = Path::new(output_path).file_stem().and_then(|stem| stem.to_str()).unwrap_or("girvan_newman");
+    let dendrogram_path = Path::new(output_path).with_file_name(sanitize_file_name(&format!("{}_dendrogram.csv", stem)));
+    let mut writer = csv_output(&dendrogram_path, args)?;
+    for split in &dendrogram.splits {
+        writer.serialize(split).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Dendrogram written to {}", dendrogram_path.display());
+    Ok(())
+}
+
+/// Modularity of partitions from files (`author,community` rows, e.g. from another tool)
+/// or from a detector named instead of a file, side by side on the same graph.
+fn run_modularity(file_path: &str, specs: &[&str], args: &Args) -> Result<(), String> {
+    let resolution = args.parse_or("resolution", 1.0)?;
+    let seed = args.parse_or("seed", 42)?;
+    let graph = load_or_report(file_path, args)?;
+    let authors: HashSet<usize> = graph.node_weights().copied().collect();
+    println!("\n{:<32}  {:>11}  {:>14}  {:>10}", "partition", "communities", "authors", "modularity");
+    for spec in specs {
+        let partition = match spec.parse::<Method>() {
+            Ok(method) => method.detect(&graph, resolution, seed),
+            Err(_) => load_partition(spec).map_err(|e| format!("Failed to load partition {}: {}", spec, e))?,
+        };
+        let covered = partition.keys().filter(|author| authors.contains(author)).count();
+        println!(
+            "{:<32}  {:>11}  {:>6} of {:>5}  {:>10.4}",
+            spec,
+            community_count(&partition),
+            covered,
+            graph.node_count(),
+            modularity(&graph, &partition, resolution)
+        );
+    }
+    println!("(authors missing from a partition count as singleton communities; resolution {})", resolution);
+    Ok(())
+}
+
+/// Louvain resolution sweep: community count and modularity per resolution, as CSV and a plot.
+fn run_sweep(file_path: &str, args: &Args) -> Result<(), String> {
+    let min = args.parse_or("min", 0.2)?;
+    let max = args.parse_or("max", 2.0)?;
+    let steps = args.parse_or("steps", 10)?;
+    let seed = args.parse_or("seed", 42)?;
+    let method: Method = args.parse_or("method", Method::Louvain)?;
+    let graph = load_or_report(file_path, args)?;
+
+    let sweep = resolution_sweep(&graph, method, &resolution_range(min, max, steps), seed);
+    println!("\n{:>10}  {:>11}  {:>10}", "resolution", "communities", "modularity");
+    for point in &sweep {
+        println!("{:>10.3}  {:>11}  {:>10.4}", point.resolution, point.communities, point.modularity);
+    }
+
+    let csv_path = "output/resolution_sweep.csv";
+    let mut writer = csv_output(csv_path, args)?;
+    for point in &sweep {
+        writer.serialize(point).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("\nSweep written to {}", csv_path);
+
+    let points: Vec<(f64, f64)> = sweep.iter().map(|p| (p.resolution, p.modularity)).collect();
+    let plot_path = "output/resolution_sweep.png";
+    let metadata = FigureMetadata::new(file_path)
+        .with_parameter("method", method.name())
+        .with_parameter("resolutions", format!("{}..{} ({} steps)", min, max, steps))
+        .with_parameter("seed", seed);
+    claim_output(plot_path, args)?;
+    resolution_curve_plot(&points, plot_path, &metadata)
+        .map_err(|e| format!("Failed to render sweep plot: {}", e))?;
+    println!("Sweep plot written to {}", plot_path);
+    Ok(())
+}
+
+/// Consensus communities over several seeded Louvain runs, with run-to-run stability.
+fn run_consensus(file_path: &str, args: &Args) -> Result<(), String> {
+    let method: Method = args.parse_or("method", Method::Louvain)?;
+    let runs = args.parse_or("runs", 10)?;
+    if runs < 2 {
+        return Err(format!("--runs must be at least 2 to compare runs, got {}", runs));
+    }
+    let resolution = args.parse_or("resolution", 1.0)?;
+    let threshold = args.parse_or("threshold", 0.5)?;
+    let seed = args.parse_or("seed", 42)?;
+    let graph = load_or_report(file_path, args)?;
+
+    let consensus = consensus_communities(&graph, method, runs, resolution, seed, threshold);
+    let stability = &consensus.stability;
+    println!("\nStability across {} runs (pairwise):", runs);
+    println!("NMI: mean {:.4}, min {:.4}", stability.mean_nmi, stability.min_nmi);
+    println!("ARI: mean {:.4}, min {:.4}", stability.mean_ari, stability.min_ari);
+    println!(
+        "\nConsensus partition: {} communities, modularity {:.4}",
+        community_count(&consensus.partition),
+        modularity(&graph, &consensus.partition, resolution)
+    );
+
+    let csv_path = "output/consensus_communities.csv";
+    write_partition_with(&consensus.partition, csv_path, existing_output(csv_path, args)?).map_err(output_error)?;
+    println!("Consensus communities written to {}", csv_path);
+
+    let plot_path = "output/consensus_community_sizes.png";
+    let metadata = FigureMetadata::new(file_path)
+        .with_parameter("method", format!("{} consensus", method.name()))
+        .with_parameter("runs", runs)
+        .with_parameter("resolution", resolution)
+        .with_parameter("threshold", threshold)
+        .with_parameter("seed", seed);
+    claim_output(plot_path, args)?;
+    community_size_plot(&consensus.partition, plot_path, &metadata)
+        .map_err(|e| format!("Failed to render community sizes: {}", e))?;
+    println!("Community size distribution written to {}", plot_path);
+    Ok(())
+}
+
+/// Detect communities with Louvain and score them against a SNAP ground-truth file.
+fn run_evaluate(file_path: &str, truth_path: &str, args: &Args) -> Result<(), String> {
+    let method: Method = args.parse_or("method", Method::Louvain)?;
+    let resolution = args.parse_or("resolution", 1.0)?;
+    let seed = args.parse_or("seed", 42)?;
+    let graph = load_or_report(file_path, args)?;
+    let communities = load_ground_truth(truth_path)
+        .map_err(|e| format!("Failed to load ground truth: {}", e))?;
+    println!(
+        "Ground truth loaded with {} communities ({} authors in more than one; first membership used).",
+        communities.len(),
+        overlapping_authors(&communities)
+    );
+
+    let detected = method.detect(&graph, resolution, seed);
+    let evaluation = evaluate_partition(&detected, &ground_truth_partition(&communities));
+    println!(
+        "\nEvaluated on {} of {} authors: {} detected vs {} ground-truth communities",
+        evaluation.shared_authors,
+        graph.node_count(),
+        evaluation.detected_communities,
+        evaluation.true_communities
+    );
+    println!("NMI: {:.4}", evaluation.nmi);
+    println!("ARI: {:.4}", evaluation.ari);
+    Ok(())
+}
+
+/// Fit a (degree-corrected) stochastic block model and write the block assignment.
+fn run_sbm(file_path: &str, args: &Args) -> Result<(), String> {
+    let blocks = args.parse_or("blocks", 10)?;
+    let seed = args.parse_or("seed", 42)?;
+    let degree_corrected = !args.has("uncorrected");
+    let graph = load_or_report(file_path, args)?;
+
+    let fit = fit_sbm(&graph, blocks, degree_corrected, seed);
+    let model = if degree_corrected { "Degree-corrected SBM" } else { "SBM" };
+    println!(
+        "\n{} with {} blocks: log-likelihood {:.2} after {} sweeps",
+        model, blocks, fit.log_likelihood, fit.sweeps
+    );
+    let sizes: Vec<String> = community_sizes(&fit.blocks)
+        .into_iter()
+        .rev()
+        .flat_map(|(size, count)| std::iter::repeat_n(size.to_string(), count))
+        .collect();
+    println!("Block sizes: {}", sizes.join(", "));
+
+    let csv_path = "output/sbm_blocks.csv";
+    write_partition_with(&fit.blocks, csv_path, existing_output(csv_path, args)?).map_err(output_error)?;
+    println!("Block assignment written to {}", csv_path);
+    Ok(())
+}
+
+/// Project node embeddings to 2D with t-SNE and plot them coloured by Louvain community.
+///
+/// Without `--embedding`, the largest component is embedded spectrally in `--dims` dimensions.
+fn run_tsne(file_path: &str, args: &Args) -> Result<(), String> {
+    let dims = args.parse_or("dims", 10)?;
+    let perplexity = args.parse_or("perplexity", 30.0)?;
+    let iterations = args.parse_or("iterations", 1000)?;
+    let seed = args.parse_or("seed", 42)?;
+    let embedding_path = args.value("embedding");
+    let graph = load_or_report(file_path, args)?;
+
+    let (authors, vectors): (Vec<usize>, Vec<Vec<f64>>) = match embedding_path {
+        Some(path) => {
+            let embedding = load_embedding(path).map_err(|e| format!("Failed to load embedding: {}", e))?;
+            let mut rows: Vec<(usize, Vec<f64>)> = embedding.into_iter().collect();
+            rows.sort_by_key(|row| row.0);
+            rows.into_iter().unzip()
+        }
+        None => {
+            let embedding = spectral_embedding(&graph, dims);
+            largest_component(&graph)
+                .into_iter()
+                .map(|node| (graph[node], embedding[node.index()].clone()))
+                .unzip()
+        }
+    };
+    println!("Projecting {} embeddings with t-SNE (perplexity {}).", authors.len(), perplexity);
+    let projected = tsne(&vectors, perplexity, iterations, seed);
+    let points: Vec<(usize, (f64, f64))> = authors.into_iter().zip(projected).collect();
+
+    let csv_path = "output/tsne.csv";
+    let mut writer = csv_output(csv_path, args)?;
+    writer.write_record(["author", "x", "y"]).map_err(|e| e.to_string())?;
+    for (author, (x, y)) in &points {
+        writer
+            .write_record([author.to_string(), x.to_string(), y.to_string()])
+            .map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Projection written to {}", csv_path);
+
+    let communities = louvain(&graph, 1.0, seed);
+    let source = match embedding_path {
+        Some(path) => path.to_string(),
+        None => format!("spectral, {} dims", dims),
+    };
+    let metadata = FigureMetadata::new(file_path)
+        .with_parameter("embedding", source)
+        .with_parameter("perplexity", perplexity)
+        .with_parameter("iterations", iterations)
+        .with_parameter("seed", seed)
+        .with_parameter("colouring", format!("louvain seed {}", seed));
+    let plot_path = "output/tsne.png";
+    claim_output(plot_path, args)?;
+    projection_plot(&points, Some(&communities), "t-SNE Projection", plot_path, &metadata)
+        .map_err(|e| format!("Failed to render projection: {}", e))?;
+    println!("Projection plot written to {}", plot_path);
+    Ok(())
+}
+
+/// Enumerate maximal cliques, print their size distribution and export the largest ones.
+fn run_cliques(file_path: &str, args: &Args) -> Result<(), String> {
+    let min_size = args.parse_or("min-size", 3)?;
+    let export = args.parse_or("export", 5)?;
+    let graph = load_or_report(file_path, args)?;
+
+    let cliques = maximal_cliques(&graph, min_size);
+    println!("\n{} maximal cliques with at least {} authors.", cliques.len(), min_size);
+    println!("Clique number: {}", clique_number(&cliques));
+    println!("\n{:>4}  {:>7}", "size", "cliques");
+    for (size, count) in clique_size_distribution(&cliques) {
+        println!("{:>4}  {:>7}", size, count);
+    }
+
+    for (rank, clique) in cliques.iter().take(export).enumerate() {
+        let path = Path::new("output").join("cliques").join(format!("clique_{}.txt", rank + 1));
+        claim_output(&path, args)?;
+        write_clique(clique, &path).map_err(|e| e.to_string())?;
+        println!("Clique of {} authors written to {}", clique.len(), path.display());
+    }
+    Ok(())
+}
+
+/// Find the densest subgraph by peeling and write its members.
+fn run_densest(file_path: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    let dense = densest_subgraph(&graph);
+    println!(
+        "\nDensest subgraph: {} authors, {} edges, density {:.4} edges per author",
+        dense.members.len(),
+        dense.edges,
+        dense.density
+    );
+    let members: Vec<String> = dense.members.iter().map(usize::to_string).collect();
+    println!("Members: {}", members.join(", "));
+
+    let path = "output/densest_subgraph.txt";
+    write_output(path, &(members.join("\n") + "\n"), args)?;
+    println!("Members written to {}", path);
+    Ok(())
+}
+
+/// Compute a maximum matching (disjoint collaborating pairs) and write it as CSV.
+fn run_matching(file_path: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    let pairs = maximum_matching(&graph);
+    let unmatched = graph.node_count() - 2 * pairs.len();
+    println!("\nMaximum matching: {} pairs, {} authors unmatched", pairs.len(), unmatched);
+    if unmatched == 0 {
+        println!("The matching is perfect.");
+    }
+
+    let csv_path = "output/matching.csv";
+    let mut writer = csv_output(csv_path, args)?;
+    writer.write_record(["author", "partner"]).map_err(|e| e.to_string())?;
+    for (a, b) in &pairs {
+        writer.write_record([a.to_string(), b.to_string()]).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Matching written to {}", csv_path);
+    Ok(())
+}
+
+/// Approximate vertex cover and independent set, with bounds on the optimum.
+fn run_cover(file_path: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    let cover = vertex_cover(&graph);
+    let independent = independent_set(&graph);
+    println!(
+        "\nVertex cover: {} authors (optimum at least {}, ratio at most {:.3})",
+        cover.members.len(),
+        cover.bound,
+        cover.members.len() as f64 / cover.bound.max(1) as f64
+    );
+    println!(
+        "Independent set: {} authors (optimum at most {}, ratio at least {:.3})",
+        independent.members.len(),
+        independent.bound,
+        independent.members.len() as f64 / independent.bound.max(1) as f64
+    );
+
+    for (name, members) in [("vertex_cover", &cover.members), ("independent_set", &independent.members)] {
+        let path = Path::new("output").join(format!("{}.txt", name));
+        let lines: Vec<String> = members.iter().map(usize::to_string).collect();
+        write_output(&path, &(lines.join("\n") + "\n"), args)?;
+        println!("Members written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Minimum edge cut between two authors or comma-separated groups of authors.
+fn run_cut(file_path: &str, sources: &str, sinks: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    let resolve = |list: &str| -> Result<Vec<petgraph::graph::NodeIndex>, String> {
+        list.split(',')
+            .map(|id| {
+                let author: usize = id.trim().parse().map_err(|_| format!("Invalid author id: {}", id))?;
+                author_index(&graph, author).ok_or_else(|| format!("Author {} is not in the graph", author))
+            })
+            .collect()
+    };
+    let (sources, sinks) = (resolve(sources)?, resolve(sinks)?);
+    if let Some(shared) = sources.iter().find(|node| sinks.contains(node)) {
+        return Err(format!("Author {} is on both sides of the cut", graph[*shared]));
+    }
+
+    let cut = min_cut(&graph, &sources, &sinks);
+    println!("\nMaximum flow (edge-disjoint paths): {}", cut.flow);
+    println!("Minimum cut of {} collaborations:", cut.cut_edges.len());
+    for (a, b) in &cut.cut_edges {
+        println!("Author {} - Author {}", a, b);
+    }
+    println!("{} authors remain on the source side.", cut.source_side.len());
+    Ok(())
+}
+
+/// Coarsen the graph by heavy-edge matching and write the coarsest level with its mapping.
+fn run_coarsen(file_path: &str, args: &Args) -> Result<(), String> {
+    let target = args.parse_or("target", 500)?;
+    let seed = args.parse_or("seed", 42)?;
+    let scaling: EdgeScaling = args.parse_or("edge-scaling", EdgeScaling::Linear)?;
+    let graph = load_or_report(file_path, args)?;
+
+    let coarsening = coarsen(&graph, target, seed);
+    println!("\n{:>5}  {:>7}  {:>7}", "level", "nodes", "edges");
+    for (level, coarse) in coarsening.levels.iter().enumerate() {
+        println!("{:>5}  {:>7}  {:>7}", level, coarse.node_count(), coarse.edge_count());
+    }
+
+    let coarsest = coarsening.coarsest();
+    let graph_path = "output/coarse_graph.txt";
+    let mut lines = vec!["# coarse_a\tcoarse_b\tcollaborations".to_string()];
+    for edge in coarsest.edge_references() {
+        lines.push(format!("{}\t{}\t{}", edge.source().index(), edge.target().index(), edge.weight()));
+    }
+    write_output(graph_path, &(lines.join("\n") + "\n"), args)?;
+    println!("Coarsest graph written to {}", graph_path);
+
+    let plot_path = "output/coarse_network.png";
+    let metadata = FigureMetadata::new(file_path)
+        .with_parameter("target", target)
+        .with_parameter("seed", seed)
+        .with_parameter("edge_scaling", if scaling == EdgeScaling::Log { "log" } else { "linear" });
+    claim_output(plot_path, args)?;
+    weighted_network_plot(coarsest, scaling, plot_path, &metadata)
+        .map_err(|e| format!("Failed to render coarse network: {}", e))?;
+    println!("Coarse network written to {}", plot_path);
+
+    let mapping_path = "output/coarse_mapping.csv";
+    let mut writer = csv_output(mapping_path, args)?;
+    writer.write_record(["author", "coarse_node"]).map_err(|e| e.to_string())?;
+    for (index, coarse) in coarsening.membership().into_iter().enumerate() {
+        let author = graph[petgraph::graph::NodeIndex::new(index)];
+        writer.write_record([author.to_string(), coarse.to_string()]).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Author mapping written to {}", mapping_path);
+    Ok(())
+}
+
+/// Landmark-based estimates: closeness of one author, or distance bounds between two,
+/// over edge lengths when the edges carry weights.
+fn run_landmarks(file_path: &str, author: &str, other: Option<&str>, args: &Args) -> Result<(), String> {
+    let count = args.parse_or("landmarks", 16)?;
+    let selection: Selection = args.parse_or("selection", Selection::Degree)?;
+    let seed = args.parse_or("seed", 42)?;
+    let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+    let (graph, weighted, _) = load_with_weights(file_path, args)?;
+    let node_of = |id: &str| {
+        let author: usize = id.parse().map_err(|_| format!("Invalid author id: {}", id))?;
+        author_index(&graph, author).ok_or_else(|| format!("Author {} is not in the graph", author))
+    };
+    let node = node_of(author)?;
+    let other = other.map(node_of).transpose()?;
+
+    let start = Instant::now();
+    let landmarks = match &weighted {
+        Some(weighted) => {
+            print_weight_reading(weights);
+            Landmarks::select_weighted(weighted, weights, count, selection, seed)
+        }
+        None => Landmarks::select(&graph, count, selection, seed),
+    };
+    println!("{} landmarks selected in {:.1?}.", landmarks.nodes.len(), start.elapsed());
+
+    let start = Instant::now();
+    let (length, unit) = (|d| format_length(d, weighted.is_some()), if weighted.is_some() { "by edge length" } else { "hops" });
+    match other {
+        Some(other) => match landmarks.distance_bounds(node, other) {
+            Some(bounds) => println!(
+                "Distance between Author {} and Author {}: between {} and {} {} ({:.1?})",
+                graph[node],
+                graph[other],
+                length(bounds.lower),
+                length(bounds.upper),
+                unit,
+                start.elapsed()
+            ),
+            None => println!("No landmark reaches both authors; they are probably disconnected."),
+        },
+        None => match landmarks.approximate_closeness(node) {
+            Some(closeness) => println!(
+                "Closeness of Author {}: at least {:.4} ({:.1?})",
+                graph[node], closeness, start.elapsed()
+            ),
+            None => println!("No landmark reaches Author {}.", graph[node]),
+        },
+    }
+    Ok(())
+}
+
+/// Dataset statistics in the layout of the SNAP dataset pages, for comparison with
+/// published numbers; of the union of the files if several are given. A single file with
+/// weights other than 1 also gets its strength and weighted clustering statistics.
+fn run_stats(file_paths: &[&str], args: &Args) -> Result<(), String> {
+    let seed = args.parse_or("seed", 42)?;
+    let (graph, weighted) = match file_paths {
+        [file_path] if !args.has("sources") => {
+            let (graph, weighted, _) = load_with_weights(file_path, args)?;
+            (graph, weighted)
+        }
+        _ => (load_union(file_paths, args)?, None),
+    };
+    let exact = !args.has("estimate") && graph.node_count() <= EXACT_LIMIT;
+
+    let stats = graph_stats(&graph, exact, seed);
+    let fraction = |part: usize, whole: usize| part as f64 / whole.max(1) as f64;
+    println!("\nDataset statistics");
+    println!("Nodes\t{}", stats.nodes);
+    println!("Edges\t{}", stats.edges);
+    println!("Connected components\t{}", stats.components);
+    println!(
+        "Nodes in largest WCC\t{} ({:.3})",
+        stats.largest_component_nodes,
+        fraction(stats.largest_component_nodes, stats.nodes)
+    );
+    println!(
+        "Edges in largest WCC\t{} ({:.3})",
+        stats.largest_component_edges,
+        fraction(stats.largest_component_edges, stats.edges)
+    );
+    let method = if stats.exact { "exact" } else { "HyperANF estimate" };
+    let bound = if stats.exact { "" } else { "at least " };
+    println!("Number of triangles\t{}", stats.triangles);
+    println!("Average clustering coefficient\t{:.4}", stats.average_clustering);
+    println!("Transitivity (global clustering)\t{:.4}", stats.transitivity);
+    println!("Degree assortativity\t{:.4}", stats.assortativity);
+    if let Some(weighted) = &weighted {
+        let weights = weighted_stats(weighted);
+        println!(
+            "Strength (summed weights)\t{} to {}, mean {:.4}",
+            weights.min_strength, weights.max_strength, weights.mean_strength
+        );
+        println!("Strength-degree correlation\t{:.4}", weights.strength_degree_correlation);
+        println!("Strength assortativity\t{:.4}", weights.strength_assortativity);
+        println!("Average Barrat weighted clustering\t{:.4}", weights.barrat_clustering);
+        println!("Average Onnela weighted clustering\t{:.4}", weights.onnela_clustering);
+    }
+    println!("Diameter (longest shortest path)\t{}{} ({})", bound, stats.diameter, method);
+    println!("90-percentile effective diameter\t{:.1} ({})", stats.effective_diameter, method);
+    Ok(())
+}
+
+/// Connected components by size: how much of the network the giant component holds and
+/// what is left outside it, with one CSV row per component. With `--directed`, the
+/// strongly connected components.
+fn run_components(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let top = args.parse_or("top-k", 10)?;
+    let (sizes, nodes, kind) = if args.has("directed") {
+        let graph = load_directed_or_report(file_path, args)?;
+        println!("{} weakly connected components.", connected_components(&graph));
+        (component_sizes(&graph), graph.node_count(), "strongly connected components")
+    } else {
+        let graph = load_or_report(file_path, args)?;
+        (component_sizes(&graph), graph.node_count(), "connected components")
+    };
+    let n = nodes.max(1) as f64;
+    let giant = sizes.first().copied().unwrap_or(0);
+    println!("\n{} {}.", sizes.len(), kind);
+    println!("Giant component: {} authors ({:.3} of all).", giant, giant as f64 / n);
+    println!(
+        "Outside it: {} authors; the second largest component has {}, and {} are single authors.",
+        nodes - giant,
+        sizes.get(1).copied().unwrap_or(0),
+        sizes.iter().filter(|&&size| size == 1).count()
+    );
+
+    let mut distribution: BTreeMap<usize, usize> = BTreeMap::new();
+    sizes.iter().for_each(|&size| *distribution.entry(size).or_insert(0) += 1);
+    println!("\nSize\tComponents");
+    for (size, count) in distribution.iter().rev().take(top) {
+        println!("{}\t{}", size, count);
+    }
+    let mut writer = csv_output(output_path, args)?;
+    writer.write_record(["component", "size", "fraction"]).map_err(|e| e.to_string())?;
+    for (component, &size) in sizes.iter().enumerate() {
+        writer.serialize((component, size, size as f64 / n)).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Component sizes written to {}", output_path);
+    Ok(())
+}
+
+/// The union of several edge lists (`name=path` or a path, as for multilayer), after
+/// printing how many edges each contributes and how many of those no other file has.
+/// `--sources a,b` keeps only the edges found in the named files.
+fn load_union(specs: &[&str], args: &Args) -> Result<AuthorGraph, String> {
+    reject_directed(args)?;
+    let mut log = AuditLog::default();
+    let multilayer = Multilayer::load_audited(specs, &load_options(args)?, &mut log)
+        .map_err(|e| format!("Failed to load graphs: {}", e))?;
+    println!("Source\tEdges\tOnly in this source");
+    for (name, (edges, exclusive)) in multilayer.names.iter().zip(multilayer.edge_counts()) {
+        println!("{}\t{}\t{}", name, edges, exclusive);
+    }
+    let union = match args.value("sources") {
+        Some(names) => {
+            let layers = names
+                .split(',')
+                .map(|name| multilayer.layer_index(name).ok_or_else(|| format!("Unknown source: {}", name)))
+                .collect::<Result<Vec<usize>, String>>()?;
+            multilayer.aggregate_of(&layers)
+        }
+        None => multilayer.aggregate(),
+    };
+    Ok(report_loaded(union, &mut log, args))
+}
+
+/// Degree distribution as CSV and a log-log plot beside it, exact or in logarithmic bins;
+/// with `--weighted`, the strength distribution instead.
+fn run_degree_distribution(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let log_bins: Option<usize> = args.parse_optional("log-bins")?;
+    if log_bins == Some(0) {
+        return Err("--log-bins must be at least 1".to_string());
+    }
+    if args.has("weighted") {
+        return run_strength_distribution(file_path, output_path, log_bins, args);
+    }
+    let graph = load_or_report(file_path, args)?;
+    let distribution = degree_distribution(&graph);
+    let n = graph.node_count().max(1) as f64;
+    println!(
+        "\nDegrees from {} to {}, mean {:.2}; {} authors without collaborators.",
+        distribution.keys().next().unwrap_or(&0),
+        distribution.keys().next_back().unwrap_or(&0),
+        2.0 * graph.edge_count() as f64 / n,
+        distribution.get(&0).unwrap_or(&0)
+    );
+    let mut writer = csv_output(output_path, args)?;
+    let mut metadata = FigureMetadata::new(file_path);
+    let (points, y_desc) = match log_bins {
+        Some(bins_per_decade) => {
+            let bins = log_binned_degrees(&graph, bins_per_decade);
+            writer.write_record(["lower", "upper", "authors", "density"]).map_err(|e| e.to_string())?;
+            for bin in &bins {
+                writer.serialize((bin.lower, bin.upper, bin.authors, bin.density())).map_err(|e| e.to_string())?;
+            }
+            println!("{} logarithmic bins, {} per decade.", bins.len(), bins_per_decade);
+            metadata = metadata.with_parameter("bins_per_decade", bins_per_decade);
+            let points: Vec<(f64, f64)> = bins.iter().map(|bin| (bin.centre(), bin.density())).collect();
+            (points, "Authors per unit degree")
+        }
+        None => {
+            writer.write_record(["degree", "authors", "fraction", "ccdf"]).map_err(|e| e.to_string())?;
+            // Fraction of authors with at least the given degree.
+            let mut at_least = graph.node_count();
+            for (&degree, &count) in &distribution {
+                writer.serialize((degree, count, count as f64 / n, at_least as f64 / n)).map_err(|e| e.to_string())?;
+                at_least -= count;
             }
-            let nodes: Vec<usize> = line
-                .split_whitespace()
-                .filter_map(|x| x.parse::<usize>().ok())
+            let points: Vec<(f64, f64)> = distribution
+                .iter()
+                .filter(|&(&degree, _)| degree > 0)
+                .map(|(&degree, &count)| (degree as f64, count as f64))
                 .collect();
+            (points, "Number of authors")
+        }
+    };
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Degree distribution written to {}", output_path);
 
-            if nodes.len() == 2 {
-                let (from, to) = (nodes[0], nodes[1]);
-                if from != to {
-                    edges.insert((from.min(to), from.max(to)));
-                }
+    let plot_path = Path::new(output_path).with_extension("png");
+    claim_output(&plot_path, args)?;
+    degree_distribution_plot(&points, "Degree", y_desc, &plot_path, &metadata).map_err(|e| format!("Failed to render plot: {}", e))?;
+    println!("Log-log plot written to {}", plot_path.display());
+    Ok(())
+}
+
+/// Strength (summed collaboration weights) distribution as CSV and a log-log plot beside
+/// it, exact or in logarithmic bins, after the strength-degree correlation.
+fn run_strength_distribution(file_path: &str, output_path: &str, log_bins: Option<usize>, args: &Args) -> Result<(), String> {
+    let graph = load_weighted_or_report(file_path, args)?;
+    let distribution = strength_distribution(&graph);
+    let n = graph.node_count().max(1) as f64;
+    let total: f64 = distribution.iter().map(|&(strength, count)| strength * count as f64).sum();
+    println!(
+        "\nStrengths from {} to {}, mean {:.2}; strength-degree correlation {:.4}.",
+        distribution.first().map_or(0.0, |bin| bin.0),
+        distribution.last().map_or(0.0, |bin| bin.0),
+        total / n,
+        strength_degree_correlation(&graph)
+    );
+    let mut writer = csv_output(output_path, args)?;
+    let mut metadata = FigureMetadata::new(file_path).with_parameter("measure", "strength");
+    let (points, y_desc) = match log_bins {
+        Some(bins_per_decade) => {
+            let bins = log_binned_strengths(&graph, bins_per_decade);
+            writer.write_record(["lower", "upper", "authors", "density"]).map_err(|e| e.to_string())?;
+            for bin in &bins {
+                writer.serialize((bin.lower, bin.upper, bin.authors, bin.density())).map_err(|e| e.to_string())?;
+            }
+            println!("{} logarithmic bins, {} per decade.", bins.len(), bins_per_decade);
+            metadata = metadata.with_parameter("bins_per_decade", bins_per_decade);
+            let points: Vec<(f64, f64)> = bins.iter().map(|bin| (bin.centre(), bin.density())).collect();
+            (points, "Authors per unit strength")
+        }
+        None => {
+            writer.write_record(["strength", "authors", "fraction", "ccdf"]).map_err(|e| e.to_string())?;
+            let mut at_least = graph.node_count();
+            for &(strength, count) in &distribution {
+                writer.serialize((strength, count, count as f64 / n, at_least as f64 / n)).map_err(|e| e.to_string())?;
+                at_least -= count;
             }
+            let points: Vec<(f64, f64)> = distribution
+                .iter()
+                .filter(|&&(strength, _)| strength > 0.0)
+                .map(|&(strength, count)| (strength, count as f64))
+                .collect();
+            (points, "Number of authors")
         }
+    };
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Strength distribution written to {}", output_path);
 
-        for &(from, to) in &edges {
-            let from_index = *node_map.entry(from).or_insert_with(|| graph.add_node(from));
-            let to_index = *node_map.entry(to).or_insert_with(|| graph.add_node(to));
-            graph.add_edge(from_index, to_index, ());
+    let plot_path = Path::new(output_path).with_extension("png");
+    claim_output(&plot_path, args)?;
+    degree_distribution_plot(&points, "Strength", y_desc, &plot_path, &metadata).map_err(|e| format!("Failed to render plot: {}", e))?;
+    println!("Log-log plot written to {}", plot_path.display());
+    Ok(())
+}
+
+/// Rich-club coefficient at every degree threshold as CSV and a plot beside it,
+/// normalised by the mean over --random degree-preserving rewirings when given.
+fn run_rich_club(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let randomizations = args.parse_or("random", 0)?;
+    let seed = args.parse_or("seed", 42)?;
+    let graph = load_or_report(file_path, args)?;
+    let levels = stage!("rich club", rich_club(&graph));
+    let random = (randomizations > 0).then(|| stage!("rewiring", random_rich_club(&graph, randomizations, seed)));
+    if levels.is_empty() {
+        return Err("No degree threshold leaves two or more authors".to_string());
+    }
+    // Undefined where no rewiring kept a collaboration inside the club.
+    let normalized = |phi: f64, random: f64| (random > 0.0).then(|| phi / random);
+
+    // Thresholds 0, 1, 2, 4, 8, ... and the last one; every level goes to the CSV.
+    println!("\n{:>6}  {:>8}  {:>14}  {:>8}", "k", "authors", "collaborations", "phi(k)");
+    let last = levels.len() - 1;
+    for (i, level) in levels.iter().enumerate().filter(|(i, level)| level.degree.is_power_of_two() || *i == 0 || *i == last) {
+        print!("{:>6}  {:>8}  {:>14}  {:>8.4}", level.degree, level.authors, level.collaborations, level.coefficient);
+        match &random {
+            Some(random) => match normalized(level.coefficient, random[i]) {
+                Some(ratio) => println!("  rewired {:.4}, normalised {:.3}", random[i], ratio),
+                None => println!("  rewired {:.4}, normalised -", random[i]),
+            },
+            None => println!(),
+        }
+    }
+    if random.is_some() {
+        println!(
+            "(rewired: mean over {} degree-preserving rewirings of {} swaps per collaboration)",
+            randomizations, SWAPS_PER_EDGE
+        );
+    }
+    let mut writer = csv_output(output_path, args)?;
+    let mut metadata = FigureMetadata::new(file_path);
+    match &random {
+        Some(random) => {
+            writer
+                .write_record(["degree", "authors", "collaborations", "coefficient", "random", "normalized"])
+                .map_err(|e| e.to_string())?;
+            for (level, &phi) in levels.iter().zip(random) {
+                let row = (level.degree, level.authors, level.collaborations, level.coefficient, phi, normalized(level.coefficient, phi));
+                writer.serialize(row).map_err(|e| e.to_string())?;
+            }
+            metadata = metadata.with_parameter("randomizations", randomizations).with_parameter("seed", seed);
+        }
+        None => {
+            writer.write_record(["degree", "authors", "collaborations", "coefficient"]).map_err(|e| e.to_string())?;
+            for level in &levels {
+                writer
+                    .serialize((level.degree, level.authors, level.collaborations, level.coefficient))
+                    .map_err(|e| e.to_string())?;
+            }
         }
     }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Rich-club coefficients written to {}", output_path);
 
-    Ok(graph)
+    let plot_path = Path::new(output_path).with_extension("png");
+    claim_output(&plot_path, args)?;
+    rich_club_plot(&levels, random.as_deref(), &plot_path, &metadata).map_err(|e| format!("Failed to render plot: {}", e))?;
+    println!("Plot written to {}", plot_path.display());
+    Ok(())
 }
 
-/// Compute centrality measures for the graph.
-/// Compute centrality measures for the graph.
-pub fn compute_centralities(graph: &AuthorGraph) {
-    let mut degree_centrality = HashMap::new();
-    let mut betweenness_centrality = HashMap::new();
-    let mut eigenvector_centrality = HashMap::new();
+/// Count the triangles of the graph and through each author, optionally writing the
+/// per-author counts to a CSV file. With `--weighted`, also the weighted clustering
+/// coefficient chosen by `--clustering`.
+fn run_triangles(file_path: &str, output_path: Option<&str>, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let (graph, weighted) = if args.has("weighted") {
+        let variant = args.parse_or("clustering", WeightedClustering::Barrat)?;
+        let graph = load_weighted_or_report(file_path, args)?;
+        let clustering = stage!("clustering", weighted_clustering(&graph, variant));
+        (unweighted(&graph), Some((variant, clustering)))
+    } else {
+        (load_or_report(file_path, args)?, None)
+    };
+    let counts = stage!("triangles", triangles_per_author(&graph));
+    let total = counts.iter().sum::<usize>() / 3;
+    let closed = counts.iter().filter(|&&count| count > 0).count();
+    println!("\nNumber of triangles: {}", total);
+    println!("Authors in at least one triangle: {} of {}", closed, graph.node_count());
+    let per_author: HashMap<usize, usize> = graph.node_indices().map(|node| (graph[node], counts[node.index()])).collect();
+    println!("\nTop {} authors by triangles:", k);
+    print_top(&per_author, k);
+    if let Some((variant, clustering)) = &weighted {
+        let average = clustering.values().sum::<f64>() / clustering.len().max(1) as f64;
+        println!("\nAverage {:?} weighted clustering coefficient: {:.4}", variant, average);
+        println!("\nTop {} authors by {:?} weighted clustering:", k, variant);
+        print_top(clustering, k);
+    }
 
-    // Compute degree centrality
-    for node in graph.node_indices() {
-        degree_centrality.insert(graph[node], graph.edges(node).count());
+    if let Some(output_path) = output_path {
+        let mut authors: Vec<_> = per_author.into_iter().collect();
+        authors.sort_unstable();
+        let mut writer = csv_output(output_path, args)?;
+        match &weighted {
+            Some((_, clustering)) => {
+                writer.write_record(["author", "triangles", "weighted_clustering"]).map_err(|e| e.to_string())?;
+                for &(author, count) in &authors {
+                    writer.serialize((author, count, clustering[&author])).map_err(|e| e.to_string())?;
+                }
+            }
+            None => {
+                writer.write_record(["author", "triangles"]).map_err(|e| e.to_string())?;
+                for row in &authors {
+                    writer.serialize(row).map_err(|e| e.to_string())?;
+                }
+            }
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        println!("Per-author triangle counts written to {}", output_path);
     }
+    Ok(())
+}
 
-    // Compute betweenness centrality (simple approximation via Dijkstra)
-    for node in graph.node_indices() {
-        let distances = dijkstra(&graph, node, None, |_| 1);
-        let total_distance: usize = distances.values().sum();
-        betweenness_centrality.insert(graph[node], total_distance);
+/// List the collaboration chains between two authors: the --k shortest, or every simple
+/// path of at most --max-length collaborations, avoiding the --exclude authors. Paths are
+/// measured over edge lengths when the edges carry weights.
+fn run_paths(file_path: &str, source: &str, target: &str, args: &Args) -> Result<(), String> {
+    let max_length = args.parse_or("max-length", 4)?;
+    let limit = args.parse_or("limit", 100)?;
+    let k: Option<usize> = args.parse_optional("k")?;
+    let plot_path = args.value("plot");
+    let scope: String = args.parse_or("plot-scope", "ego".to_string())?;
+    if scope != "ego" && scope != "full" {
+        return Err(format!("Unknown plot scope: {} (expected ego or full)", scope));
+    }
+    let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+    let (graph, weighted, _) = load_with_weights(file_path, args)?;
+    let resolve = |id: &str| -> Result<petgraph::graph::NodeIndex, String> {
+        let author: usize = id.trim().parse().map_err(|_| format!("Invalid author id: {}", id))?;
+        author_index(&graph, author).ok_or_else(|| format!("Author {} is not in the graph", author))
+    };
+    let (source, target) = (resolve(source)?, resolve(target)?);
+    if source == target {
+        return Err("Source and target must be different authors".to_string());
+    }
+    let excluded = match args.value("exclude") {
+        Some(list) => list.split(',').map(resolve).collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    if excluded.contains(&source) || excluded.contains(&target) {
+        return Err("--exclude must not contain the source or target".to_string());
     }
 
-    // Compute eigenvector centrality (simple iteration)
-    let mut centrality_values: HashMap<_, f64> = graph
-        .node_indices()
-        .map(|node| (graph[node], 1.0)) // Initialize all centralities to 1.0
-        .collect();
-    let num_iterations = 100; // Set max iterations
-    let tolerance = 1e-6; // Convergence threshold
+    if weighted.is_some() {
+        print_weight_reading(weights);
+    }
+    let hops = |paths: Vec<Vec<usize>>| paths.into_iter().map(|path| ((path.len() - 1) as f64, path)).collect();
+    let paths: Vec<(f64, Vec<usize>)> = match k {
+        Some(k) => {
+            let paths = match &weighted {
+                Some(weighted) => weighted_k_shortest_paths(weighted, weights, source, target, k, &excluded),
+                None => hops(k_shortest_paths(&graph, source, target, k, &excluded)),
+            };
+            println!("\n{} shortest paths from author {} to author {}:", paths.len(), graph[source], graph[target]);
+            paths
+        }
+        None => {
+            let (paths, truncated) = match &weighted {
+                Some(weighted) => weighted_simple_paths(weighted, weights, source, target, max_length, &excluded, limit),
+                None => {
+                    let (paths, truncated) = simple_paths(&graph, source, target, max_length, &excluded, limit);
+                    (hops(paths), truncated)
+                }
+            };
+            println!(
+                "\n{}{} simple paths of at most {} collaborations from author {} to author {}:",
+                if truncated { "First " } else { "" },
+                paths.len(),
+                max_length,
+                graph[source],
+                graph[target]
+            );
+            if truncated {
+                println!("(stopped at --limit {}; there are more)", limit);
+            }
+            paths
+        }
+    };
+    if paths.is_empty() {
+        println!("No such path.");
+    }
+    for (length, path) in &paths {
+        let authors: Vec<String> = path.iter().map(usize::to_string).collect();
+        println!("{}\t{}", format_length(*length, weighted.is_some()), authors.join(" -> "));
+    }
 
-    for _ in 0..num_iterations {
-        let mut next_centrality_values = centrality_values.clone();
+    if let Some(plot_path) = plot_path {
+        let paths: Vec<Vec<usize>> = paths.into_iter().map(|(_, path)| path).collect();
+        if weighted.is_some() {
+            println!("Note: the layout of the drawing ignores the edge weights.");
+        }
+        // The ego neighbourhoods are small enough for a spectral layout, which keeps
+        // collaborators close; the whole graph uses the circular layout of network.png.
+        let (shown, layout) = if scope == "ego" {
+            let ego = ego_subgraph(&graph, &paths);
+            let layout = spectral_layout(&ego);
+            (ego, layout)
+        } else {
+            (graph.clone(), circular_layout(&graph))
+        };
+        let title = format!("Paths from Author {} to Author {}", graph[source], graph[target]);
+        let mut metadata = FigureMetadata::new(file_path).with_parameter("scope", &scope);
+        if !excluded.is_empty() {
+            let names: Vec<String> = excluded.iter().map(|&node| graph[node].to_string()).collect();
+            metadata = metadata.with_parameter("excluded", names.join(","));
+        }
+        claim_output(plot_path, args)?;
+        path_plot(&shown, &layout, &paths, &title, plot_path, &metadata)
+            .map_err(|e| format!("Failed to render paths: {}", e))?;
+        println!("Paths drawn in {}", plot_path);
+    }
+    Ok(())
+}
 
-        for node in graph.node_indices() {
-            let sum: f64 = graph
-                .edges(node)
-                .map(|edge| centrality_values[&graph[edge.target()]])
-                .sum();
-            next_centrality_values.insert(graph[node], sum);
+/// How many authors are 1, 2, 3, ... collaboration steps away from `author`, like Erdős
+/// numbers, as a table, a CSV file and a histogram. When the edges carry weights, the
+/// CSV file lists every author reached with their distance over edge lengths instead.
+fn run_distances_from(file_path: &str, author: &str, output_path: Option<&str>, args: &Args) -> Result<(), String> {
+    let author: usize = author.parse().map_err(|_| format!("Invalid author id: {}", author))?;
+    let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+    let (graph, weighted, _) = load_with_weights(file_path, args)?;
+    let source = author_index(&graph, author).ok_or_else(|| format!("Author {} is not in the graph", author))?;
+
+    let distribution = distance_distribution(&graph, source);
+    let reached: usize = distribution.values().sum();
+    println!("\nDistances from author {}:", author);
+    println!("distance\tauthors\tcumulative");
+    let mut cumulative = 0;
+    for (distance, count) in &distribution {
+        cumulative += count;
+        println!("{}\t{}\t{}", distance, count, cumulative);
+    }
+    let total: usize = distribution.iter().map(|(distance, count)| distance * count).sum();
+    println!(
+        "Reaches {} of {} other authors, at a mean distance of {:.3}; {} are unreachable.",
+        reached - 1,
+        graph.node_count() - 1,
+        total as f64 / (reached - 1).max(1) as f64,
+        graph.node_count() - reached
+    );
+    let by_length = weighted.as_ref().map(|weighted| weighted_distances(weighted, weights, source));
+    if let Some(distances) = &by_length {
+        print_weight_reading(weights);
+        println!("\nNearest authors to author {} by edge length:", author);
+        println!("author\tdistance");
+        for (other, distance) in distances.iter().skip(1).take(10) {
+            println!("{}\t{:.4}", other, distance);
         }
+        let total: f64 = distances.iter().map(|(_, distance)| distance).sum();
+        println!("Mean distance by edge length: {:.3}", total / (distances.len() - 1).max(1) as f64);
+    }
 
-        // Normalize
-        let norm: f64 = next_centrality_values.values().map(|v| v * v).sum::<f64>().sqrt();
-        for value in next_centrality_values.values_mut() {
-            *value /= norm;
+    let csv_path = output_path.map_or_else(|| format!("output/distances_from_{}.csv", author), str::to_string);
+    let mut writer = csv_output(&csv_path, args)?;
+    match &by_length {
+        Some(distances) => {
+            writer.write_record(["author", "distance"]).map_err(|e| e.to_string())?;
+            for row in distances {
+                writer.serialize(row).map_err(|e| e.to_string())?;
+            }
+        }
+        None => {
+            writer.write_record(["distance", "authors"]).map_err(|e| e.to_string())?;
+            for (distance, count) in &distribution {
+                writer.serialize((distance, count)).map_err(|e| e.to_string())?;
+            }
         }
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Distance distribution written to {}", csv_path);
+    if by_length.is_some() {
+        println!("Note: the table of collaboration steps and the histogram ignore the edge weights.");
+    }
 
-        // Check convergence
-        let max_difference = centrality_values
+    let plot_path = Path::new(&csv_path).with_extension("png");
+    let metadata = FigureMetadata::new(file_path).with_parameter("source", author);
+    claim_output(&plot_path, args)?;
+    distance_plot(author, &distribution, &plot_path, &metadata).map_err(|e| format!("Failed to render histogram: {}", e))?;
+    println!("Histogram written to {}", plot_path.display());
+    Ok(())
+}
+
+/// A centrality measure computed on its own.
+type Measure = fn(&AuthorGraph) -> HashMap<usize, f64>;
+
+/// One of the measures of the default analysis, by name.
+fn measure_by_name(name: &str) -> Result<Measure, String> {
+    match name {
+        "degree" => Ok(|graph| degree_centrality(graph).into_iter().map(|(author, degree)| (author, degree as f64)).collect()),
+        "betweenness" => Ok(|graph| betweenness_centrality(graph, true)),
+        "closeness" => Ok(closeness_centrality),
+        "harmonic" => Ok(harmonic_centrality),
+        "eigenvector" => Ok(|graph| eigenvector_centrality(graph).scores),
+        _ => Err(format!("Unknown measure: {} (expected degree, betweenness, closeness, harmonic or eigenvector)", name)),
+    }
+}
+
+/// Compare one measure between two saved runs (--results files).
+fn run_rank_diff_results(first_path: &str, second_path: &str, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let measure: String = args.parse_or("measure", "degree".to_string())?;
+    let read = |path: &str| -> Result<HashMap<usize, f64>, String> {
+        let results = read_results(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        results.scores(&measure).ok_or_else(|| format!("{} has no {} scores", path, measure))
+    };
+    let (first, second) = (read(first_path)?, read(second_path)?);
+    println!("\nTop {} authors by {}:", k, measure);
+    print_rank_diff(first_path, second_path, &rank_diff(&first, &second, k));
+    Ok(())
+}
+
+/// Compare the rankings of two measures on one dataset.
+fn run_rank_diff_measures(file_path: &str, first: &str, second: &str, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let (first_measure, second_measure) = (measure_by_name(first)?, measure_by_name(second)?);
+    let graph = load_or_report(file_path, args)?;
+    let diff = rank_diff(&first_measure(&graph), &second_measure(&graph), k);
+    println!("\nTop {} authors by {} and by {}:", k, first, second);
+    print_rank_diff(first, second, &diff);
+    Ok(())
+}
+
+/// Print two top-k lists side by side, marking how far each author in the second moved
+/// from the first; gains are green and losses red when writing to a terminal (unless
+/// NO_COLOR is set).
+fn print_rank_diff(first_label: &str, second_label: &str, diff: &RankDiff) {
+    use std::io::IsTerminal;
+    let colour = std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let paint = |text: String, code: &str| if colour { format!("\x1b[{}m{}\x1b[0m", code, text) } else { text };
+    println!("{:>4}  {:<28}  {:<28}  move", "rank", first_label, second_label);
+    for row in 0..diff.first.len().max(diff.second.len()) {
+        let left = diff.first.get(row).map_or(String::new(), |&(author, score, _)| format!("{:<8} {:.6}", author, score));
+        let (right, movement) = match diff.second.get(row) {
+            Some(entry) => {
+                let movement = match entry.change() {
+                    None => paint("new".to_string(), "33"),
+                    Some(0) => "=".to_string(),
+                    Some(change) if change > 0 => paint(format!("\u{2191}{}", change), "32"),
+                    Some(change) => paint(format!("\u{2193}{}", -change), "31"),
+                };
+                (format!("{:<8} {:.6}", entry.author, entry.score), movement)
+            }
+            None => (String::new(), String::new()),
+        };
+        println!("{:>4}  {:<28}  {:<28}  {}", row + 1, left, right, movement);
+    }
+    if !diff.dropped.is_empty() {
+        let dropped: Vec<String> = diff
+            .dropped
             .iter()
-            .map(|(node, value)| (value - next_centrality_values[node]).abs())
-            .fold(0.0, f64::max);
+            .map(|(author, rank)| match rank {
+                Some(rank) => format!("{} (now #{})", author, rank),
+                None => format!("{} (not ranked)", author),
+            })
+            .collect();
+        println!("{}", paint(format!("Dropped out of the top {}: {}", diff.first.len(), dropped.join(", ")), "31"));
+    }
+}
+
+/// Bootstrap confidence intervals for the ranks of the top authors; positions whose
+/// interval does not overlap their neighbours' are marked as meaningful.
+fn run_bootstrap(file_path: &str, args: &Args) -> Result<(), String> {
+    let name: String = args.parse_or("measure", "degree".to_string())?;
+    let measure = measure_by_name(&name)?;
+    let resamples: usize = args.parse_or("resamples", 100)?;
+    if resamples == 0 {
+        return Err("--resamples must be positive".to_string());
+    }
+    let confidence: f64 = args.parse_or("confidence", 0.95)?;
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(format!("--confidence must be between 0 and 1, got {}", confidence));
+    }
+    let seed = args.parse_or("seed", 42)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+
+    let intervals = bootstrap_ranks(&graph, measure, k, resamples, confidence, seed);
+    println!(
+        "\nTop {} authors by {} centrality, with {:.0}% bootstrap rank intervals over {} edge resamples:",
+        k,
+        name,
+        confidence * 100.0,
+        resamples
+    );
+    println!("position\tauthor\tscore\trank interval\tin top {}\tmeaningful", k);
+    for (position, interval) in intervals.iter().enumerate() {
+        println!(
+            "{}\t{}\t{:.6}\t{}-{}\t{:.0}%\t{}",
+            position + 1,
+            interval.author,
+            interval.score,
+            interval.lower,
+            interval.upper,
+            interval.top_k_share * 100.0,
+            if interval.separated { "yes" } else { "no" }
+        );
+    }
+    let meaningful = intervals.iter().filter(|interval| interval.separated).count();
+    println!("{} of {} positions are separated from their neighbours.", meaningful, intervals.len());
+    Ok(())
+}
+
+/// Robustness of the centrality rankings to noise: rewire --fraction of the edges with
+/// degree-preserving swaps in each of --trials runs and compare every measure with the
+/// unperturbed ranking.
+fn run_noise(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let fraction: f64 = args.parse_or("fraction", 0.05)?;
+    if !(fraction > 0.0 && fraction <= 1.0) {
+        return Err(format!("--fraction must be in (0, 1], got {}", fraction));
+    }
+    let trials: usize = args.parse_or("trials", 5)?;
+    let seed: u64 = args.parse_or("seed", 42)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
 
-        if max_difference < tolerance {
-            break;
+    let original = centrality_scores(&graph);
+    let mut writer = csv_output(output_path, args)?;
+    writer
+        .write_record(["trial", "measure", "edges_rewired", "kendall_tau", "top_k_overlap"])
+        .map_err(|e| e.to_string())?;
+    let mut summary: Vec<(&str, Vec<f64>, Vec<f64>)> = Vec::new();
+    for trial in 0..trials {
+        let mut log = AuditLog::default();
+        let perturbed = rewire(&graph, fraction, seed + trial as u64, &mut log);
+        let rewired = log.entries.first().map_or(0, |entry| entry.count);
+        println!("Trial {}: {}", trial + 1, if log.is_empty() { "no swap possible".to_string() } else { log.to_string() });
+        let scores = centrality_scores(&perturbed);
+        for ((name, before), (_, after)) in original.measures().into_iter().zip(scores.measures()) {
+            let stability = rank_stability(before, after, k);
+            writer
+                .serialize((trial + 1, name, rewired, stability.kendall_tau, stability.top_k_overlap))
+                .map_err(|e| e.to_string())?;
+            match summary.iter_mut().find(|(measure, _, _)| *measure == name) {
+                Some((_, taus, overlaps)) => {
+                    taus.push(stability.kendall_tau);
+                    overlaps.push(stability.top_k_overlap);
+                }
+                None => summary.push((name, vec![stability.kendall_tau], vec![stability.top_k_overlap])),
+            }
         }
+    }
+    writer.flush().map_err(|e| e.to_string())?;
 
-        centrality_values = next_centrality_values;
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len().max(1) as f64;
+    println!("\nRank stability with {:.1}% of the edges rewired, over {} trials:", fraction * 100.0, trials);
+    println!("measure\tmean tau\tmin tau\tmean top-{} overlap", k);
+    for (name, taus, overlaps) in &summary {
+        let min = taus.iter().copied().fold(f64::INFINITY, f64::min);
+        println!("{}\t{:.4}\t{:.4}\t{:.3}", name, mean(taus), min, mean(overlaps));
     }
+    println!("Per-trial metrics written to {}", output_path);
+    Ok(())
+}
 
-    // Store eigenvector centralities as usize for compatibility with print_top
-    for (node, value) in centrality_values {
-        eigenvector_centrality.insert(node, (value * 1_000_000.0) as usize); // Scale to usize for readability
+/// Weisfeiler–Lehman fingerprints, to check whether edge lists describe the same graph.
+fn run_fingerprint(file_paths: &[&str], args: &Args) -> Result<(), String> {
+    let mut fingerprints = Vec::with_capacity(file_paths.len());
+    for file_path in file_paths {
+        let fingerprint = wl_fingerprint(&load_or_report(file_path, args)?);
+        println!("{}\t{}", fingerprint, file_path);
+        fingerprints.push(fingerprint);
+    }
+    if fingerprints.len() > 1 {
+        if fingerprints.iter().all(|f| *f == fingerprints[0]) {
+            println!("All fingerprints match: the graphs are very likely isomorphic.");
+        } else {
+            println!("Fingerprints differ: the graphs are not isomorphic.");
+        }
     }
+    Ok(())
+}
 
-    // Print results
-    println!("Top authors by degree centrality:");
-    print_top(&degree_centrality);
+/// Write the graph in networkx's node-link JSON format.
+fn run_export_json(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    claim_output(output_path, args)?;
+    write_node_link(&graph, output_path).map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    println!("Node-link JSON written to {}", output_path);
+    Ok(())
+}
 
-    println!("\nTop authors by betweenness centrality:");
-    print_top(&betweenness_centrality);
+/// Print one page of the author rows of a results file written with --results.
+fn run_results_page(results_path: &str, args: &Args) -> Result<(), String> {
+    let offset: usize = args.parse_or("offset", 0)?;
+    let limit: usize = args.parse_or("limit", 100)?;
+    if limit == 0 {
+        return Err("--limit must be positive".to_string());
+    }
+    let results = read_results(results_path).map_err(|e| format!("Failed to read {}: {}", results_path, e))?;
+    let page = results.page(offset, limit);
+    println!("author\tdegree\tbetweenness\tcloseness\tharmonic\teigenvector\tclustering");
+    for (i, author) in page.authors.iter().enumerate() {
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            author,
+            page.degree[i],
+            page.betweenness[i],
+            page.closeness[i],
+            page.harmonic[i],
+            page.eigenvector[i],
+            page.clustering.get(i).map_or(String::new(), f64::to_string)
+        );
+    }
+    let shown = page.offset + page.authors.len();
+    match page.next {
+        _ if page.authors.is_empty() => println!("No rows from offset {} ({} in total)", offset, page.total),
+        Some(next) => println!("Rows {}-{} of {}; next page: --offset {}", page.offset + 1, shown, page.total, next),
+        None => println!("Rows {}-{} of {} (last page)", page.offset + 1, shown, page.total),
+    }
+    Ok(())
+}
+
+/// Write a canonical, sorted and deduplicated copy of an edge list.
+fn run_normalize(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    let canonical = canonical_edges(&graph, args.has("dense"));
+    claim_output(output_path, args)?;
+    write_canonical(&canonical, output_path).map_err(|e| e.to_string())?;
+    println!("Normalized edge list written to {}", output_path);
 
-    println!("\nTop authors by eigenvector centrality:");
-    print_top(&eigenvector_centrality);
+    if let Some(original) = &canonical.original {
+        let mapping_path = Path::new(output_path).with_extension("ids.csv");
+        let mut writer = csv_output(&mapping_path, args)?;
+        writer.write_record(["id", "author"]).map_err(|e| e.to_string())?;
+        for (id, author) in original.iter().enumerate() {
+            writer.write_record([id.to_string(), author.to_string()]).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        println!("Dense id mapping written to {}", mapping_path.display());
+    }
+    Ok(())
 }
 
-/// Utility to print the top centrality values.
-fn print_top(centrality: &HashMap<usize, usize>) {
-    let mut centrality_vec: Vec<_> = centrality.iter().collect();
-    centrality_vec.sort_by(|a, b| b.1.cmp(a.1));
-    for &(author, score) in centrality_vec.iter().take(10) {
-        println!("Author {}: {}", author, score);
+/// Compare dataset statistics with the values published by SNAP to catch loader bugs.
+fn run_verify(file_path: &str, args: &Args) -> Result<(), String> {
+    let reference = match args.value("reference") {
+        Some(name) => REFERENCES.iter().find(|r| r.name.eq_ignore_ascii_case(name)),
+        None => reference_for(file_path),
+    };
+    let Some(reference) = reference else {
+        let names: Vec<&str> = REFERENCES.iter().map(|r| r.name).collect();
+        return Err(format!("No reference values for {} (known: {})", file_path, names.join(", ")));
+    };
+    let graph = load_or_report(file_path, args)?;
+    let self_loops = self_loop_authors(file_path).map_err(|e| e.to_string())?;
+    let stats = graph_stats(&graph, true, 0);
+
+    println!("\nComparison with SNAP {} ({} self-loops restored)", reference.name, self_loops.len());
+    println!("{:<34}{:>12}{:>12}  status", "statistic", "computed", "reference");
+    let checks = compare(&graph, &stats, &self_loops, reference);
+    for check in &checks {
+        let status = if check.passed() { "ok" } else { "MISMATCH" };
+        let precision = if check.tolerance == 0.0 { 0 } else { 4 };
+        println!(
+            "{:<34}{:>12.*}{:>12.*}  {}",
+            check.statistic, precision, check.computed, precision, check.expected, status
+        );
+    }
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} statistics differ from the reference", failed, checks.len()));
     }
+    println!("All {} statistics match the reference.", checks.len());
+    Ok(())
 }
 
-/// Visualize the graph.
-pub fn visualize_graph(graph: &AuthorGraph) {
-    // Ensure the output directory exists
-    let output_dir = "output";
-    fs::create_dir_all(output_dir).unwrap();
+/// Smooth a per-author signal over the collaboration graph and write both versions.
+fn run_smooth(file_path: &str, values_path: &str, args: &Args) -> Result<(), String> {
+    let method: String = args.parse_or("method", "heat".to_string())?;
+    let time = args.parse_or("time", 1.0)?;
+    let graph = load_or_report(file_path, args)?;
+    let signal = load_signal(values_path).map_err(|e| format!("Failed to load values: {}", e))?;
+    println!("Loaded values for {} authors.", signal.len());
 
-    let root = BitMapBackend::new("output/network.png", (1024, 768)).into_drawing_area();
-    root.fill(&WHITE).unwrap();
-    let mut chart = ChartBuilder::on(&root)
-        .caption("Collaboration Network", ("sans-serif", 50))
-        .build_cartesian_2d(-10..10, -10..10)
-        .unwrap();
+    let smoothed = match method.as_str() {
+        "average" => neighbourhood_average(&graph, &signal),
+        "heat" => heat_kernel(&graph, &signal, time),
+        _ => return Err(format!("Unknown smoothing method: {} (expected average or heat)", method)),
+    };
 
-    chart.configure_mesh().draw().unwrap();
+    let csv_path = "output/smoothed.csv";
+    let mut writer = csv_output(csv_path, args)?;
+    writer.write_record(["author", "value", "smoothed"]).map_err(|e| e.to_string())?;
+    let mut authors: Vec<usize> = smoothed.keys().copied().collect();
+    authors.sort_unstable();
+    for author in authors {
+        let original = signal.get(&author).map(f64::to_string).unwrap_or_default();
+        writer
+            .write_record([author.to_string(), original, smoothed[&author].to_string()])
+            .map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Smoothed values for {} authors written to {}", smoothed.len(), csv_path);
+    Ok(())
+}
 
-    for edge in graph.edge_references() {
-        let (start, end) = (
-            graph[edge.source()],
-            graph[edge.target()],
+/// Group degree, closeness and betweenness of author sets such as labs or institutions.
+fn run_group(file_path: &str, specs: &[&str], args: &Args) -> Result<(), String> {
+    let graph = load_or_report(file_path, args)?;
+    println!("\ngroup\tmembers\tdegree\tcloseness\tbetweenness");
+    for spec in specs {
+        let (name, path) = match spec.split_once('=') {
+            Some((name, path)) => (name.to_string(), path),
+            None => (Path::new(spec).file_stem().map_or(spec.to_string(), |s| s.to_string_lossy().into_owned()), *spec),
+        };
+        let group = load_group(path).map_err(|e| format!("Failed to load group {}: {}", name, e))?;
+        let centrality = group_centrality(&graph, &group);
+        if centrality.size < group.len() {
+            eprintln!("Group {}: {} of {} authors are not in the graph.", name, group.len() - centrality.size, group.len());
+        }
+        println!(
+            "{}\t{}\t{:.4}\t{:.4}\t{:.4}",
+            name, centrality.size, centrality.degree, centrality.closeness, centrality.betweenness
         );
-        chart.draw_series(LineSeries::new(
-            vec![(start as i32, 0), (end as i32, 0)],
-            &BLACK,
-        )).unwrap();
     }
+    Ok(())
+}
 
-    root.present().unwrap();
+/// Print the authors with the largest strength, which reads the weights as they are
+/// next to the shortest-path measures that read them through --weights.
+fn print_strength(graph: &WeightedAuthorGraph, k: usize) {
+    println!("\nTop {} authors by strength (sum of raw collaboration weights):", k);
+    print_top(&strength(graph), k);
 }
 
-/// Utility to read lines from a file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+/// Betweenness by adaptive sampling, accurate to --epsilon with probability 1 - --delta,
+/// or with --exact the exact top-k set found with bound pruning.
+fn run_betweenness(file_path: &str, args: &Args) -> Result<(), String> {
+    if args.has("weighted") {
+        let k = args.parse_or("top-k", 10)?;
+        let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+        let graph = load_weighted_or_report(file_path, args)?;
+        let start = Instant::now();
+        let scores = weighted_betweenness_centrality(&graph, weights, true);
+        println!("Weighted betweenness from {} Dijkstra searches in {:.1?}.", graph.node_count(), start.elapsed());
+        println!("\nTop {} authors by weighted betweenness centrality:", k);
+        print_top(&scores, k);
+        print_strength(&graph, k);
+        return Ok(());
+    }
+    if args.has("exact") {
+        let k = args.parse_or("top-k", 10)?;
+        let graph = load_or_report(file_path, args)?;
+        let start = Instant::now();
+        let top = top_k_betweenness(&graph, k);
+        println!(
+            "Exact top {} found with {} of {} breadth-first searches in {:.1?}.",
+            top.ranking.len(),
+            top.searches,
+            graph.node_count(),
+            start.elapsed()
+        );
+        println!("\nTop {} authors by betweenness centrality (score bounds):", k);
+        for bounds in &top.ranking {
+            println!("Author {}: [{}, {}]", bounds.author, bounds.lower, bounds.upper);
+        }
+        return Ok(());
+    }
+    let epsilon: f64 = args.parse_or("epsilon", 0.01)?;
+    let delta: f64 = args.parse_or("delta", 0.1)?;
+    for (name, value) in [("epsilon", epsilon), ("delta", delta)] {
+        if !(value > 0.0 && value < 1.0) {
+            return Err(format!("--{} must be between 0 and 1, got {}", name, value));
+        }
+    }
+    let seed = args.parse_or("seed", 42)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+
+    let start = Instant::now();
+    let estimate = approximate_betweenness(&graph, epsilon, delta, seed);
+    println!(
+        "Betweenness sampled from {} shortest paths (at most {} needed) in {:.1?}; error at most {:.4} with probability {}.",
+        estimate.samples,
+        estimate.max_samples,
+        start.elapsed(),
+        estimate.error_bound,
+        1.0 - delta
+    );
+    println!("\nTop {} authors by betweenness centrality:", k);
+    print_top(&estimate.scores, k);
+    Ok(())
 }
 
-/// Main function to tie everything together.
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Usage: {} <path_to_dataset>", args[0]);
-        return;
+/// PageRank with configurable damping and stopping rule, reporting whether it converged.
+fn run_pagerank(file_path: &str, args: &Args) -> Result<(), String> {
+    let defaults = PageRankOptions::default();
+    let options = PageRankOptions {
+        damping: args.parse_or("damping", defaults.damping)?,
+        max_iterations: args.parse_or("max-iterations", defaults.max_iterations)?,
+        tolerance: args.parse_or("tolerance", defaults.tolerance)?,
+    };
+    options.validate()?;
+    let k = args.parse_or("top-k", 10)?;
+    let (rank, elapsed) = if args.has("directed") {
+        let graph = load_directed_or_report(file_path, args)?;
+        let start = Instant::now();
+        (pagerank_with(&graph, &options, None), start.elapsed())
+    } else {
+        let graph = load_or_report(file_path, args)?;
+        let start = Instant::now();
+        (pagerank_with(&graph, &options, None), start.elapsed())
+    };
+    let status = if rank.residual < options.tolerance { "converged" } else { "did not converge" };
+    println!(
+        "PageRank (damping {}) {} after {} iterations, L1 residual {:.3e} ({:.1?}).",
+        options.damping, status, rank.iterations, rank.residual, elapsed
+    );
+    println!("\nTop {} authors by PageRank:", k);
+    print_top(&rank.scores, k);
+    Ok(())
+}
+
+/// The collaborations with the highest edge betweenness, printed and written as CSV.
+fn run_edge_betweenness(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let ranking = top_k(&edge_betweenness_centrality(&graph, true), k);
+    println!("\nTop {} collaborations by edge betweenness:", k);
+    for &((a, b), score) in &ranking {
+        println!("Authors {}-{}: {}", a, b, score);
+    }
+    let mut writer = csv_output(output_path, args)?;
+    writer.write_record(["rank", "author_a", "author_b", "edge_betweenness"]).map_err(|e| e.to_string())?;
+    for (rank, ((a, b), score)) in ranking.iter().enumerate() {
+        writer
+            .write_record([(rank + 1).to_string(), a.to_string(), b.to_string(), score.to_string()])
+            .map_err(|e| e.to_string())?;
     }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("\nTop collaborations written to {}", output_path);
+    Ok(())
+}
 
-    let file_path = &args[1];
+/// Current-flow betweenness, next to the shortest-path betweenness of the same authors.
+fn run_current_flow(file_path: &str, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let current = stage!("current-flow", current_flow_betweenness(&graph, true));
+    let shortest = stage!("betweenness", betweenness_centrality(&graph, true));
+    println!("\nTop {} authors by current-flow betweenness:", k);
+    println!("author\tcurrent-flow\tshortest-path");
+    for (author, score) in top_k(&current, k) {
+        println!("{}\t{:.6}\t{:.6}", author, score, shortest[&author]);
+    }
+    Ok(())
+}
 
-    match load_graph(file_path) {
-        Ok(graph) => {
-            println!("Graph loaded with {} nodes and {} edges.", graph.node_count(), graph.edge_count());
+/// Katz centrality; --alpha defaults to half the largest value for which it converges.
+fn run_katz(file_path: &str, args: &Args) -> Result<(), String> {
+    let alpha: Option<f64> = args.parse_optional("alpha")?;
+    let beta: f64 = args.parse_or("beta", 1.0)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
 
-            let components = connected_components(&graph);
-            println!("Number of connected components: {}", components);
+    let radius = spectral_radius(&graph);
+    println!("Spectral radius: {:.6} (alpha must be below {:.6})", radius, 1.0 / radius);
+    let alpha = alpha.unwrap_or(0.5 / radius.max(1.0));
+    let katz = katz_centrality(&graph, alpha, beta)?;
+    println!("\nTop {} authors by Katz centrality (alpha {:.6}, beta {}):", k, alpha, beta);
+    print_top(&katz, k);
+    Ok(())
+}
 
-            compute_centralities(&graph);
-            visualize_graph(&graph);
-        }
-        Err(e) => {
-            eprintln!("Failed to load graph: {}", e);
+/// Sampled (Eppstein–Wang) closeness with per-author confidence intervals.
+fn run_closeness(file_path: &str, args: &Args) -> Result<(), String> {
+    let samples: usize = args.parse_or("samples", 200)?;
+    if samples == 0 {
+        return Err("--samples must be positive".to_string());
+    }
+    let confidence: f64 = args.parse_or("confidence", 0.95)?;
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(format!("--confidence must be between 0 and 1, got {}", confidence));
+    }
+    let seed = args.parse_or("seed", 42)?;
+    let k = args.parse_or("top-k", 10)?;
+    if args.has("weighted") {
+        let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+        let graph = load_weighted_or_report(file_path, args)?;
+        let start = Instant::now();
+        let scores = weighted_closeness_centrality(&graph, weights);
+        println!("Weighted closeness from {} Dijkstra searches in {:.1?}.", graph.node_count(), start.elapsed());
+        println!("\nTop {} authors by weighted closeness centrality:", k);
+        print_top(&scores, k);
+        print_strength(&graph, k);
+        return Ok(());
+    }
+    let graph = load_or_report(file_path, args)?;
+
+    let start = Instant::now();
+    let estimates = approximate_closeness(&graph, samples, confidence, seed);
+    println!("Closeness estimated from up to {} sources per component in {:.1?}.", samples, start.elapsed());
+    let closeness = estimates.iter().map(|(&author, estimate)| (author, estimate.closeness)).collect();
+    println!("\nTop {} authors by closeness centrality ({:.0}% intervals):", k, confidence * 100.0);
+    for (author, score) in top_k(&closeness, k) {
+        let estimate = &estimates[&author];
+        println!("Author {}: {:.4} [{:.4}, {:.4}]", author, score, estimate.lower, estimate.upper);
+    }
+    Ok(())
+}
+
+/// Stress or load centrality, chosen with --measure.
+fn run_path_centrality(file_path: &str, args: &Args) -> Result<(), String> {
+    let measure: PathCentrality = args.parse_or("measure", PathCentrality::Load)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let name = match measure {
+        PathCentrality::Stress => "stress",
+        PathCentrality::Load => "load",
+    };
+    println!("\nTop {} authors by {} centrality:", k, name);
+    print_top(&path_centrality(&graph, measure), k);
+    Ok(())
+}
+
+/// Percolation centrality from per-author percolation states in [0, 1].
+fn run_percolation(file_path: &str, states_path: &str, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let states = load_signal(states_path).map_err(|e| format!("Failed to load states: {}", e))?;
+    if let Some((author, state)) = states.iter().find(|(_, &x)| !(0.0..=1.0).contains(&x)) {
+        return Err(format!("Percolation state of author {} must be in [0, 1], got {}", author, state));
+    }
+    let percolated = states.values().filter(|&&x| x > 0.0).count();
+    println!("Loaded states for {} authors ({} percolated).", states.len(), percolated);
+
+    println!("\nTop {} authors by percolation centrality:", k);
+    print_top(&percolation_centrality(&graph, &states), k);
+    Ok(())
+}
+
+/// Attribute-weighted degree and attribute-personalized PageRank, with plain PageRank
+/// for comparison.
+fn run_attributes(file_path: &str, values_path: &str, args: &Args) -> Result<(), String> {
+    let damping: f64 = args.parse_or("damping", 0.85)?;
+    let k = args.parse_or("top-k", 10)?;
+    if !(0.0..1.0).contains(&damping) {
+        return Err(format!("Damping factor must be in [0, 1), got {}", damping));
+    }
+    let graph = load_or_report(file_path, args)?;
+    let attribute = load_signal(values_path).map_err(|e| format!("Failed to load values: {}", e))?;
+    println!("Loaded values for {} authors.", attribute.len());
+
+    println!("\nTop {} authors by attribute-weighted degree:", k);
+    print_top(&attribute_weighted_degree(&graph, &attribute), k);
+    println!("\nTop {} authors by PageRank:", k);
+    print_top(&pagerank(&graph, damping, None), k);
+    println!("\nTop {} authors by attribute-personalized PageRank:", k);
+    print_top(&pagerank(&graph, damping, Some(&attribute)), k);
+    Ok(())
+}
+
+/// Edge centralities from node centralities of the line graph.
+fn run_line_graph(file_path: &str, args: &Args) -> Result<(), String> {
+    let damping: f64 = args.parse_or("damping", 0.85)?;
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_or_report(file_path, args)?;
+    let line = line_graph(&graph);
+    println!("Line graph has {} nodes and {} edges.", line.graph.node_count(), line.graph.edge_count());
+
+    let rankings = [
+        ("degree", degree_centrality(&line.graph).into_iter().map(|(id, d)| (id, d as f64)).collect()),
+        ("PageRank", pagerank(&line.graph, damping, None)),
+    ];
+    for (name, scores) in &rankings {
+        println!("\nTop {} collaborations by line-graph {}:", k, name);
+        for (id, score) in top_k(scores, k) {
+            let (a, b) = line.collaboration(id);
+            println!("Authors {}-{}: {}", a, b, score);
         }
     }
+
+    let output_path = "output/line_graph.txt";
+    claim_output(output_path, args)?;
+    write_edge_list(&line.graph, "line graph: collaboration\tcollaboration", output_path).map_err(|e| e.to_string())?;
+    let mapping_path = "output/line_graph_nodes.csv";
+    let mut writer = csv_output(mapping_path, args)?;
+    writer.write_record(["collaboration", "author_a", "author_b"]).map_err(|e| e.to_string())?;
+    for (id, (a, b)) in line.edges.iter().enumerate() {
+        writer.write_record([id.to_string(), a.to_string(), b.to_string()]).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("\nLine graph written to {} with its collaborations in {}", output_path, mapping_path);
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Expand one-paper-per-line author lists into an edge list the other commands can read.
+fn run_hypergraph(file_path: &str, args: &Args) -> Result<(), String> {
+    let expansion: Expansion = args.parse_or("expansion", Expansion::Clique)?;
+    let hypergraph = load_hypergraph(file_path).map_err(|e| format!("Failed to load hypergraph: {}", e))?;
+    let largest = hypergraph.hyperedges.iter().map(Vec::len).max().unwrap_or(0);
+    println!("Hypergraph loaded with {} hyperedges (largest has {} authors).", hypergraph.hyperedges.len(), largest);
 
-    #[test]
-    fn test_load_graph() {
-        let test_data = "ca-GrQc.txt";
-        std::fs::write(test_data, "1\t2\n2\t3\n3\t1\n4\t5\n").unwrap();
+    let graph = hypergraph.expand(expansion);
+    println!("Expanded graph has {} nodes and {} edges.", graph.node_count(), graph.edge_count());
+    let header = match expansion {
+        Expansion::Clique => "clique expansion: author\tauthor".to_string(),
+        Expansion::Star => format!("star expansion: paper i is node {} + i", hypergraph.first_paper_id()),
+    };
+    let output_path = "output/expanded_graph.txt";
+    claim_output(output_path, args)?;
+    write_edge_list(&graph, &header, output_path).map_err(|e| e.to_string())?;
+    println!("Expanded graph written to {}", output_path);
+    Ok(())
+}
 
-        let graph = load_graph(test_data).unwrap();
-        assert_eq!(graph.node_count(), 5);
-        assert_eq!(graph.edge_count(), 4);
+/// Structural balance and signed clustering of a graph with +/- edges.
+fn run_signed(file_path: &str, args: &Args) -> Result<(), String> {
+    let k = args.parse_or("top-k", 10)?;
+    let graph = load_signed_graph(file_path).map_err(|e| format!("Failed to load graph: {}", e))?;
+    let negative = graph.edge_weights().filter(|&&sign| sign < 0).count();
+    println!(
+        "Signed graph loaded with {} nodes and {} edges ({} positive, {} negative).",
+        graph.node_count(),
+        graph.edge_count(),
+        graph.edge_count() - negative,
+        negative
+    );
 
-        std::fs::remove_file(test_data).unwrap();
+    let census = triad_census(&graph);
+    println!("\nTriangles by number of negative edges:");
+    for (negatives, count) in census.by_negative_edges.iter().enumerate() {
+        let kind = if negatives % 2 == 0 { "balanced" } else { "unbalanced" };
+        println!("{}\t{}\t({})", negatives, count, kind);
     }
+    println!("Balanced fraction of {} triangles: {:.4}", census.total(), census.balance_ratio());
+    println!("Global signed clustering coefficient: {:.4}", global_signed_clustering(&graph));
 
-    #[test]
-    fn test_compute_centralities() {
-        let mut graph = AuthorGraph::new_undirected();
-        let n1 = graph.add_node(1);
-        let n2 = graph.add_node(2);
-        let n3 = graph.add_node(3);
+    let clustering = signed_clustering(&graph);
+    let average = clustering.values().sum::<f64>() / clustering.len().max(1) as f64;
+    println!("Average local signed clustering coefficient: {:.4}", average);
+    println!("\nTop {} authors by signed clustering:", k);
+    print_top(&clustering, k);
+    Ok(())
+}
 
-        graph.add_edge(n1, n2, ());
-        graph.add_edge(n2, n3, ());
+/// Per-layer and aggregated degree and PageRank of a multilayer network, plus the
+/// correlation of degrees between layers.
+fn run_multilayer(specs: &[&str], args: &Args) -> Result<(), String> {
+    let damping: f64 = args.parse_or("damping", 0.85)?;
+    let k = args.parse_or("top-k", 10)?;
+    let multilayer = Multilayer::load(specs, &load_options(args)?).map_err(|e| format!("Failed to load layers: {}", e))?;
+    for (name, layer) in multilayer.names.iter().zip(&multilayer.layers) {
+        if layer.node_count() == 0 {
+            return Err(format!("Layer {} has no edges", name));
+        }
+        println!("Layer {}: {} nodes and {} edges.", name, layer.node_count(), layer.edge_count());
+    }
+    for (name, (_, exclusive)) in multilayer.names.iter().zip(multilayer.edge_counts()) {
+        println!("Edges only in {}: {}", name, exclusive);
+    }
+    let aggregate = multilayer.aggregate();
+    println!("Aggregate: {} nodes and {} edges.", aggregate.node_count(), aggregate.edge_count());
 
-        compute_centralities(&graph);
+    let mut names: Vec<&str> = multilayer.names.iter().map(String::as_str).collect();
+    names.push("aggregate");
+    let graphs: Vec<&AuthorGraph> = multilayer.layers.iter().chain([&aggregate]).collect();
+    let degrees: Vec<_> = graphs.iter().map(|graph| degree_centrality(graph)).collect();
+    let ranks: Vec<_> = graphs.iter().map(|graph| pagerank(graph, damping, None)).collect();
+    for (i, name) in names.iter().enumerate() {
+        println!("\nTop {} authors by degree in {}:", k, name);
+        print_top(&degrees[i], k);
+        println!("\nTop {} authors by PageRank in {}:", k, name);
+        print_top(&ranks[i], k);
+    }
 
-        // Simple assertions to ensure the function runs
-        assert!(graph.node_count() > 0);
+    println!("\nDegree correlation between layers (Pearson):");
+    println!("\t{}", multilayer.names.join("\t"));
+    for (name, row) in multilayer.names.iter().zip(multilayer.degree_correlation()) {
+        let cells: Vec<String> = row.iter().map(|r| format!("{:.3}", r)).collect();
+        println!("{}\t{}", name, cells.join("\t"));
     }
 
-    #[test]
-    fn test_connected_components() {
-        let mut graph = AuthorGraph::new_undirected();
-        let n1 = graph.add_node(1);
-        let n2 = graph.add_node(2);
-        let n3 = graph.add_node(3);
-        let n4 = graph.add_node(4);
-        let n5 = graph.add_node(5);
+    let csv_path = "output/multilayer_centrality.csv";
+    let mut writer = csv_output(csv_path, args)?;
+    let header = std::iter::once("author".to_string())
+        .chain(names.iter().map(|name| format!("{}_degree", name)))
+        .chain(names.iter().map(|name| format!("{}_pagerank", name)));
+    writer.write_record(header).map_err(|e| e.to_string())?;
+    let mut authors = multilayer.authors();
+    authors.sort_unstable();
+    for author in authors {
+        let record = std::iter::once(author.to_string())
+            .chain(degrees.iter().map(|d| d.get(&author).copied().unwrap_or(0).to_string()))
+            .chain(ranks.iter().map(|r| r.get(&author).copied().unwrap_or(0.0).to_string()));
+        writer.write_record(record).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("\nPer-layer centralities written to {}", csv_path);
+    Ok(())
+}
 
-        graph.add_edge(n1, n2, ());
-        graph.add_edge(n2, n3, ());
-        graph.add_edge(n4, n5, ());
+/// Estimate the distance distribution and effective diameter with HyperANF.
+fn run_anf(file_path: &str, args: &Args) -> Result<(), String> {
+    let log2_registers = args.parse_or("log2-registers", 8)?;
+    let seed = args.parse_or("seed", 42)?;
+    let graph = load_or_report(file_path, args)?;
 
-        let components = connected_components(&graph);
-        assert_eq!(components, 2);
+    let start = Instant::now();
+    let function = hyper_anf(&graph, log2_registers, seed);
+    println!("HyperANF converged after {} steps in {:.1?}.", function.pairs.len() - 1, start.elapsed());
+    let distribution = function.distance_distribution();
+    println!("\n{:>8}  {:>14}  {:>14}", "distance", "pairs", "cumulative");
+    for (t, (&count, &cumulative)) in distribution.iter().zip(&function.pairs).enumerate() {
+        println!("{:>8}  {:>14.0}  {:>14.0}", t, count, cumulative);
     }
+    println!("\nEffective diameter (90th percentile): {:.2}", function.effective_diameter(0.9));
+    println!("Average distance: {:.2}", function.average_distance());
 
-    #[test]
-fn test_visualize_graph() {
-    // Create a small sample graph
-    let mut graph = AuthorGraph::new_undirected();
-    let n1 = graph.add_node(1);
-    let n2 = graph.add_node(2);
-    let n3 = graph.add_node(3);
-    
-    graph.add_edge(n1, n2, ());
-    graph.add_edge(n2, n3, ());
-    graph.add_edge(n3, n1, ());
+    let csv_path = "output/distance_distribution.csv";
+    let mut writer = csv_output(csv_path, args)?;
+    writer.write_record(["distance", "pairs", "cumulative"]).map_err(|e| e.to_string())?;
+    for (t, (count, cumulative)) in distribution.iter().zip(&function.pairs).enumerate() {
+        writer
+            .write_record([t.to_string(), count.to_string(), cumulative.to_string()])
+            .map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Distance distribution written to {}", csv_path);
+    Ok(())
+}
 
-    // Call the visualization function
-    visualize_graph(&graph);
+/// Average shortest-path length and hop-count distribution of the largest component,
+/// next to what a random graph of the same size and mean degree would have, to show
+/// whether the network is a small world: distances as short as a random graph's, but
+/// far more clustering.
+fn run_path_lengths(file_path: &str, output_path: &str, args: &Args) -> Result<(), String> {
+    let log2_registers = args.parse_or("log2-registers", 8)?;
+    let seed = args.parse_or("seed", 42)?;
+    let weights = args.parse_or("weights", EdgeWeights::Similarity)?;
+    let samples = args.parse_or("samples", 200)?;
+    let (graph, weighted, _) = load_with_weights(file_path, args)?;
+    let component = largest_component_graph(&graph);
+    let exact = !args.has("estimate") && component.node_count() <= EXACT_LIMIT;
+    let function = if exact {
+        stage!("distances", exact_neighbourhood_function(&component))
+    } else {
+        stage!("distances", hyper_anf(&component, log2_registers, seed))
+    };
 
-    // Check that the output file exists
-    let output_path = "output/network.png";
-    assert!(std::path::Path::new(output_path).exists());
-    
-    // Clean up the generated file
-    std::fs::remove_file(output_path).unwrap();
+    // Ordered pairs, each author with itself at distance 0; report unordered pairs.
+    let distribution = function.distance_distribution();
+    let pairs: f64 = distribution.iter().skip(1).sum::<f64>() / 2.0;
+    let n = component.node_count() as f64;
+    let mean_degree = 2.0 * component.edge_count() as f64 / n.max(1.0);
+    let clustering = local_clustering(&component);
+    println!(
+        "\nLargest component: {} authors, {} collaborations ({})",
+        component.node_count(),
+        component.edge_count(),
+        if exact { "exact" } else { "HyperANF estimate" }
+    );
+    // (distance, pairs, fraction, cumulative fraction)
+    let mut rows = Vec::new();
+    let mut cumulative = 0.0;
+    for (distance, &count) in distribution.iter().enumerate().skip(1) {
+        cumulative += count / 2.0;
+        rows.push((distance, count / 2.0, count / 2.0 / pairs.max(1.0), cumulative / pairs.max(1.0)));
+    }
+    println!("{:>8}  {:>12}  {:>10}", "distance", "pairs", "cumulative");
+    for &(distance, count, _, cumulative) in &rows {
+        println!("{:>8}  {:>12.0}  {:>10.4}", distance, count, cumulative);
+    }
+    println!("\n{:<24}  {:>10}  {:>10}", "", "observed", "random");
+    println!("{:<24}  {:>10.3}  {:>10.3}", "Average path length", function.average_distance(), n.ln() / mean_degree.ln());
+    println!(
+        "{:<24}  {:>10.4}  {:>10.4}",
+        "Average clustering",
+        clustering.values().sum::<f64>() / n.max(1.0),
+        mean_degree / n.max(1.0)
+    );
+    println!("(random: an Erdős–Rényi graph with the same authors and mean degree {:.2})", mean_degree);
+    if let Some(weighted) = &weighted {
+        let component = largest_component_graph(weighted);
+        let average = stage!(
+            "weighted distances",
+            average_weighted_distance(&component, weights, (!exact).then_some(samples), seed)
+        );
+        print_weight_reading(weights);
+        println!(
+            "Average path length by edge length: {:.3}{}",
+            average,
+            if exact { String::new() } else { format!(" (from {} sampled authors)", samples) }
+        );
+        println!("Note: the distance distribution, clustering and the random graph count collaborations and ignore the edge weights.");
+    }
+    let mut writer = csv_output(output_path, args)?;
+    writer.write_record(["distance", "pairs", "fraction", "cumulative_fraction"]).map_err(|e| e.to_string())?;
+    for row in &rows {
+        writer.serialize(row).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    println!("Distance distribution written to {}", output_path);
+    Ok(())
 }
 
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
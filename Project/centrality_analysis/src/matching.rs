@@ -0,0 +1,44 @@
+use crate::AuthorGraph;
+use petgraph::algo;
+
+/// Maximum-cardinality matching of the collaboration graph, as `(author, author)` pairs
+/// with the smaller id first, sorted.
+///
+/// Uses petgraph's implementation of Gabow's blossom algorithm, so it works on general
+/// (non-bipartite) graphs.
+pub fn maximum_matching(graph: &AuthorGraph) -> Vec<(usize, usize)> {
+    let matching = algo::maximum_matching(graph);
+    let mut pairs: Vec<(usize, usize)> = matching
+        .edges()
+        .map(|(a, b)| {
+            let (a, b) = (graph[a], graph[b]);
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maximum_matching_through_blossom() {
+        // A 5-cycle 0..4 with a pendant author 5 on node 0: a greedy matching of the
+        // cycle can leave 5 unmatched, but a perfect matching exists.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|author| graph.add_node(author)).collect();
+        for i in 0..5 {
+            graph.add_edge(nodes[i], nodes[(i + 1) % 5], ());
+        }
+        graph.add_edge(nodes[0], nodes[5], ());
+
+        let pairs = maximum_matching(&graph);
+        assert_eq!(pairs.len(), 3);
+        let mut matched: Vec<usize> = pairs.iter().flat_map(|&(a, b)| [a, b]).collect();
+        matched.sort_unstable();
+        assert_eq!(matched, vec![0, 1, 2, 3, 4, 5]);
+        assert!(pairs.contains(&(0, 5)));
+    }
+}
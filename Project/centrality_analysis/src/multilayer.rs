@@ -0,0 +1,214 @@
+use crate::audit::AuditLog;
+use crate::{degree_centrality, load_graph_audited, AuthorGraph, LoadOptions};
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Undirected;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+
+/// The union of several layers, each edge tagged with the indices of the layers that
+/// contain it, in increasing order.
+pub type ProvenanceGraph = Graph<usize, Vec<usize>, Undirected>;
+
+/// Several edge types over the same authors, e.g. co-authorship and citation, one graph
+/// per layer. An author may be missing from some layers.
+#[derive(Debug, Clone)]
+pub struct Multilayer {
+    pub names: Vec<String>,
+    pub layers: Vec<AuthorGraph>,
+}
+
+impl Multilayer {
+    /// Load one edge list per layer. A spec is `name=path`, or just a path, in which case
+    /// the layer is named after the file stem. The limits in `options` apply per layer.
+    pub fn load(specs: &[&str], options: &LoadOptions) -> io::Result<Multilayer> {
+        Multilayer::load_audited(specs, options, &mut AuditLog::default())
+    }
+
+    /// Load the layers, recording the input dropped from all of them together in `log`.
+    pub fn load_audited(specs: &[&str], options: &LoadOptions, log: &mut AuditLog) -> io::Result<Multilayer> {
+        let mut names = Vec::with_capacity(specs.len());
+        let mut layers = Vec::with_capacity(specs.len());
+        for spec in specs {
+            let (name, path) = match spec.split_once('=') {
+                Some((name, path)) => (name.to_string(), path),
+                None => {
+                    let stem = Path::new(spec).file_stem().map(|s| s.to_string_lossy().into_owned());
+                    (stem.unwrap_or_else(|| spec.to_string()), *spec)
+                }
+            };
+            names.push(name);
+            layers.push(load_graph_audited(path, options, log)?);
+        }
+        Ok(Multilayer { names, layers })
+    }
+
+    /// Every author appearing in any layer, in order of first appearance.
+    pub fn authors(&self) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.node_weights().copied())
+            .filter(|&author| seen.insert(author))
+            .collect()
+    }
+
+    /// Union of all layers: two authors are linked if they are linked in any layer.
+    pub fn aggregate(&self) -> AuthorGraph {
+        self.provenance().map(|_, &author| author, |_, _| ())
+    }
+
+    /// Union of all layers remembering where each edge came from: authors in order of
+    /// first appearance, edges in order of their first layer.
+    pub fn provenance(&self) -> ProvenanceGraph {
+        let mut graph = ProvenanceGraph::default();
+        let index: HashMap<usize, NodeIndex> =
+            self.authors().into_iter().map(|author| (author, graph.add_node(author))).collect();
+        let mut edges = HashMap::new();
+        for (i, layer) in self.layers.iter().enumerate() {
+            for edge in layer.edge_references() {
+                let (a, b) = (layer[edge.source()], layer[edge.target()]);
+                let key = (a.min(b), a.max(b));
+                let edge = *edges.entry(key).or_insert_with(|| graph.add_edge(index[&a], index[&b], Vec::new()));
+                graph[edge].push(i);
+            }
+        }
+        graph
+    }
+
+    /// Union of the given layers only, without the authors that have no edge in them.
+    pub fn aggregate_of(&self, layers: &[usize]) -> AuthorGraph {
+        let provenance = self.provenance();
+        let selected = provenance.filter_map(
+            |_, &author| Some(author),
+            |_, sources| sources.iter().any(|source| layers.contains(source)).then_some(()),
+        );
+        selected.filter_map(|node, &author| selected.neighbors(node).next().map(|_| author), |_, _| Some(()))
+    }
+
+    /// Index of the layer with the given name.
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|layer| layer == name)
+    }
+
+    /// For every layer, its number of edges and how many of them appear in no other layer.
+    pub fn edge_counts(&self) -> Vec<(usize, usize)> {
+        let mut counts = vec![(0, 0); self.layers.len()];
+        for sources in self.provenance().edge_weights() {
+            for &source in sources {
+                counts[source].0 += 1;
+                if sources.len() == 1 {
+                    counts[source].1 += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Pearson correlation between the degrees in every pair of layers, over all authors
+    /// of the multilayer (an author missing from a layer has degree zero there).
+    pub fn degree_correlation(&self) -> Vec<Vec<f64>> {
+        let authors = self.authors();
+        let degrees: Vec<Vec<f64>> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let degree = degree_centrality(layer);
+                authors.iter().map(|author| degree.get(author).copied().unwrap_or(0) as f64).collect()
+            })
+            .collect();
+        degrees
+            .iter()
+            .map(|a| degrees.iter().map(|b| pearson(a, b)).collect())
+            .collect()
+    }
+}
+
+/// Pearson correlation coefficient; zero when either side is constant.
+pub fn pearson(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len().min(ys.len()) as f64;
+    if n == 0.0 {
+        return 0.0;
+    }
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        covariance += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x) * (x - mean_x);
+        var_y += (y - mean_y) * (y - mean_y);
+    }
+    if var_x <= 0.0 || var_y <= 0.0 {
+        return 0.0;
+    }
+    covariance / (var_x * var_y).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(edges: &[(usize, usize)]) -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let mut index = HashMap::new();
+        for &(a, b) in edges {
+            let a = *index.entry(a).or_insert_with(|| graph.add_node(a));
+            let b = *index.entry(b).or_insert_with(|| graph.add_node(b));
+            graph.add_edge(a, b, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_aggregate_and_degree_correlation() {
+        let multilayer = Multilayer {
+            names: vec!["coauthor".to_string(), "citation".to_string()],
+            layers: vec![layer(&[(1, 2), (2, 3)]), layer(&[(2, 3), (3, 4)])],
+        };
+        assert_eq!(multilayer.authors(), vec![1, 2, 3, 4]);
+
+        let aggregate = multilayer.aggregate();
+        assert_eq!(aggregate.node_count(), 4);
+        // The shared edge 2-3 appears once.
+        assert_eq!(aggregate.edge_count(), 3);
+
+        // Degrees [1, 2, 1, 0] and [0, 1, 2, 1].
+        let correlation = multilayer.degree_correlation();
+        assert!((correlation[0][0] - 1.0).abs() < 1e-12);
+        assert!((correlation[0][1] - 0.0).abs() < 1e-12);
+        assert_eq!(correlation[0][1], correlation[1][0]);
+    }
+
+    #[test]
+    fn test_provenance_and_filtering() {
+        let multilayer = Multilayer {
+            names: vec!["coauthor".to_string(), "citation".to_string()],
+            layers: vec![layer(&[(1, 2), (2, 3)]), layer(&[(3, 2), (3, 4), (4, 5)])],
+        };
+        let provenance = multilayer.provenance();
+        let tags: Vec<&Vec<usize>> = provenance.edge_weights().collect();
+        assert_eq!(tags, vec![&vec![0], &vec![0, 1], &vec![1], &vec![1]]);
+        assert_eq!(multilayer.edge_counts(), vec![(2, 1), (3, 2)]);
+
+        // Author 1 only collaborates in the co-authorship layer.
+        let citation = multilayer.aggregate_of(&[multilayer.layer_index("citation").unwrap()]);
+        assert_eq!(citation.node_weights().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+        assert_eq!(citation.edge_count(), 3);
+        assert_eq!(multilayer.aggregate_of(&[0, 1]).edge_count(), multilayer.aggregate().edge_count());
+    }
+
+    #[test]
+    fn test_load_names_layers() {
+        let path = "multilayer_test.txt";
+        std::fs::write(path, "1\t2\n2\t3\n3\t3\n2\t1\n").unwrap();
+        let mut log = AuditLog::default();
+        let multilayer = Multilayer::load_audited(&[path, "cites=multilayer_test.txt"], &LoadOptions::default(), &mut log);
+        let multilayer = multilayer.unwrap();
+        assert_eq!(multilayer.names, vec!["multilayer_test", "cites"]);
+        assert_eq!(multilayer.layers[1].edge_count(), 2);
+        // Both layers count: one self-loop and one repeat each.
+        assert_eq!(log.to_string(), "repeated edges merged: 2, self-loops dropped: 2");
+        std::fs::remove_file(path).unwrap();
+    }
+}
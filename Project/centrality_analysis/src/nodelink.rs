@@ -0,0 +1,174 @@
+use crate::audit::AuditLog;
+use crate::{AuthorGraph, DuplicatePolicy, LoadOptions, SelfLoopPolicy};
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// The node-link JSON format of `networkx.readwrite.json_graph`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeLinkData {
+    #[serde(default)]
+    pub directed: bool,
+    #[serde(default)]
+    pub multigraph: bool,
+    #[serde(default)]
+    pub graph: Map<String, Value>,
+    pub nodes: Vec<NodeLinkNode>,
+    /// Called `links` before networkx 3.4, which is accepted when reading.
+    #[serde(alias = "links")]
+    pub edges: Vec<NodeLinkEdge>,
+}
+
+/// A node; attributes other than `id` are ignored when reading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeLinkNode {
+    pub id: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeLinkEdge {
+    pub source: Value,
+    pub target: Value,
+}
+
+/// Node-link data of the graph, nodes and edges sorted by author id.
+pub fn node_link_data(graph: &AuthorGraph) -> NodeLinkData {
+    let mut authors: Vec<usize> = graph.node_weights().copied().collect();
+    authors.sort_unstable();
+    let mut edges: Vec<(usize, usize)> = graph
+        .edge_references()
+        .map(|edge| {
+            let (a, b) = (graph[edge.source()], graph[edge.target()]);
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    edges.sort_unstable();
+    NodeLinkData {
+        directed: false,
+        multigraph: false,
+        graph: Map::new(),
+        nodes: authors.into_iter().map(|author| NodeLinkNode { id: author.into() }).collect(),
+        edges: edges
+            .into_iter()
+            .map(|(a, b)| NodeLinkEdge { source: a.into(), target: b.into() })
+            .collect(),
+    }
+}
+
+/// Write the graph as node-link JSON, readable by `json_graph.node_link_graph`.
+pub fn write_node_link<P: AsRef<Path>>(graph: &AuthorGraph, path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, &node_link_data(graph))?;
+    writeln!(writer)?;
+    writer.flush()
+}
+
+/// Author id of a node-link id: a non-negative integer, or a string holding one.
+fn author_id(id: &Value) -> io::Result<usize> {
+    let parsed = match id {
+        Value::Number(number) => number.as_u64().and_then(|id| usize::try_from(id).ok()),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    };
+    parsed.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("node id {} is not an author id", id)))
+}
+
+/// Build a collaboration graph from node-link data. Listed nodes are added even without
+/// edges; like `load_graph`, self-loops and repeated edges (including parallel edges of a
+/// multigraph and both directions of a directed graph) are handled as `options` says and
+/// counted in `log`.
+pub fn graph_from_node_link(data: &NodeLinkData, options: &LoadOptions, log: &mut AuditLog) -> io::Result<AuthorGraph> {
+    let limit_error = |what: &str, limit: usize| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("graph has more than {} {} (the configured limit)", limit, what),
+        )
+    };
+    let mut graph = AuthorGraph::new_undirected();
+    let mut node_map = HashMap::new();
+    let mut add_node = |graph: &mut AuthorGraph, author: usize| {
+        let index = *node_map.entry(author).or_insert_with(|| graph.add_node(author));
+        match options.max_nodes {
+            Some(max) if graph.node_count() > max => Err(limit_error("nodes", max)),
+            _ => Ok(index),
+        }
+    };
+    for node in &data.nodes {
+        add_node(&mut graph, author_id(&node.id)?)?;
+    }
+    let mut seen = HashSet::new();
+    let (mut repeated, mut self_loops) = (0, 0);
+    for edge in &data.edges {
+        let (from, to) = (author_id(&edge.source)?, author_id(&edge.target)?);
+        if from == to {
+            self_loops += 1;
+            if options.self_loops == SelfLoopPolicy::Drop {
+                continue;
+            }
+        }
+        if !seen.insert((from.min(to), from.max(to))) {
+            repeated += 1;
+            if options.duplicates != DuplicatePolicy::Keep {
+                continue;
+            }
+        }
+        if let Some(max) = options.max_edges.filter(|&max| graph.edge_count() >= max) {
+            return Err(limit_error("edges", max));
+        }
+        let (from, to) = (add_node(&mut graph, from)?, add_node(&mut graph, to)?);
+        graph.add_edge(from, to, ());
+    }
+    // Without weights there is nothing to aggregate into, so aggregating merges.
+    let merged = if options.duplicates == DuplicatePolicy::Keep { "repeated edges kept" } else { "repeated edges merged" };
+    log.record(merged, repeated);
+    log.record(options.self_loops.operation(), self_loops);
+    Ok(graph)
+}
+
+/// Load a node-link JSON file as written by `json_graph.node_link_data`.
+pub fn load_node_link<P: AsRef<Path>>(path: P, options: &LoadOptions, log: &mut AuditLog) -> io::Result<AuthorGraph> {
+    let data: NodeLinkData = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    graph_from_node_link(&data, options, log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph_from_reader;
+
+    #[test]
+    fn test_node_link_round_trip() {
+        let graph = load_graph_from_reader("3 1\n1 2\n2 3\n3 4\n".as_bytes()).unwrap();
+        let json = serde_json::to_string(&node_link_data(&graph)).unwrap();
+        assert!(json.starts_with(r#"{"directed":false,"multigraph":false,"graph":{},"nodes":[{"id":1},"#));
+        assert!(json.ends_with(r#""edges":[{"source":1,"target":2},{"source":1,"target":3},{"source":2,"target":3},{"source":3,"target":4}]}"#));
+
+        let data: NodeLinkData = serde_json::from_str(&json).unwrap();
+        let loaded = graph_from_node_link(&data, &LoadOptions::default(), &mut AuditLog::default()).unwrap();
+        assert_eq!(node_link_data(&loaded), node_link_data(&graph));
+    }
+
+    #[test]
+    fn test_read_networkx_output() {
+        // networkx < 3.4 writes "links"; ids may be strings; extra attributes are ignored.
+        let json = r#"{"directed": true, "multigraph": false, "graph": {"name": "toy"},
+            "nodes": [{"id": "7", "label": "x"}, {"id": 8}, {"id": 9}],
+            "links": [{"source": "7", "target": 8, "weight": 2}, {"source": 8, "target": "7"}]}"#;
+        let data: NodeLinkData = serde_json::from_str(json).unwrap();
+        let graph = graph_from_node_link(&data, &LoadOptions::default(), &mut AuditLog::default()).unwrap();
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 1));
+
+        let limited = LoadOptions { max_nodes: Some(2), ..LoadOptions::default() };
+        assert!(graph_from_node_link(&data, &limited, &mut AuditLog::default()).is_err());
+        let named: NodeLinkData = serde_json::from_str(r#"{"nodes": [{"id": "alice"}], "edges": []}"#).unwrap();
+        assert!(graph_from_node_link(&named, &LoadOptions::default(), &mut AuditLog::default()).is_err());
+    }
+}
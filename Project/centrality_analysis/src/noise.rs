@@ -0,0 +1,180 @@
+use crate::audit::AuditLog;
+use crate::{top_k, AuthorGraph};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// Swap attempts per requested swap before giving up, for graphs where few swaps are
+/// possible (such as stars and cliques).
+const ATTEMPTS_PER_SWAP: usize = 100;
+
+/// Perturb the graph by degree-preserving double edge swaps: two collaborations `a-b`
+/// and `c-d` become `a-d` and `c-b`, unless that would create a self-loop or a repeated
+/// edge. Swaps continue until about `fraction` of the edges have been removed and as many
+/// added, so every author keeps their degree while the structure around them changes.
+/// The edges actually removed and added are recorded in `log`; authors keep their node
+/// indices.
+pub fn rewire(graph: &AuthorGraph, fraction: f64, seed: u64, log: &mut AuditLog) -> AuthorGraph {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let key = |a: NodeIndex, b: NodeIndex| (a.min(b), a.max(b));
+    let mut edges: Vec<(NodeIndex, NodeIndex)> = graph.edge_references().map(|edge| (edge.source(), edge.target())).collect();
+    let original: HashSet<_> = edges.iter().map(|&(a, b)| key(a, b)).collect();
+    let mut present = original.clone();
+
+    let swaps = (fraction * edges.len() as f64 / 2.0).ceil() as usize;
+    let (mut done, mut attempts) = (0, 0);
+    while done < swaps && edges.len() >= 2 && attempts < swaps * ATTEMPTS_PER_SWAP {
+        attempts += 1;
+        let (first, second) = (rng.gen_range(0..edges.len()), rng.gen_range(0..edges.len()));
+        let (a, b) = edges[first];
+        let (c, d) = if rng.gen_bool(0.5) { edges[second] } else { (edges[second].1, edges[second].0) };
+        if first == second || a == d || c == b || present.contains(&key(a, d)) || present.contains(&key(c, b)) {
+            continue;
+        }
+        present.remove(&key(a, b));
+        present.remove(&key(c, d));
+        present.insert(key(a, d));
+        present.insert(key(c, b));
+        edges[first] = (a, d);
+        edges[second] = (c, b);
+        done += 1;
+    }
+
+    let mut rewired = graph.clone();
+    rewired.clear_edges();
+    for &(a, b) in &edges {
+        rewired.add_edge(a, b, ());
+    }
+    let removed = original.difference(&present).count();
+    log.record("edges removed by rewiring", removed);
+    log.record("edges added by rewiring", removed);
+    rewired
+}
+
+/// How much a ranking changed under perturbation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankStability {
+    /// Kendall's tau-b between the scores of the authors in both rankings: 1 when the
+    /// order is unchanged, 0 when unrelated.
+    pub kendall_tau: f64,
+    /// Fraction of the top `k` authors that are still in the top `k`.
+    pub top_k_overlap: f64,
+}
+
+/// Compare the scores of the same authors before and after perturbation.
+pub fn rank_stability(original: &HashMap<usize, f64>, perturbed: &HashMap<usize, f64>, k: usize) -> RankStability {
+    let pairs: Vec<(f64, f64)> = original
+        .iter()
+        .filter_map(|(author, &score)| perturbed.get(author).map(|&other| (score, other)))
+        .collect();
+    let before: HashSet<usize> = top_k(original, k).into_iter().map(|(author, _)| author).collect();
+    let after = top_k(perturbed, k);
+    let kept = after.iter().filter(|(author, _)| before.contains(author)).count();
+    RankStability { kendall_tau: kendall_tau(pairs), top_k_overlap: kept as f64 / before.len().max(1) as f64 }
+}
+
+/// Kendall's tau-b, in O(n log n) by Knight's algorithm: sort by the first score, then
+/// count the discordant pairs as the swaps a merge sort by the second score performs.
+pub fn kendall_tau(mut pairs: Vec<(f64, f64)>) -> f64 {
+    let n = pairs.len() as u64;
+    let total = n * n.saturating_sub(1) / 2;
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let first_ties = tied_pairs(&pairs, |a, b| a.0 == b.0);
+    let joint_ties = tied_pairs(&pairs, |a, b| a == b);
+
+    let mut second: Vec<f64> = pairs.iter().map(|pair| pair.1).collect();
+    let mut buffer = second.clone();
+    let discordant = merge_count(&mut second, &mut buffer);
+    let second_ties = tied_pairs(&second, |a, b| a == b);
+
+    let denominator = (((total - first_ties) as f64) * ((total - second_ties) as f64)).sqrt();
+    if denominator == 0.0 {
+        return 1.0;
+    }
+    let concordant_minus_discordant =
+        total as f64 - first_ties as f64 - second_ties as f64 + joint_ties as f64 - 2.0 * discordant as f64;
+    concordant_minus_discordant / denominator
+}
+
+/// Pairs within runs of equal neighbours in a sorted slice.
+fn tied_pairs<T>(sorted: &[T], equal: impl Fn(&T, &T) -> bool) -> u64 {
+    let (mut pairs, mut run) = (0, 1u64);
+    for window in sorted.windows(2) {
+        if equal(&window[0], &window[1]) {
+            run += 1;
+        } else {
+            pairs += run * (run - 1) / 2;
+            run = 1;
+        }
+    }
+    pairs + run * (run - 1) / 2
+}
+
+/// Sort `values` by merge sort, returning the number of strictly inverted pairs.
+fn merge_count(values: &mut [f64], buffer: &mut [f64]) -> u64 {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let middle = n / 2;
+    let mut swaps = merge_count(&mut values[..middle], &mut buffer[..middle]);
+    swaps += merge_count(&mut values[middle..], &mut buffer[middle..]);
+    let (mut i, mut j) = (0, middle);
+    for slot in buffer[..n].iter_mut() {
+        if j >= n || (i < middle && values[i] <= values[j]) {
+            *slot = values[i];
+            i += 1;
+        } else {
+            *slot = values[j];
+            swaps += (middle - i) as u64;
+            j += 1;
+        }
+    }
+    values.copy_from_slice(&buffer[..n]);
+    swaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{degree_centrality, load_graph_from_reader};
+
+    #[test]
+    fn test_rewire_preserves_degrees() {
+        let mut edges = String::new();
+        for i in 0..40 {
+            edges.push_str(&format!("{} {}\n{} {}\n", i, (i + 1) % 40, i, (i + 7) % 40));
+        }
+        let graph = load_graph_from_reader(edges.as_bytes()).unwrap();
+        let mut log = AuditLog::default();
+        let rewired = rewire(&graph, 0.2, 3, &mut log);
+        assert_eq!(degree_centrality(&rewired), degree_centrality(&graph));
+        assert_eq!(rewired.edge_count(), graph.edge_count());
+        let removed = log.entries[0].count;
+        assert!((12..=32).contains(&removed), "{}", log);
+        assert!(rewired.edge_references().all(|edge| edge.source() != edge.target()));
+
+        // A star has no valid swap.
+        let star = load_graph_from_reader("0 1\n0 2\n0 3\n".as_bytes()).unwrap();
+        let mut log = AuditLog::default();
+        assert_eq!(rewire(&star, 0.5, 1, &mut log).edge_count(), 3);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_kendall_tau_with_ties() {
+        // 22 concordant and 2 discordant pairs; x ties in 2 further pairs, y in 2.
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0, 5.0, 6.0];
+        let y = [2.0, 1.0, 3.0, 3.0, 5.0, 4.0, 6.0, 6.0];
+        let tau = kendall_tau(x.iter().copied().zip(y.iter().copied()).collect());
+        assert!((tau - 10.0 / 13.0).abs() < 1e-12, "{}", tau);
+        assert_eq!(kendall_tau(vec![(1.0, 3.0), (2.0, 2.0), (3.0, 1.0)]), -1.0);
+
+        let scores = |values: &[f64]| values.iter().copied().enumerate().collect::<HashMap<_, _>>();
+        let stability = rank_stability(&scores(&[0.9, 0.5, 0.1, 0.3]), &scores(&[0.4, 0.8, 0.1, 0.3]), 2);
+        assert_eq!(stability.top_k_overlap, 1.0);
+        assert!((stability.kendall_tau - 2.0 / 3.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::AuthorGraph;
+use petgraph::visit::EdgeRef;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Canonical form of an edge list: every edge once as `(smaller, larger)`, sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalEdges {
+    pub edges: Vec<(usize, usize)>,
+    /// With dense ids, `original[i]` is the author renumbered to `i`, in increasing order.
+    pub original: Option<Vec<usize>>,
+}
+
+/// Canonical edges of the graph, optionally renumbered to dense ids `0..n` that keep the
+/// order of the original ids. Equal graphs give equal output however the input was ordered.
+pub fn canonical_edges(graph: &AuthorGraph, dense: bool) -> CanonicalEdges {
+    let mut edges: Vec<(usize, usize)> = graph
+        .edge_references()
+        .map(|edge| {
+            let (a, b) = (graph[edge.source()], graph[edge.target()]);
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    let original = dense.then(|| {
+        let mut authors: Vec<usize> = graph.node_weights().copied().collect();
+        authors.sort_unstable();
+        for (a, b) in edges.iter_mut() {
+            *a = authors.binary_search(a).expect("edge endpoints are nodes");
+            *b = authors.binary_search(b).expect("edge endpoints are nodes");
+        }
+        authors
+    });
+    edges.sort_unstable();
+    CanonicalEdges { edges, original }
+}
+
+/// Write the edges as tab-separated lines without comments.
+pub fn write_canonical<P: AsRef<Path>>(canonical: &CanonicalEdges, path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    for (a, b) in &canonical.edges {
+        writeln!(writer, "{}\t{}", a, b)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph_from_reader;
+
+    #[test]
+    fn test_canonical_edges_ignore_order_and_duplicates() {
+        let messy = "# comment\n30 10\n10\t20\n20 30\n30 10\n7 7\n";
+        let tidy = "10 20\n10 30\n20 30\n";
+        let graph = load_graph_from_reader(messy.as_bytes()).unwrap();
+        let canonical = canonical_edges(&graph, false);
+        assert_eq!(canonical.edges, vec![(10, 20), (10, 30), (20, 30)]);
+        assert_eq!(canonical, canonical_edges(&load_graph_from_reader(tidy.as_bytes()).unwrap(), false));
+
+        let dense = canonical_edges(&graph, true);
+        assert_eq!(dense.edges, vec![(0, 1), (0, 2), (1, 2)]);
+        assert_eq!(dense.original, Some(vec![10, 20, 30]));
+    }
+}
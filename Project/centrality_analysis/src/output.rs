@@ -0,0 +1,170 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// What writing an output does when the file is already there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExistingOutput {
+    /// Fail with `AlreadyExists`, leaving the file as it is.
+    #[default]
+    Refuse,
+    Overwrite,
+    /// Add to the end of tables and text files; outputs that cannot be extended, such as
+    /// images, are refused by [`ExistingOutput::prepare_whole`].
+    Append,
+}
+
+impl ExistingOutput {
+    /// Make `path` ready to be written: create its missing parent directories and apply
+    /// the policy. Returns whether the file exists and is to be appended to.
+    pub fn prepare<P: AsRef<Path>>(self, path: P) -> io::Result<bool> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        match (self, path.exists()) {
+            (_, false) | (ExistingOutput::Overwrite, true) => Ok(false),
+            (ExistingOutput::Append, true) => Ok(true),
+            (ExistingOutput::Refuse, true) => {
+                Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", path.display())))
+            }
+        }
+    }
+
+    /// Like [`ExistingOutput::prepare`], for outputs that can only be written whole, such
+    /// as images: appending to an existing one is an `InvalidInput` error instead of a
+    /// silent replacement.
+    pub fn prepare_whole<P: AsRef<Path>>(self, path: P) -> io::Result<()> {
+        if self.prepare(&path)? {
+            let message = format!("{} cannot be appended to", path.as_ref().display());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+        Ok(())
+    }
+
+    /// Open `path` for writing, at its end when appending.
+    pub fn create<P: AsRef<Path>>(self, path: P) -> io::Result<File> {
+        let append = self.prepare(&path)?;
+        open(path.as_ref(), append)
+    }
+
+    /// A CSV writer for `path`. When appending to a file that already has rows, the first
+    /// line written, the header, is left out, so that the new rows line up under the old
+    /// header; the table must have the same columns.
+    pub fn csv_writer<P: AsRef<Path>>(self, path: P) -> io::Result<csv::Writer<OutputFile>> {
+        let append = self.prepare(&path)?;
+        let skip_line = append && fs::metadata(&path)?.len() > 0;
+        let file = open(path.as_ref(), append)?;
+        Ok(csv::Writer::from_writer(OutputFile { file, skip_line }))
+    }
+}
+
+fn open(path: &Path, append: bool) -> io::Result<File> {
+    OpenOptions::new().write(true).create(true).append(append).truncate(!append).open(path)
+}
+
+/// The file behind [`ExistingOutput::csv_writer`], which drops the first line written to
+/// it when appending.
+#[derive(Debug)]
+pub struct OutputFile {
+    file: File,
+    skip_line: bool,
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.skip_line {
+            return self.file.write(buf);
+        }
+        if let Some(end) = buf.iter().position(|&byte| byte == b'\n') {
+            self.skip_line = false;
+            self.file.write_all(&buf[end + 1..])?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Longest file name `sanitize_file_name` returns, in bytes; well below the 255 that
+/// common file systems allow, leaving room for the rest of a Windows path.
+pub const MAX_FILE_NAME: usize = 100;
+
+/// A file name made from `name` that is valid on every platform: path separators, the
+/// characters Windows forbids and control characters become `_`, trailing dots and
+/// spaces go, reserved device names such as `CON` or `com1.csv` get a `_` prefix, and
+/// the name is cut to [`MAX_FILE_NAME`] bytes, keeping its extension.
+pub fn sanitize_file_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { '_' } else { c })
+        .collect();
+    let mut sanitized = replaced.trim_end_matches(['.', ' ']).to_string();
+    if sanitized.len() > MAX_FILE_NAME {
+        let extension = Path::new(&sanitized).extension().map_or(0, |extension| extension.len() + 1);
+        let extension = if extension < MAX_FILE_NAME / 2 { extension } else { 0 };
+        let mut cut = MAX_FILE_NAME - extension;
+        while !sanitized.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        sanitized.replace_range(cut..sanitized.len() - extension, "");
+    }
+    let stem = sanitized.split('.').next().unwrap_or_default().trim_end().to_ascii_uppercase();
+    let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || (stem.len() == 4
+            && (stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.as_bytes()[3].is_ascii_digit());
+    if reserved || sanitized.is_empty() {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_existing_output_policies() {
+        let dir = Path::new("test_output_policies");
+        let path = dir.join("nested").join("table.csv");
+        let write = |policy: ExistingOutput, rows: &[[&str; 2]]| -> io::Result<()> {
+            let mut writer = policy.csv_writer(&path)?;
+            writer.write_record(["author", "score"])?;
+            rows.iter().try_for_each(|row| writer.write_record(row))?;
+            writer.flush()
+        };
+        write(ExistingOutput::Refuse, &[["1", "0.5"]]).unwrap();
+        let error = write(ExistingOutput::Refuse, &[["2", "0.25"]]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        write(ExistingOutput::Append, &[["2", "0.25"], ["3", "0.125"]]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "author,score\n1,0.5\n2,0.25\n3,0.125\n");
+        write(ExistingOutput::Overwrite, &[["4", "1"]]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "author,score\n4,1\n");
+
+        let text = dir.join("members.txt");
+        ExistingOutput::Refuse.create(&text).unwrap().write_all(b"1\n").unwrap();
+        ExistingOutput::Append.create(&text).unwrap().write_all(b"2\n").unwrap();
+        assert_eq!(fs::read_to_string(&text).unwrap(), "1\n2\n");
+        let error = ExistingOutput::Append.prepare_whole(&text).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        ExistingOutput::Append.prepare_whole(dir.join("plot.png")).unwrap();
+        ExistingOutput::Overwrite.prepare_whole(&text).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("top_betweenness.png"), "top_betweenness.png");
+        assert_eq!(sanitize_file_name("ca-GrQc: run 2/3?.csv"), "ca-GrQc_ run 2_3_.csv");
+        assert_eq!(sanitize_file_name("results. "), "results");
+        assert_eq!(sanitize_file_name("con.csv"), "_con.csv");
+        assert_eq!(sanitize_file_name("COM1"), "_COM1");
+        assert_eq!(sanitize_file_name("computer.csv"), "computer.csv");
+        assert_eq!(sanitize_file_name(""), "_");
+        let long = sanitize_file_name(&format!("{}.csv", "é".repeat(80)));
+        assert!(long.len() <= MAX_FILE_NAME && long.ends_with("é.csv"));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::AuthorGraph;
+use petgraph::graph::Graph;
+use petgraph::EdgeType;
+use std::collections::HashMap;
+
+/// Power iterations after which PageRank stops even without converging.
+const MAX_ITERATIONS: usize = 1000;
+/// Convergence threshold on the L1 change between iterations.
+const TOLERANCE: f64 = 1e-10;
+
+/// Settings of the PageRank power iteration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRankOptions {
+    /// Probability that the random surfer follows a collaboration rather than restarting.
+    pub damping: f64,
+    pub max_iterations: usize,
+    /// The iteration stops once the L1 change between iterations falls below this.
+    pub tolerance: f64,
+}
+
+impl Default for PageRankOptions {
+    fn default() -> PageRankOptions {
+        PageRankOptions { damping: 0.85, max_iterations: MAX_ITERATIONS, tolerance: TOLERANCE }
+    }
+}
+
+impl PageRankOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..1.0).contains(&self.damping) {
+            return Err(format!("Damping factor must be in [0, 1), got {}", self.damping));
+        }
+        if self.max_iterations == 0 {
+            return Err("The maximum number of iterations must be positive".to_string());
+        }
+        if self.tolerance.is_nan() || self.tolerance <= 0.0 {
+            return Err(format!("Tolerance must be positive, got {}", self.tolerance));
+        }
+        Ok(())
+    }
+}
+
+/// PageRank scores and how the power iteration ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRank {
+    pub scores: HashMap<usize, f64>,
+    pub iterations: usize,
+    /// L1 change in the last iteration; below the tolerance if the iteration converged.
+    pub residual: f64,
+}
+
+/// PageRank scores (summing to one) with the given damping factor and the default
+/// iteration limits.
+pub fn pagerank(graph: &AuthorGraph, damping: f64, teleport: Option<&HashMap<usize, f64>>) -> HashMap<usize, f64> {
+    pagerank_with(graph, &PageRankOptions { damping, ..PageRankOptions::default() }, teleport).scores
+}
+
+/// PageRank with the given options.
+///
+/// With a `teleport` vector the random surfer restarts at an author with probability
+/// proportional to their value (personalized PageRank), e.g. publication counts.
+/// Authors missing from it never receive teleports; without one, or if it has no
+/// positive mass, restarts are uniform. Dangling authors restart the same way.
+///
+/// In a directed graph the surfer follows edges in their direction only, and nodes
+/// without outgoing edges are the dangling ones, as in networkx's `pagerank`.
+pub fn pagerank_with<Ty: EdgeType>(
+    graph: &Graph<usize, (), Ty>,
+    options: &PageRankOptions,
+    teleport: Option<&HashMap<usize, f64>>,
+) -> PageRank {
+    let n = graph.node_count();
+    if n == 0 {
+        return PageRank { scores: HashMap::new(), iterations: 0, residual: 0.0 };
+    }
+    let damping = options.damping;
+    let mut restart: Vec<f64> = graph
+        .node_indices()
+        .map(|node| match teleport {
+            Some(values) => values.get(&graph[node]).copied().unwrap_or(0.0).max(0.0),
+            None => 1.0,
+        })
+        .collect();
+    let mass: f64 = restart.iter().sum();
+    if mass > 0.0 {
+        restart.iter_mut().for_each(|v| *v /= mass);
+    } else {
+        restart.iter_mut().for_each(|v| *v = 1.0 / n as f64);
+    }
+
+    let degree: Vec<usize> = graph.node_indices().map(|node| graph.neighbors(node).count()).collect();
+    let mut rank = restart.clone();
+    let (mut iterations, mut residual) = (0, f64::INFINITY);
+    while iterations < options.max_iterations {
+        let dangling: f64 = rank.iter().zip(&degree).filter(|(_, &d)| d == 0).map(|(r, _)| r).sum();
+        let mut next: Vec<f64> = restart.iter().map(|r| (1.0 - damping + damping * dangling) * r).collect();
+        for node in graph.node_indices() {
+            let share = damping * rank[node.index()] / degree[node.index()].max(1) as f64;
+            for other in graph.neighbors(node) {
+                next[other.index()] += share;
+            }
+        }
+        residual = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        iterations += 1;
+        if residual < options.tolerance {
+            break;
+        }
+    }
+    let scores = graph.node_indices().map(|node| (graph[node], rank[node.index()])).collect();
+    PageRank { scores, iterations, residual }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Star with hub 0 and leaves 1..=3, plus the edge 3–4.
+    fn star_with_tail() -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for leaf in 1..=3 {
+            graph.add_edge(nodes[0], nodes[leaf], ());
+        }
+        graph.add_edge(nodes[3], nodes[4], ());
+        graph
+    }
+
+    #[test]
+    fn test_pagerank_matches_networkx() {
+        // networkx.pagerank(G, alpha=0.85)
+        let scores = pagerank(&star_with_tail(), 0.85, None);
+        let expected = [0.3575578, 0.1313080, 0.1313080, 0.2454920, 0.1343341];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((scores[&author] - value).abs() < 1e-6, "{}: {}", author, scores[&author]);
+        }
+    }
+
+    #[test]
+    fn test_directed_pagerank_matches_networkx() {
+        // The cycle 0 -> 1 -> 2 -> 0 leaking into 3 -> 4, where the surfer dangles.
+        let input = "0 1\n1 2\n2 0\n2 3\n3 4\n1 4\n";
+        let graph = crate::load_directed_graph_from_reader_audited(
+            input.as_bytes(),
+            &Default::default(),
+            &mut Default::default(),
+        )
+        .unwrap();
+        let scores = pagerank_with(&graph, &PageRankOptions::default(), None).scores;
+        let expected = [0.1549864, 0.2134456, 0.1724216, 0.1549864, 0.30416];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((scores[&author] - value).abs() < 1e-6, "{}: {}", author, scores[&author]);
+        }
+    }
+
+    #[test]
+    fn test_personalized_pagerank_follows_teleport() {
+        let graph = star_with_tail();
+        let teleport: HashMap<usize, f64> = [(4, 10.0)].into_iter().collect();
+        let scores = pagerank(&graph, 0.85, Some(&teleport));
+        // networkx.pagerank(G, alpha=0.85, personalization={4: 10})
+        let expected = [0.2572166, 0.0728780, 0.0728780, 0.3137034, 0.2833239];
+        for (author, value) in expected.iter().enumerate() {
+            assert!((scores[&author] - value).abs() < 1e-6, "{}: {}", author, scores[&author]);
+        }
+        // Without any positive mass the restarts fall back to uniform.
+        let empty: HashMap<usize, f64> = [(1, 0.0)].into_iter().collect();
+        assert_eq!(pagerank(&graph, 0.85, Some(&empty)), pagerank(&graph, 0.85, None));
+    }
+
+    #[test]
+    fn test_pagerank_options() {
+        let graph = star_with_tail();
+        let converged = pagerank_with(&graph, &PageRankOptions::default(), None);
+        assert!(converged.residual < TOLERANCE && converged.iterations < MAX_ITERATIONS);
+        assert_eq!(converged.scores, pagerank(&graph, 0.85, None));
+
+        let options = PageRankOptions { max_iterations: 2, ..PageRankOptions::default() };
+        let stopped = pagerank_with(&graph, &options, None);
+        assert_eq!(stopped.iterations, 2);
+        assert!(stopped.residual > TOLERANCE);
+
+        assert!(PageRankOptions { damping: 1.0, ..options }.validate().is_err());
+        assert!(PageRankOptions { tolerance: 0.0, ..options }.validate().is_err());
+        assert!(options.validate().is_ok());
+    }
+}
@@ -0,0 +1,369 @@
+use crate::betweenness::Tentative;
+use crate::{AuthorGraph, EdgeWeights, WeightedAuthorGraph};
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Undirected;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+/// Every simple path (no author visited twice) of at most `max_length` collaborations
+/// from `source` to `target` that avoids the `excluded` authors, as author ids, shortest
+/// first. The number of paths grows exponentially with the length cap, so enumeration
+/// stops after `limit` paths have been found; the second value tells whether it did.
+pub fn simple_paths(
+    graph: &AuthorGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    max_length: usize,
+    excluded: &[NodeIndex],
+    limit: usize,
+) -> (Vec<Vec<usize>>, bool) {
+    let (paths, truncated) = simple_node_paths(graph, source, target, max_length, excluded, limit);
+    let mut paths: Vec<Vec<usize>> = paths.iter().map(|path| authors(graph, path)).collect();
+    paths.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    (paths, truncated)
+}
+
+/// The paths of [`simple_paths`] in a weighted graph, each with its length over edge
+/// lengths, the weights read as `weights` says, shortest first; `max_length` still caps
+/// the number of collaborations.
+pub fn weighted_simple_paths(
+    graph: &WeightedAuthorGraph,
+    weights: EdgeWeights,
+    source: NodeIndex,
+    target: NodeIndex,
+    max_length: usize,
+    excluded: &[NodeIndex],
+    limit: usize,
+) -> (Vec<(f64, Vec<usize>)>, bool) {
+    let lengths = weights.lengths(graph);
+    let (paths, truncated) = simple_node_paths(graph, source, target, max_length, excluded, limit);
+    let mut paths: Vec<(f64, Vec<usize>)> =
+        paths.iter().map(|path| (path_length(&lengths, path), authors(graph, path))).collect();
+    paths.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.len().cmp(&b.1.len())).then_with(|| a.1.cmp(&b.1)));
+    (paths, truncated)
+}
+
+/// The search of [`simple_paths`], as node indices in the order they are found.
+fn simple_node_paths<E>(
+    graph: &Graph<usize, E, Undirected>,
+    source: NodeIndex,
+    target: NodeIndex,
+    max_length: usize,
+    excluded: &[NodeIndex],
+    limit: usize,
+) -> (Vec<Vec<NodeIndex>>, bool) {
+    let mut blocked = vec![false; graph.node_count()];
+    excluded.iter().for_each(|node| blocked[node.index()] = true);
+    let mut paths = Vec::new();
+    if blocked[source.index()] || blocked[target.index()] || source == target || max_length == 0 {
+        return (paths, false);
+    }
+
+    // Depth-first search with one neighbour iterator per author on the current path.
+    let mut path = vec![source];
+    blocked[source.index()] = true;
+    let mut stack = vec![graph.neighbors(source)];
+    let mut truncated = false;
+    while let Some(neighbours) = stack.last_mut() {
+        match neighbours.next() {
+            Some(next) if next == target => {
+                if paths.len() == limit {
+                    truncated = true;
+                    break;
+                }
+                paths.push(path.iter().chain([&target]).copied().collect());
+            }
+            Some(next) if !blocked[next.index()] && path.len() < max_length => {
+                blocked[next.index()] = true;
+                path.push(next);
+                stack.push(graph.neighbors(next));
+            }
+            Some(_) => {}
+            None => {
+                stack.pop();
+                if let Some(last) = path.pop() {
+                    blocked[last.index()] = false;
+                }
+            }
+        }
+    }
+    (paths, truncated)
+}
+
+/// The `k` shortest simple paths from `source` to `target` that avoid the `excluded`
+/// authors, as author ids, by Yen's algorithm: each new path branches off one of the
+/// paths found so far at some author, leaving by a collaboration none of the paths with
+/// the same beginning took. Paths of equal length are ordered by their node indices.
+pub fn k_shortest_paths(
+    graph: &AuthorGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    k: usize,
+    excluded: &[NodeIndex],
+) -> Vec<Vec<usize>> {
+    let shortest = |from, blocked: &[bool], removed: &HashSet<_>| shortest_path(graph, from, target, blocked, removed);
+    let hops = |path: &[NodeIndex]| (path.len() - 1) as f64;
+    let paths = yen(graph.node_count(), source, k, excluded, shortest, hops);
+    paths.iter().map(|(_, path)| authors(graph, path)).collect()
+}
+
+/// The `k` shortest simple paths of [`k_shortest_paths`] over edge lengths, the weights
+/// read as `weights` says, each with its length.
+pub fn weighted_k_shortest_paths(
+    graph: &WeightedAuthorGraph,
+    weights: EdgeWeights,
+    source: NodeIndex,
+    target: NodeIndex,
+    k: usize,
+    excluded: &[NodeIndex],
+) -> Vec<(f64, Vec<usize>)> {
+    let lengths = weights.lengths(graph);
+    let shortest =
+        |from, blocked: &[bool], removed: &HashSet<_>| shortest_weighted_path(&lengths, from, target, blocked, removed);
+    let paths = yen(graph.node_count(), source, k, excluded, shortest, |path| path_length(&lengths, path));
+    paths.into_iter().map(|(length, path)| (length, authors(graph, &path))).collect()
+}
+
+/// Yen's algorithm: `shortest` finds a path from an author to the target avoiding blocked
+/// authors and removed collaborations, and `length` measures a path. Returns up to `k`
+/// paths with their lengths, ties ordered by node indices.
+fn yen(
+    node_count: usize,
+    source: NodeIndex,
+    k: usize,
+    excluded: &[NodeIndex],
+    shortest: impl Fn(NodeIndex, &[bool], &HashSet<(NodeIndex, NodeIndex)>) -> Option<Vec<NodeIndex>>,
+    length: impl Fn(&[NodeIndex]) -> f64,
+) -> Vec<(f64, Vec<NodeIndex>)> {
+    let mut blocked = vec![false; node_count];
+    excluded.iter().for_each(|node| blocked[node.index()] = true);
+    let mut found: Vec<(f64, Vec<NodeIndex>)> = Vec::new();
+    let Some(first) = shortest(source, &blocked, &HashSet::new()) else {
+        return Vec::new();
+    };
+    found.push((length(&first), first));
+
+    let mut candidates: Vec<(f64, Vec<NodeIndex>)> = Vec::new();
+    while found.len() < k {
+        let last = found.last().unwrap().1.clone();
+        for spur in 0..last.len() - 1 {
+            let root = &last[..=spur];
+            let removed: HashSet<(NodeIndex, NodeIndex)> = found
+                .iter()
+                .map(|(_, path)| path)
+                .filter(|path| path.len() > spur + 1 && path[..=spur] == *root)
+                .map(|path| edge_key(path[spur], path[spur + 1]))
+                .collect();
+            root[..spur].iter().for_each(|node| blocked[node.index()] = true);
+            if let Some(tail) = shortest(last[spur], &blocked, &removed) {
+                let path: Vec<NodeIndex> = root[..spur].iter().chain(&tail).copied().collect();
+                if !found.iter().chain(&candidates).any(|(_, other)| *other == path) {
+                    candidates.push((length(&path), path));
+                }
+            }
+            root[..spur].iter().for_each(|node| blocked[node.index()] = false);
+        }
+        let best = (0..candidates.len()).min_by(|&a, &b| {
+            let (a, b) = (&candidates[a], &candidates[b]);
+            a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1))
+        });
+        match best {
+            Some(best) => found.push(candidates.swap_remove(best)),
+            None => break,
+        }
+    }
+    found
+}
+
+/// The authors on the given paths (author ids) and their collaborators, with every
+/// collaboration among them: the ego neighbourhoods of a path query, small enough to
+/// draw legibly.
+pub fn ego_subgraph(graph: &AuthorGraph, paths: &[Vec<usize>]) -> AuthorGraph {
+    let on_path: HashSet<usize> = paths.iter().flatten().copied().collect();
+    let mut keep = vec![false; graph.node_count()];
+    for node in graph.node_indices().filter(|node| on_path.contains(&graph[*node])) {
+        keep[node.index()] = true;
+        graph.neighbors(node).for_each(|other| keep[other.index()] = true);
+    }
+    graph.filter_map(|node, &author| keep[node.index()].then_some(author), |_, _| Some(()))
+}
+
+fn edge_key(a: NodeIndex, b: NodeIndex) -> (NodeIndex, NodeIndex) {
+    (a.min(b), a.max(b))
+}
+
+/// Author ids along a path of node indices.
+fn authors<E>(graph: &Graph<usize, E, Undirected>, path: &[NodeIndex]) -> Vec<usize> {
+    path.iter().map(|&node| graph[node]).collect()
+}
+
+/// Sum of the edge lengths along a path, the shortest of parallel edges.
+fn path_length(lengths: &WeightedAuthorGraph, path: &[NodeIndex]) -> f64 {
+    path.windows(2)
+        .map(|pair| lengths.edges_connecting(pair[0], pair[1]).map(|edge| *edge.weight()).fold(f64::INFINITY, f64::min))
+        .sum()
+}
+
+/// Breadth-first shortest path avoiding blocked authors and removed collaborations.
+fn shortest_path(
+    graph: &AuthorGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    blocked: &[bool],
+    removed: &HashSet<(NodeIndex, NodeIndex)>,
+) -> Option<Vec<NodeIndex>> {
+    if blocked[source.index()] || blocked[target.index()] {
+        return None;
+    }
+    let mut parent: Vec<Option<NodeIndex>> = vec![None; graph.node_count()];
+    parent[source.index()] = Some(source);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        if node == target {
+            let mut path = vec![target];
+            while *path.last().unwrap() != source {
+                path.push(parent[path.last().unwrap().index()].unwrap());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        // Sorted so that ties between equally short paths do not depend on edge order.
+        let mut neighbours: Vec<NodeIndex> = graph.neighbors(node).collect();
+        neighbours.sort_unstable();
+        for next in neighbours {
+            if parent[next.index()].is_none() && !blocked[next.index()] && !removed.contains(&edge_key(node, next)) {
+                parent[next.index()] = Some(node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra shortest path over edge lengths avoiding blocked authors and removed
+/// collaborations; equally distant authors are settled by index, so ties do not depend on
+/// edge order.
+fn shortest_weighted_path(
+    lengths: &WeightedAuthorGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    blocked: &[bool],
+    removed: &HashSet<(NodeIndex, NodeIndex)>,
+) -> Option<Vec<NodeIndex>> {
+    if blocked[source.index()] || blocked[target.index()] {
+        return None;
+    }
+    let n = lengths.node_count();
+    let mut distance = vec![f64::INFINITY; n];
+    let mut parent: Vec<Option<NodeIndex>> = vec![None; n];
+    let mut settled = vec![false; n];
+    distance[source.index()] = 0.0;
+    let mut heap = BinaryHeap::from([Tentative(0.0, source)]);
+    while let Some(Tentative(reached, node)) = heap.pop() {
+        if settled[node.index()] {
+            continue;
+        }
+        settled[node.index()] = true;
+        if node == target {
+            let mut path = vec![target];
+            while let Some(previous) = parent[path.last().unwrap().index()] {
+                path.push(previous);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for edge in lengths.edges(node) {
+            let next = if edge.source() == node { edge.target() } else { edge.source() };
+            if blocked[next.index()] || removed.contains(&edge_key(node, next)) {
+                continue;
+            }
+            let candidate = reached + edge.weight();
+            if candidate < distance[next.index()] {
+                distance[next.index()] = candidate;
+                parent[next.index()] = Some(node);
+                heap.push(Tentative(candidate, next));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 grid, authors 0..9 numbered row by row.
+    fn grid() -> AuthorGraph {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..9).map(|author| graph.add_node(author)).collect();
+        for i in 0..9 {
+            if i % 3 < 2 {
+                graph.add_edge(nodes[i], nodes[i + 1], ());
+            }
+            if i < 6 {
+                graph.add_edge(nodes[i], nodes[i + 3], ());
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_simple_paths_with_constraints() {
+        // Counts from networkx's all_simple_paths: 6 of length 4, 4 of 6 and 2 of 8.
+        let graph = grid();
+        let (source, target) = (NodeIndex::new(0), NodeIndex::new(8));
+        let (paths, truncated) = simple_paths(&graph, source, target, 6, &[], 100);
+        assert_eq!((paths.len(), truncated), (10, false));
+        assert_eq!(paths[0], vec![0, 1, 2, 5, 8]);
+        assert_eq!(simple_paths(&graph, source, target, 8, &[], 100).0.len(), 12);
+        // Only the two paths round the edge avoid the centre.
+        let (around, _) = simple_paths(&graph, source, target, 8, &[NodeIndex::new(4)], 100);
+        assert_eq!(around, vec![vec![0, 1, 2, 5, 8], vec![0, 3, 6, 7, 8]]);
+        let (first, truncated) = simple_paths(&graph, source, target, 8, &[], 5);
+        assert_eq!((first.len(), truncated), (5, true));
+    }
+
+    #[test]
+    fn test_k_shortest_paths() {
+        let graph = grid();
+        let (source, target) = (NodeIndex::new(0), NodeIndex::new(8));
+        let paths = k_shortest_paths(&graph, source, target, 8, &[]);
+        let lengths: Vec<usize> = paths.iter().map(|path| path.len() - 1).collect();
+        assert_eq!(lengths, vec![4, 4, 4, 4, 4, 4, 6, 6]);
+        let distinct: HashSet<_> = paths.iter().collect();
+        assert_eq!(distinct.len(), 8);
+        assert!(paths.iter().all(|path| path.iter().collect::<HashSet<_>>().len() == path.len()));
+
+        // Fewer paths than asked for, and none through an excluded endpoint.
+        let excluded = [NodeIndex::new(4)];
+        assert_eq!(k_shortest_paths(&graph, source, target, 5, &excluded).len(), 2);
+        assert!(k_shortest_paths(&graph, source, target, 5, &[target]).is_empty());
+
+        // The top row and the row below it, with their 7 collaborations.
+        let ego = ego_subgraph(&graph, &[vec![0, 1, 2]]);
+        assert_eq!((ego.node_count(), ego.edge_count()), (6, 7));
+    }
+
+    #[test]
+    fn test_weighted_paths() {
+        // networkx shortest_simple_paths with weight="weight": four paths of length 4
+        // through the centre, then the two round the edge of length 8.
+        let mut graph = grid().map(|_, &author| author, |_, _| 0.0);
+        for edge in graph.edge_indices() {
+            let (a, b) = graph.edge_endpoints(edge).unwrap();
+            graph[edge] = ((a.index() * b.index()) % 4 + 1) as f64;
+        }
+        let (source, target) = (NodeIndex::new(0), NodeIndex::new(8));
+        let paths = weighted_k_shortest_paths(&graph, EdgeWeights::Distance, source, target, 6, &[]);
+        let lengths: Vec<f64> = paths.iter().map(|(length, _)| *length).collect();
+        assert_eq!(lengths, vec![4.0, 4.0, 4.0, 4.0, 8.0, 8.0]);
+        assert_eq!(paths[0].1, vec![0, 1, 4, 5, 8]);
+        let (simple, _) = weighted_simple_paths(&graph, EdgeWeights::Distance, source, target, 4, &[], 100);
+        assert_eq!(simple, paths);
+
+        // As similarities the heavy edges round the grid are the short ones.
+        let similar = weighted_k_shortest_paths(&graph, EdgeWeights::Similarity, source, target, 3, &[]);
+        assert_eq!(similar[0].1, vec![0, 1, 2, 5, 8]);
+        assert!((similar[0].0 - 8.0 / 3.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,800 @@
+use crate::coarsen::WeightedGraph;
+use crate::community::{community_sizes, Partition};
+use crate::figure::{self, FigureMetadata};
+use crate::layout::{circular_layout, Layout};
+use crate::rich_club::RichClubLevel;
+use crate::{degree_centrality, degree_distribution, top_k, AuthorGraph};
+use petgraph::graph::{EdgeReference, NodeIndex};
+use petgraph::visit::EdgeRef;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Distinct colours given to the largest communities; smaller ones are drawn grey.
+const COMMUNITY_COLOURS: usize = 20;
+
+/// Draw the graph using the given node positions.
+pub fn draw_network<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    graph: &AuthorGraph,
+    layout: &Layout,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    draw_network_colored(area, graph, layout, None)
+}
+
+/// Draw the graph, colouring nodes by community when a partition is given.
+pub fn draw_network_colored<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    graph: &AuthorGraph,
+    layout: &Layout,
+    communities: Option<&Partition>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    draw_network_region(area, graph, layout, NetworkStyle { communities, edge_weights: None }, &Viewport::FULL)
+}
+
+/// How a network drawing colours its nodes and draws its edges.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetworkStyle<'a> {
+    /// Colour nodes by community.
+    pub communities: Option<&'a Partition>,
+    /// Scale edges by their weights, indexed by edge, as [`draw_weighted_network`] does.
+    pub edge_weights: Option<(&'a [f64], EdgeScaling)>,
+}
+
+/// A rectangle of layout coordinates, `x.0 < x.1` and `y.0 < y.1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x: (f64, f64),
+    pub y: (f64, f64),
+}
+
+impl Viewport {
+    /// The whole layout square [-1, 1]² with a small margin.
+    pub const FULL: Viewport = Viewport { x: (-1.1, 1.1), y: (-1.1, 1.1) };
+
+    pub fn contains(&self, (x, y): (f64, f64)) -> bool {
+        self.x.0 <= x && x <= self.x.1 && self.y.0 <= y && y <= self.y.1
+    }
+
+    /// The part of the segment from `a` to `b` inside the viewport (Liang–Barsky).
+    pub fn clip(&self, a: (f64, f64), b: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let (mut enter, mut leave) = (0.0f64, 1.0f64);
+        for (p, q) in [(-dx, a.0 - self.x.0), (dx, self.x.1 - a.0), (-dy, a.1 - self.y.0), (dy, self.y.1 - a.1)] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                enter = enter.max(q / p);
+            } else {
+                leave = leave.min(q / p);
+            }
+        }
+        (enter <= leave).then_some(((a.0 + enter * dx, a.1 + enter * dy), (a.0 + leave * dx, a.1 + leave * dy)))
+    }
+}
+
+impl FromStr for Viewport {
+    type Err = String;
+
+    /// Parse `x0,y0,x1,y1`, the lower-left and upper-right corners.
+    fn from_str(text: &str) -> Result<Viewport, String> {
+        let corners: Vec<f64> = text
+            .split(',')
+            .map(|value| value.trim().parse::<f64>().map_err(|_| format!("invalid viewport coordinate: {}", value)))
+            .collect::<Result<_, _>>()?;
+        match corners[..] {
+            [x0, y0, x1, y1] if x0 < x1 && y0 < y1 => Ok(Viewport { x: (x0, x1), y: (y0, y1) }),
+            [_, _, _, _] => Err(format!("empty viewport: {} (expected x0 < x1 and y0 < y1)", text)),
+            _ => Err(format!("invalid viewport: {} (expected x0,y0,x1,y1)", text)),
+        }
+    }
+}
+
+/// Draw the part of the graph inside `viewport`: nodes within it, and the visible part of
+/// every edge crossing it. Nodes grow with the drawing width, so a high-resolution render
+/// of a small region stays legible.
+pub fn draw_network_region<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    graph: &AuthorGraph,
+    layout: &Layout,
+    style: NetworkStyle,
+    viewport: &Viewport,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let palette = CommunityPalette::new(style.communities);
+    let colour_of = |node: NodeIndex| palette.colour(graph[node]);
+    let radius = (area.dim_in_pixel().0 / 512).max(2) as i32;
+
+    let mut chart = ChartBuilder::on(area)
+        .caption("Collaboration Network", ("sans-serif", 30))
+        .margin(10)
+        .build_cartesian_2d(viewport.x.0..viewport.x.1, viewport.y.0..viewport.y.1)?;
+
+    let visible = |edge: &EdgeReference<()>| viewport.clip(layout[edge.source().index()], layout[edge.target().index()]);
+    match style.edge_weights {
+        None => {
+            chart.draw_series(graph.edge_references().filter_map(|edge| {
+                let (a, b) = visible(&edge)?;
+                Some(PathElement::new(vec![a, b], BLACK.mix(0.15)))
+            }))?;
+        }
+        Some((weights, scaling)) => {
+            let mut edges: Vec<_> = graph.edge_references().collect();
+            edges.sort_by(|a, b| weights[a.id().index()].total_cmp(&weights[b.id().index()]));
+            let min = edges.first().map_or(1.0, |edge| weights[edge.id().index()]);
+            let max = edges.last().map_or(1.0, |edge| weights[edge.id().index()]);
+            chart.draw_series(edges.iter().filter_map(|edge| {
+                let (a, b) = visible(edge)?;
+                let strength = edge_strength(weights[edge.id().index()], min, max, scaling);
+                let style = BLACK.mix(0.1 + 0.7 * strength).stroke_width(1 + (5.0 * strength).round() as u32);
+                Some(PathElement::new(vec![a, b], style))
+            }))?;
+        }
+    }
+    chart.draw_series(
+        graph
+            .node_indices()
+            .filter(|node| viewport.contains(layout[node.index()]))
+            .map(|node| Circle::new(layout[node.index()], radius, colour_of(node).filled())),
+    )?;
+    Ok(())
+}
+
+/// How edge weights map to drawn thickness and opacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeScaling {
+    Linear,
+    /// Scale by the logarithm of the weight, so a few very heavy edges do not wash out
+    /// the differences among the rest.
+    Log,
+}
+
+impl FromStr for EdgeScaling {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<EdgeScaling, String> {
+        match name {
+            "linear" => Ok(EdgeScaling::Linear),
+            "log" => Ok(EdgeScaling::Log),
+            _ => Err(format!("unknown edge scaling: {} (expected linear or log)", name)),
+        }
+    }
+}
+
+/// Place of `weight` between the lightest (`min`) and heaviest (`max`) positive edge
+/// weights, from 0 to 1; all edges get 1 when the weights are equal.
+pub fn edge_strength(weight: f64, min: f64, max: f64, scaling: EdgeScaling) -> f64 {
+    let (weight, min, max) = match scaling {
+        EdgeScaling::Linear => (weight, min, max),
+        EdgeScaling::Log => (weight.ln(), min.ln(), max.ln()),
+    };
+    if max > min {
+        ((weight - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Draw a graph with weighted edges, scaling each edge's thickness and opacity by its
+/// weight so that strong collaborations stand out. Heavier edges are drawn on top.
+pub fn draw_weighted_network<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    graph: &WeightedGraph,
+    layout: &Layout,
+    scaling: EdgeScaling,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let mut chart = ChartBuilder::on(area)
+        .caption("Collaboration Network", ("sans-serif", 30))
+        .margin(10)
+        .build_cartesian_2d(-1.1..1.1, -1.1..1.1)?;
+
+    let mut edges: Vec<_> = graph.edge_references().filter(|edge| *edge.weight() > 0).collect();
+    edges.sort_by_key(|edge| *edge.weight());
+    let min = edges.first().map_or(1.0, |edge| *edge.weight() as f64);
+    let max = edges.last().map_or(1.0, |edge| *edge.weight() as f64);
+    chart.draw_series(edges.iter().map(|edge| {
+        let strength = edge_strength(*edge.weight() as f64, min, max, scaling);
+        let style = BLACK.mix(0.1 + 0.7 * strength).stroke_width(1 + (5.0 * strength).round() as u32);
+        PathElement::new(vec![layout[edge.source().index()], layout[edge.target().index()]], style)
+    }))?;
+    chart.draw_series(graph.node_indices().map(|node| Circle::new(layout[node.index()], 3, BLUE.filled())))?;
+    Ok(())
+}
+
+/// Node colours by community: the largest communities get distinct colours, the rest grey.
+pub(crate) struct CommunityPalette<'a> {
+    communities: Option<&'a Partition>,
+    rank: HashMap<usize, usize>,
+}
+
+impl<'a> CommunityPalette<'a> {
+    pub(crate) fn new(communities: Option<&'a Partition>) -> CommunityPalette<'a> {
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for &community in communities.into_iter().flat_map(|p| p.values()) {
+            *sizes.entry(community).or_insert(0) += 1;
+        }
+        let mut by_size: Vec<(usize, usize)> = sizes.into_iter().collect();
+        by_size.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let rank = by_size.into_iter().enumerate().map(|(r, (c, _))| (c, r)).collect();
+        CommunityPalette { communities, rank }
+    }
+
+    pub(crate) fn colour(&self, author: usize) -> RGBAColor {
+        match self.communities.and_then(|p| p.get(&author)).map(|c| self.rank[c]) {
+            None => BLUE.to_rgba(),
+            Some(r) if r < COMMUNITY_COLOURS => Palette99::pick(r).to_rgba(),
+            Some(_) => RGBColor(160, 160, 160).to_rgba(),
+        }
+    }
+}
+
+/// Draw a 2D projection of node embeddings as a scatter plot of `(author, position)`.
+pub fn draw_projection<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    title: &str,
+    points: &[(usize, (f64, f64))],
+    communities: Option<&Partition>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let (mut min, mut max) = ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY));
+    for &(_, (x, y)) in points {
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+    }
+    if !(min.0 < max.0 && min.1 < max.1) {
+        (min, max) = ((min.0.min(0.0) - 1.0, min.1.min(0.0) - 1.0), (max.0.max(0.0) + 1.0, max.1.max(0.0) + 1.0));
+    }
+    let palette = CommunityPalette::new(communities);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(title, ("sans-serif", 30))
+        .margin(10)
+        .build_cartesian_2d(min.0..max.0, min.1..max.1)?;
+    chart.draw_series(
+        points
+            .iter()
+            .map(|&(author, position)| Circle::new(position, 2, palette.colour(author).filled())),
+    )?;
+    Ok(())
+}
+
+/// Draw the degree distribution as a log-log scatter plot.
+pub fn draw_degree_distribution<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    graph: &AuthorGraph,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let points: Vec<(f64, f64)> = degree_distribution(graph)
+        .into_iter()
+        .filter(|&(degree, _)| degree > 0)
+        .map(|(degree, count)| (degree as f64, count as f64))
+        .collect();
+    draw_degree_points(area, &points, "Degree", "Number of authors")
+}
+
+/// Draw `(degree, value)` points on log-log axes, or `(strength, value)` with `x_desc`
+/// "Strength"; values may be below one, as the densities of wide logarithmic bins are.
+pub fn draw_degree_points<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    points: &[(f64, f64)],
+    x_desc: &str,
+    y_desc: &str,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let min_degree = points.iter().map(|p| p.0).filter(|&v| v > 0.0).fold(1.0, f64::min);
+    let max_degree = points.iter().map(|p| p.0).fold(1.0, f64::max);
+    let max_count = points.iter().map(|p| p.1).fold(1.0, f64::max);
+    let min_count = points.iter().map(|p| p.1).filter(|&v| v > 0.0).fold(1.0, f64::min);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(format!("{} Distribution", x_desc), ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(
+            (min_degree * 0.8..max_degree * 2.0).log_scale(),
+            (min_count / 2.0..max_count * 2.0).log_scale(),
+        )?;
+    chart
+        .configure_mesh()
+        .x_desc(x_desc)
+        .y_desc(y_desc)
+        .draw()?;
+    chart.draw_series(points.iter().filter(|p| p.1 > 0.0).map(|&p| Circle::new(p, 3, RED.filled())))?;
+    Ok(())
+}
+
+/// Draw the graph with collaboration paths, given as author ids, highlighted: their
+/// collaborations in thick red on top of the others, and their authors in red with
+/// their ids, so the chain can be followed on a slide.
+pub fn draw_highlighted_paths<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    graph: &AuthorGraph,
+    layout: &Layout,
+    paths: &[Vec<usize>],
+    title: &str,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let position: HashMap<usize, (f64, f64)> = graph.node_indices().map(|node| (graph[node], layout[node.index()])).collect();
+    let on_path: HashSet<usize> = paths.iter().flatten().copied().collect();
+    let radius = (area.dim_in_pixel().0 / 512).max(2) as i32;
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(title, ("sans-serif", 30))
+        .margin(10)
+        .build_cartesian_2d(Viewport::FULL.x.0..Viewport::FULL.x.1, Viewport::FULL.y.0..Viewport::FULL.y.1)?;
+    chart.draw_series(graph.edge_references().map(|edge| {
+        PathElement::new(vec![layout[edge.source().index()], layout[edge.target().index()]], BLACK.mix(0.15))
+    }))?;
+    chart.draw_series(
+        graph
+            .node_indices()
+            .filter(|node| !on_path.contains(&graph[*node]))
+            .map(|node| Circle::new(layout[node.index()], radius, BLUE.mix(0.6).filled())),
+    )?;
+    chart.draw_series(paths.iter().flat_map(|path| path.windows(2)).filter_map(|pair| {
+        let (a, b) = (*position.get(&pair[0])?, *position.get(&pair[1])?);
+        Some(PathElement::new(vec![a, b], RED.stroke_width(3)))
+    }))?;
+    chart.draw_series(on_path.iter().filter_map(|author| {
+        let point = *position.get(author)?;
+        Some(
+            EmptyElement::at(point)
+                + Circle::new((0, 0), radius + 2, RED.filled())
+                + Text::new(author.to_string(), (6, -14), ("sans-serif", 14)),
+        )
+    }))?;
+    Ok(())
+}
+
+/// Draw how many authors are at each distance from a source author, as a histogram.
+pub fn draw_distance_distribution<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    author: usize,
+    distribution: &BTreeMap<usize, usize>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let max_distance = distribution.keys().copied().max().unwrap_or(0);
+    let max_count = distribution.values().copied().max().unwrap_or(1);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(format!("Distances from Author {}", author), ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d((0..max_distance).into_segmented(), 0..max_count + max_count / 10 + 1)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_desc("Distance (collaboration steps)")
+        .y_desc("Number of authors")
+        .draw()?;
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(BLUE.mix(0.6).filled())
+            .margin(5)
+            .data(distribution.iter().map(|(&distance, &count)| (distance, count))),
+    )?;
+    Ok(())
+}
+
+/// Draw a horizontal bar chart of `(author, score)` pairs, best first from the top.
+pub fn draw_top_bar_chart<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    title: &str,
+    ranking: &[(usize, f64)],
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let rows = ranking.len().max(1);
+    let max_score = ranking.iter().map(|r| r.1).fold(0.0, f64::max);
+    let max_score = if max_score > 0.0 { max_score * 1.05 } else { 1.0 };
+    // Row 0 is at the bottom, so the best author goes into the last row.
+    // A segmented 0..n axis has n + 1 rows.
+    let label_of = |row: usize| {
+        rows.checked_sub(row + 1)
+            .and_then(|rank| ranking.get(rank))
+            .map(|(author, _)| format!("Author {}", author))
+            .unwrap_or_default()
+    };
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(title, ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(110)
+        .build_cartesian_2d(0.0..max_score, (0..rows - 1).into_segmented())?;
+    chart
+        .configure_mesh()
+        .disable_y_mesh()
+        .y_labels(rows)
+        .y_label_formatter(&|value| match value {
+            SegmentValue::CenterOf(row) => label_of(*row),
+            _ => String::new(),
+        })
+        .x_desc("Score")
+        .draw()?;
+    chart.draw_series(ranking.iter().enumerate().map(|(rank, &(_, score))| {
+        let row = rows - 1 - rank;
+        let mut bar = Rectangle::new(
+            [(0.0, SegmentValue::Exact(row)), (score, SegmentValue::Exact(row + 1))],
+            BLUE.mix(0.6).filled(),
+        );
+        bar.set_margin(3, 3, 0, 0);
+        bar
+    }))?;
+    Ok(())
+}
+
+/// Draw how many communities have each size, as a log-log scatter plot.
+pub fn draw_community_size_distribution<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    partition: &Partition,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let points: Vec<(f64, f64)> = community_sizes(partition)
+        .into_iter()
+        .map(|(size, count)| (size as f64, count as f64))
+        .collect();
+    let max_size = points.iter().map(|p| p.0).fold(1.0, f64::max);
+    let max_count = points.iter().map(|p| p.1).fold(1.0, f64::max);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption("Community Size Distribution", ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(
+            (1.0..max_size * 2.0).log_scale(),
+            (1.0..max_count * 2.0).log_scale(),
+        )?;
+    chart
+        .configure_mesh()
+        .x_desc("Community size (authors)")
+        .y_desc("Number of communities")
+        .draw()?;
+    chart.draw_series(points.into_iter().map(|p| Circle::new(p, 3, RED.filled())))?;
+    Ok(())
+}
+
+/// Draw modularity as a function of the resolution parameter, one point per run.
+pub fn draw_resolution_curve<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    points: &[(f64, f64)],
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let (min_x, max_x) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+    let (min_x, max_x) = if min_x < max_x { (min_x, max_x) } else { (0.0, 2.0) };
+    let max_y = points.iter().map(|p| p.1).fold(0.0, f64::max).max(0.1) * 1.1;
+    let min_y = points.iter().map(|p| p.1).fold(0.0, f64::min);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption("Modularity vs Resolution", ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(min_x..max_x, min_y..max_y)?;
+    chart
+        .configure_mesh()
+        .x_desc("Resolution")
+        .y_desc("Modularity")
+        .draw()?;
+    chart.draw_series(LineSeries::new(points.iter().copied(), &BLUE))?;
+    chart.draw_series(points.iter().map(|&p| Circle::new(p, 3, BLUE.filled())))?;
+    Ok(())
+}
+
+/// Draw the rich-club coefficient against the degree threshold, with the mean over
+/// degree-preserving rewirings alongside when given.
+pub fn draw_rich_club<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    levels: &[RichClubLevel],
+    random: Option<&[f64]>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let max_degree = levels.last().map_or(1, |level| level.degree.max(1)) as f64;
+    let mut chart = ChartBuilder::on(area)
+        .caption("Rich-Club Coefficient", ("sans-serif", 30))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0.0..max_degree, 0.0..1.05)?;
+    chart
+        .configure_mesh()
+        .x_desc("Degree threshold k")
+        .y_desc("phi(k)")
+        .draw()?;
+    let observed: Vec<(f64, f64)> = levels.iter().map(|level| (level.degree as f64, level.coefficient)).collect();
+    chart
+        .draw_series(LineSeries::new(observed.iter().copied(), &RED))?
+        .label("observed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+    chart.draw_series(observed.iter().map(|&p| Circle::new(p, 3, RED.filled())))?;
+    if let Some(random) = random {
+        let points = observed.iter().zip(random).map(|(&(degree, _), &phi)| (degree, phi));
+        chart
+            .draw_series(LineSeries::new(points, &BLUE))?
+            .label("rewired")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::LowerRight)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+    }
+    Ok(())
+}
+
+/// Render a single plot panel into its own PNG with the metadata caption.
+fn single_panel<P, F>(
+    output_path: P,
+    size: (u32, u32),
+    metadata: &FigureMetadata,
+    draw: F,
+) -> Result<(), Box<dyn Error>>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&DrawingArea<BitMapBackend, Shift>) -> Result<(), Box<dyn Error>>,
+{
+    let path = output_path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    {
+        let root = BitMapBackend::new(path, size).into_drawing_area();
+        root.fill(&WHITE)?;
+        let (plot_area, caption_area) = root.split_vertically(size.1 - 30);
+        draw(&plot_area)?;
+        figure::draw_caption(&caption_area, metadata)?;
+        root.present()?;
+    }
+    figure::embed_metadata(path, metadata)?;
+    Ok(())
+}
+
+/// Render the community size distribution of `partition` as a standalone PNG.
+pub fn community_size_plot<P: AsRef<Path>>(
+    partition: &Partition,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 768), metadata, |area| {
+        Ok(draw_community_size_distribution(area, partition)?)
+    })
+}
+
+/// Render a modularity-vs-resolution curve from `(resolution, modularity)` points.
+pub fn resolution_curve_plot<P: AsRef<Path>>(
+    points: &[(f64, f64)],
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 768), metadata, |area| {
+        Ok(draw_resolution_curve(area, points)?)
+    })
+}
+
+/// Render the rich-club coefficients, and their rewired baseline if any, as a standalone PNG.
+pub fn rich_club_plot<P: AsRef<Path>>(
+    levels: &[RichClubLevel],
+    random: Option<&[f64]>,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 768), metadata, |area| {
+        Ok(draw_rich_club(area, levels, random)?)
+    })
+}
+
+/// Render a 2D embedding projection, coloured by community when a partition is given.
+pub fn projection_plot<P: AsRef<Path>>(
+    points: &[(usize, (f64, f64))],
+    communities: Option<&Partition>,
+    title: &str,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 798), metadata, |area| {
+        Ok(draw_projection(area, title, points, communities)?)
+    })
+}
+
+/// Render a weighted graph on a circular layout, with edges scaled by weight.
+pub fn weighted_network_plot<P: AsRef<Path>>(
+    graph: &WeightedGraph,
+    scaling: EdgeScaling,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    let layout = circular_layout(&graph.map(|_, &node| node, |_, _| ()));
+    single_panel(output_path, (1024, 768), metadata, |area| {
+        Ok(draw_weighted_network(area, graph, &layout, scaling)?)
+    })
+}
+
+/// Render the distance distribution from `author` as a standalone PNG.
+pub fn distance_plot<P: AsRef<Path>>(
+    author: usize,
+    distribution: &BTreeMap<usize, usize>,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 768), metadata, |area| {
+        Ok(draw_distance_distribution(area, author, distribution)?)
+    })
+}
+
+/// Render `(degree, value)` or `(strength, value)` points as a standalone log-log PNG.
+pub fn degree_distribution_plot<P: AsRef<Path>>(
+    points: &[(f64, f64)],
+    x_desc: &str,
+    y_desc: &str,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 768), metadata, |area| {
+        Ok(draw_degree_points(area, points, x_desc, y_desc)?)
+    })
+}
+
+/// Render the graph with the given paths highlighted as a standalone PNG.
+pub fn path_plot<P: AsRef<Path>>(
+    graph: &AuthorGraph,
+    layout: &Layout,
+    paths: &[Vec<usize>],
+    title: &str,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    single_panel(output_path, (1024, 798), metadata, |area| {
+        Ok(draw_highlighted_paths(area, graph, layout, paths, title)?)
+    })
+}
+
+/// Render a standalone horizontal bar chart of a top-k ranking.
+pub fn bar_chart<P: AsRef<Path>>(
+    ranking: &[(usize, f64)],
+    title: &str,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    let height = 140 + 40 * ranking.len().max(1) as u32;
+    single_panel(output_path, (1024, height), metadata, |area| {
+        Ok(draw_top_bar_chart(area, title, ranking)?)
+    })
+}
+
+/// Render a report-ready composite image: network, degree distribution and the
+/// top-10 authors by degree side by side, with the metadata caption underneath.
+pub fn composite_figure<P: AsRef<Path>>(
+    graph: &AuthorGraph,
+    output_path: P,
+    metadata: &FigureMetadata,
+) -> Result<(), Box<dyn Error>> {
+    let path = output_path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let ranking: Vec<(usize, f64)> = top_k(&degree_centrality(graph), 10)
+        .into_iter()
+        .map(|(author, score)| (author, score as f64))
+        .collect();
+    {
+        let root = BitMapBackend::new(path, (1800, 640)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let (panel_area, caption_area) = root.split_vertically(610);
+        let panels = panel_area.split_evenly((1, 3));
+        draw_network(&panels[0], graph, &circular_layout(graph))?;
+        draw_degree_distribution(&panels[1], graph)?;
+        draw_top_bar_chart(&panels[2], "Top 10 Authors by Degree", &ranking)?;
+        figure::draw_caption(&caption_area, metadata)?;
+        root.present()?;
+    }
+    figure::embed_metadata(path, metadata)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_chart() {
+        let ranking = vec![(7, 3.0), (3, 2.5), (9, 1.0)];
+        let output_path = "bar_chart_test.png";
+        bar_chart(&ranking, "Top 3", output_path, &FigureMetadata::new("toy.txt")).unwrap();
+        assert!(Path::new(output_path).exists());
+
+        fs::remove_file(output_path).unwrap();
+    }
+
+    #[test]
+    fn test_community_plots() {
+        let partition: Partition = [(1, 0), (2, 0), (3, 0), (4, 1), (5, 1), (6, 2)].into_iter().collect();
+        let metadata = FigureMetadata::new("toy.txt");
+
+        community_size_plot(&partition, "community_sizes_test.png", &metadata).unwrap();
+        resolution_curve_plot(&[(0.5, 0.2), (1.0, 0.4), (1.5, 0.35)], "resolution_curve_test.png", &metadata)
+            .unwrap();
+        assert!(Path::new("community_sizes_test.png").exists());
+        assert!(Path::new("resolution_curve_test.png").exists());
+
+        fs::remove_file("community_sizes_test.png").unwrap();
+        fs::remove_file("resolution_curve_test.png").unwrap();
+    }
+
+    #[test]
+    fn test_projection_plot() {
+        let points = vec![(1, (0.0, 0.0)), (2, (1.0, 2.0)), (3, (-1.0, 0.5))];
+        let partition: Partition = [(1, 0), (2, 0), (3, 1)].into_iter().collect();
+        let output_path = "projection_test.png";
+        projection_plot(&points, Some(&partition), "t-SNE", output_path, &FigureMetadata::new("toy.txt")).unwrap();
+        assert!(Path::new(output_path).exists());
+
+        fs::remove_file(output_path).unwrap();
+    }
+
+    #[test]
+    fn test_path_plot() {
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (1..6).map(|author| graph.add_node(author)).collect();
+        for i in 0..4 {
+            graph.add_edge(nodes[i], nodes[i + 1], ());
+        }
+        graph.add_edge(nodes[0], nodes[4], ());
+        let output_path = "path_plot_test.png";
+        let paths = vec![vec![1, 2, 3], vec![1, 5, 4, 3]];
+        path_plot(&graph, &circular_layout(&graph), &paths, "Paths", output_path, &FigureMetadata::new("toy.txt")).unwrap();
+        assert!(Path::new(output_path).exists());
+
+        fs::remove_file(output_path).unwrap();
+    }
+
+    #[test]
+    fn test_viewport() {
+        let viewport: Viewport = "0,0,1,0.5".parse().unwrap();
+        assert!(viewport.contains((0.5, 0.25)));
+        assert!(!viewport.contains((0.5, 0.75)));
+        // A segment crossing the whole box keeps only its middle.
+        assert_eq!(viewport.clip((-1.0, 0.25), (2.0, 0.25)), Some(((0.0, 0.25), (1.0, 0.25))));
+        assert_eq!(viewport.clip((-1.0, 0.75), (2.0, 0.75)), None);
+
+        assert!("1,0,0,1".parse::<Viewport>().is_err());
+        assert!("0,0,1".parse::<Viewport>().is_err());
+    }
+
+    #[test]
+    fn test_weighted_network_plot() {
+        assert_eq!(edge_strength(1.0, 1.0, 100.0, EdgeScaling::Linear), 0.0);
+        assert!((edge_strength(10.0, 1.0, 100.0, EdgeScaling::Log) - 0.5).abs() < 1e-12);
+        assert!(edge_strength(10.0, 1.0, 100.0, EdgeScaling::Linear) < 0.1);
+        assert_eq!(edge_strength(3.0, 3.0, 3.0, EdgeScaling::Log), 1.0);
+
+        let mut graph = WeightedGraph::new_undirected();
+        let nodes: Vec<_> = (0..4).map(|_| graph.add_node(1)).collect();
+        for (i, weight) in [1, 5, 50].into_iter().enumerate() {
+            graph.add_edge(nodes[i], nodes[i + 1], weight);
+        }
+        let output_path = "weighted_network_test.png";
+        weighted_network_plot(&graph, "log".parse().unwrap(), output_path, &FigureMetadata::new("toy.txt")).unwrap();
+        assert!(Path::new(output_path).exists());
+
+        fs::remove_file(output_path).unwrap();
+    }
+
+    #[test]
+    fn test_composite_figure() {
+        let mut graph = AuthorGraph::new_undirected();
+        let hub = graph.add_node(1);
+        for author in 2..6 {
+            let leaf = graph.add_node(author);
+            graph.add_edge(hub, leaf, ());
+        }
+
+        let output_path = "composite_figure_test.png";
+        composite_figure(&graph, output_path, &FigureMetadata::new("star.txt")).unwrap();
+        assert!(Path::new(output_path).exists());
+
+        fs::remove_file(output_path).unwrap();
+    }
+}
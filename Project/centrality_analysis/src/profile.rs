@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+thread_local! {
+    /// Stage timings of this thread, in completion order, once recording is enabled.
+    static TIMINGS: RefCell<Option<Vec<(&'static str, Duration)>>> = const { RefCell::new(None) };
+}
+
+/// Run an expression as a named stage of the analysis: it is timed when
+/// [`enable_timings`] has been called, and runs inside a `tracing` span of the same name
+/// when the crate is built with the `tracing` feature, so `tracing-flame` output folds
+/// by stage. Stages may nest.
+#[macro_export]
+macro_rules! stage {
+    ($name:literal, $body:expr) => {{
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($name).entered();
+        let start = std::time::Instant::now();
+        let value = $body;
+        $crate::profile::record($name, start.elapsed());
+        value
+    }};
+}
+
+/// Start recording stage timings on this thread, discarding earlier ones.
+pub fn enable_timings() {
+    TIMINGS.with(|timings| *timings.borrow_mut() = Some(Vec::new()));
+}
+
+/// Record that a stage took `elapsed`; does nothing unless recording is enabled.
+pub fn record(name: &'static str, elapsed: Duration) {
+    TIMINGS.with(|timings| {
+        if let Some(timings) = timings.borrow_mut().as_mut() {
+            timings.push((name, elapsed));
+        }
+    });
+}
+
+/// Total time and number of runs per stage, in order of first completion.
+pub fn stage_totals() -> Vec<(&'static str, Duration, usize)> {
+    TIMINGS.with(|timings| {
+        let mut totals: Vec<(&'static str, Duration, usize)> = Vec::new();
+        for &(name, elapsed) in timings.borrow().iter().flatten() {
+            match totals.iter_mut().find(|(stage, _, _)| *stage == name) {
+                Some((_, total, runs)) => {
+                    *total += elapsed;
+                    *runs += 1;
+                }
+                None => totals.push((name, elapsed, 1)),
+            }
+        }
+        totals
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_timings() {
+        // Nothing is kept before recording starts.
+        let value = stage!("ignored", 1 + 1);
+        assert_eq!(value, 2);
+        assert!(stage_totals().is_empty());
+
+        enable_timings();
+        for _ in 0..3 {
+            stage!("outer", stage!("inner", std::thread::sleep(Duration::from_millis(1))));
+        }
+        let totals = stage_totals();
+        let names: Vec<_> = totals.iter().map(|(name, _, runs)| (*name, *runs)).collect();
+        assert_eq!(names, vec![("inner", 3), ("outer", 3)]);
+        assert!(totals[1].1 >= totals[0].1 && totals[0].1 >= Duration::from_millis(3));
+    }
+}
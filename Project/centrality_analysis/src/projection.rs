@@ -0,0 +1,316 @@
+use crate::line_fields;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Iterations during which the input similarities are exaggerated.
+const EARLY_EXAGGERATION_ITERATIONS: usize = 250;
+const EARLY_EXAGGERATION: f64 = 12.0;
+/// Barnes-Hut accuracy: a cell is summarized when its width over its distance is below this.
+const THETA: f64 = 0.5;
+/// Quadtree depth limit, so coincident points end up sharing a leaf.
+const MAX_DEPTH: usize = 32;
+
+/// Project high-dimensional points to 2D with Barnes-Hut t-SNE (van der Maaten, 2014).
+///
+/// Input similarities use the `3 * perplexity` nearest neighbours of each point;
+/// `seed` fixes the random initialization.
+pub fn tsne(points: &[Vec<f64>], perplexity: f64, iterations: usize, seed: u64) -> Vec<(f64, f64)> {
+    let n = points.len();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut y: Vec<(f64, f64)> = (0..n)
+        .map(|_| (rng.gen_range(-1e-4..1e-4), rng.gen_range(-1e-4..1e-4)))
+        .collect();
+    if n < 2 {
+        return y;
+    }
+
+    let affinities = joint_affinities(points, perplexity);
+    // Learning rate scaled with the number of points (Belkina et al., 2019).
+    let learning_rate = (n as f64 / EARLY_EXAGGERATION).max(50.0);
+    let mut velocity = vec![(0.0, 0.0); n];
+    let mut gains = vec![(1.0, 1.0); n];
+    for iteration in 0..iterations {
+        let early = iteration < EARLY_EXAGGERATION_ITERATIONS;
+        let exaggeration = if early { EARLY_EXAGGERATION } else { 1.0 };
+        let momentum = if early { 0.5 } else { 0.8 };
+
+        let tree = QuadTree::build(&y);
+        let mut repulsion = vec![(0.0, 0.0); n];
+        let mut z = 0.0;
+        for (i, force) in repulsion.iter_mut().enumerate() {
+            z += tree.repulsion(y[i], force);
+        }
+        // Every point met itself once at distance zero.
+        let z = (z - n as f64).max(f64::MIN_POSITIVE);
+
+        for i in 0..n {
+            let mut attraction = (0.0, 0.0);
+            for &(j, p) in &affinities[i] {
+                let (dx, dy) = (y[i].0 - y[j].0, y[i].1 - y[j].1);
+                let q = 1.0 / (1.0 + dx * dx + dy * dy);
+                attraction.0 += exaggeration * p * q * dx;
+                attraction.1 += exaggeration * p * q * dy;
+            }
+            let gradient = (
+                4.0 * (attraction.0 - repulsion[i].0 / z),
+                4.0 * (attraction.1 - repulsion[i].1 / z),
+            );
+            gains[i].0 = update_gain(gains[i].0, gradient.0, velocity[i].0);
+            gains[i].1 = update_gain(gains[i].1, gradient.1, velocity[i].1);
+            velocity[i].0 = momentum * velocity[i].0 - learning_rate * gains[i].0 * gradient.0;
+            velocity[i].1 = momentum * velocity[i].1 - learning_rate * gains[i].1 * gradient.1;
+        }
+        for (point, step) in y.iter_mut().zip(&velocity) {
+            point.0 += step.0;
+            point.1 += step.1;
+        }
+    }
+    y
+}
+
+/// Adaptive per-coordinate step size (Jacobs, 1988): grow while the direction holds.
+fn update_gain(gain: f64, gradient: f64, velocity: f64) -> f64 {
+    let gain = if (gradient > 0.0) != (velocity > 0.0) { gain + 0.2 } else { gain * 0.8 };
+    gain.max(0.01)
+}
+
+/// Symmetric joint probabilities p_ij over nearest neighbours, as sparse rows.
+fn joint_affinities(points: &[Vec<f64>], perplexity: f64) -> Vec<Vec<(usize, f64)>> {
+    let n = points.len();
+    let k = ((3.0 * perplexity) as usize).clamp(1, n - 1);
+    let mut rows: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); n];
+    for (i, point) in points.iter().enumerate() {
+        let mut distances: Vec<(f64, usize)> = points
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(j, other)| (point.iter().zip(other).map(|(a, b)| (a - b) * (a - b)).sum(), j))
+            .collect();
+        distances.select_nth_unstable_by(k - 1, |a, b| a.0.total_cmp(&b.0));
+        distances.truncate(k);
+
+        let conditional = conditional_probabilities(&distances, perplexity.min(k as f64));
+        for (&(_, j), p) in distances.iter().zip(conditional) {
+            let value = p / (2.0 * n as f64);
+            *rows[i].entry(j).or_insert(0.0) += value;
+            *rows[j].entry(i).or_insert(0.0) += value;
+        }
+    }
+    rows.into_iter().map(|row| row.into_iter().collect()).collect()
+}
+
+/// Gaussian conditional probabilities p_j|i whose perplexity matches the target,
+/// found by bisection on the precision.
+fn conditional_probabilities(distances: &[(f64, usize)], perplexity: f64) -> Vec<f64> {
+    let target = perplexity.max(1.0).ln();
+    let nearest = distances.iter().map(|d| d.0).fold(f64::INFINITY, f64::min);
+    let (mut low, mut high, mut beta) = (0.0, f64::INFINITY, 1.0);
+    let mut probabilities = vec![0.0; distances.len()];
+    for _ in 0..100 {
+        // Shifting by the nearest distance avoids underflow for far-away neighbourhoods.
+        for (p, &(d, _)) in probabilities.iter_mut().zip(distances) {
+            *p = (-beta * (d - nearest)).exp();
+        }
+        let sum: f64 = probabilities.iter().sum();
+        let entropy = beta * probabilities.iter().zip(distances).map(|(p, d)| p * (d.0 - nearest)).sum::<f64>()
+            / sum
+            + sum.ln();
+        probabilities.iter_mut().for_each(|p| *p /= sum);
+        if (entropy - target).abs() < 1e-5 {
+            break;
+        }
+        if entropy > target {
+            low = beta;
+            beta = if high.is_finite() { (beta + high) / 2.0 } else { beta * 2.0 };
+        } else {
+            high = beta;
+            beta = (beta + low) / 2.0;
+        }
+    }
+    probabilities
+}
+
+/// Quadtree over the current 2D positions, storing each cell's size and centre of mass.
+struct QuadTree {
+    cells: Vec<Cell>,
+}
+
+struct Cell {
+    center: (f64, f64),
+    half_width: f64,
+    count: f64,
+    mass_center: (f64, f64),
+    children: Option<[usize; 4]>,
+    points: Vec<(f64, f64)>,
+}
+
+impl Cell {
+    fn new(center: (f64, f64), half_width: f64) -> Cell {
+        Cell { center, half_width, count: 0.0, mass_center: (0.0, 0.0), children: None, points: Vec::new() }
+    }
+}
+
+impl QuadTree {
+    fn build(points: &[(f64, f64)]) -> QuadTree {
+        let (mut min, mut max) = ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY));
+        for &(x, y) in points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        let center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+        let half_width = ((max.0 - min.0).max(max.1 - min.1) / 2.0).max(1e-12) * 1.0001;
+        let mut tree = QuadTree { cells: vec![Cell::new(center, half_width)] };
+        for &point in points {
+            tree.insert(0, point, 0);
+        }
+        tree
+    }
+
+    fn insert(&mut self, index: usize, point: (f64, f64), depth: usize) {
+        let cell = &mut self.cells[index];
+        cell.count += 1.0;
+        cell.mass_center.0 += (point.0 - cell.mass_center.0) / cell.count;
+        cell.mass_center.1 += (point.1 - cell.mass_center.1) / cell.count;
+
+        if let Some(children) = cell.children {
+            let child = children[self.quadrant(index, point)];
+            self.insert(child, point, depth + 1);
+            return;
+        }
+        if cell.points.is_empty() || depth >= MAX_DEPTH {
+            cell.points.push(point);
+            return;
+        }
+
+        let (center, half) = (cell.center, cell.half_width / 2.0);
+        let first = self.cells.len();
+        for (dx, dy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            self.cells.push(Cell::new((center.0 + dx * half, center.1 + dy * half), half));
+        }
+        self.cells[index].children = Some([first, first + 1, first + 2, first + 3]);
+        for existing in std::mem::take(&mut self.cells[index].points) {
+            let child = first + self.quadrant(index, existing);
+            self.insert(child, existing, depth + 1);
+        }
+        let child = first + self.quadrant(index, point);
+        self.insert(child, point, depth + 1);
+    }
+
+    fn quadrant(&self, index: usize, point: (f64, f64)) -> usize {
+        let center = self.cells[index].center;
+        usize::from(point.0 >= center.0) + 2 * usize::from(point.1 >= center.1)
+    }
+
+    /// Add the unnormalized repulsive force on `point` to `force` and return its
+    /// contribution to the normalization Z (including the point itself, at q = 1).
+    fn repulsion(&self, point: (f64, f64), force: &mut (f64, f64)) -> f64 {
+        let mut z = 0.0;
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let cell = &self.cells[index];
+            if cell.count == 0.0 {
+                continue;
+            }
+            let (dx, dy) = (point.0 - cell.mass_center.0, point.1 - cell.mass_center.1);
+            let distance_squared = dx * dx + dy * dy;
+            match cell.children {
+                Some(children) if (2.0 * cell.half_width).powi(2) >= THETA * THETA * distance_squared => {
+                    stack.extend(children);
+                }
+                Some(_) => z += accumulate(force, cell.count, dx, dy),
+                None => {
+                    for &other in &cell.points {
+                        z += accumulate(force, 1.0, point.0 - other.0, point.1 - other.1);
+                    }
+                }
+            }
+        }
+        z
+    }
+}
+
+/// Repulsion from `count` points at offset `(dx, dy)`; returns their share of Z.
+fn accumulate(force: &mut (f64, f64), count: f64, dx: f64, dy: f64) -> f64 {
+    let q = 1.0 / (1.0 + dx * dx + dy * dy);
+    force.0 += count * q * q * dx;
+    force.1 += count * q * q * dy;
+    count * q
+}
+
+/// Load node embeddings, one author per line: the author id followed by the
+/// coordinates, separated by commas or whitespace. Lines starting with `#` and
+/// lines that do not parse (such as a header) are skipped.
+pub fn load_embedding<P: AsRef<Path>>(path: P) -> io::Result<HashMap<usize, Vec<f64>>> {
+    let mut embedding = HashMap::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let Some(mut fields) = line_fields(&line, true) else {
+            continue;
+        };
+        let Some(Ok(author)) = fields.next().map(str::parse::<usize>) else {
+            continue;
+        };
+        if let Ok(values) = fields.map(str::parse::<f64>).collect::<Result<Vec<f64>, _>>() {
+            if !values.is_empty() {
+                embedding.insert(author, values);
+            }
+        }
+    }
+    Ok(embedding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tsne_separates_clusters() {
+        // Two tight, well-separated groups in 5 dimensions.
+        let mut rng = StdRng::seed_from_u64(1);
+        let points: Vec<Vec<f64>> = (0..40)
+            .map(|i| {
+                let offset = if i < 20 { 0.0 } else { 10.0 };
+                (0..5).map(|_| offset + rng.gen_range(-0.5..0.5)).collect()
+            })
+            .collect();
+
+        let projected = tsne(&points, 5.0, 500, 7);
+        let centroid = |range: std::ops::Range<usize>| {
+            let len = range.len() as f64;
+            let (x, y) = range.map(|i| projected[i]).fold((0.0, 0.0), |a, p| (a.0 + p.0, a.1 + p.1));
+            (x / len, y / len)
+        };
+        let spread = |range: std::ops::Range<usize>, c: (f64, f64)| {
+            range
+                .map(|i| ((projected[i].0 - c.0).powi(2) + (projected[i].1 - c.1).powi(2)).sqrt())
+                .fold(0.0, f64::max)
+        };
+        let (a, b) = (centroid(0..20), centroid(20..40));
+        let gap = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+        assert!(gap > spread(0..20, a) + spread(20..40, b), "gap {}", gap);
+    }
+
+    #[test]
+    fn test_perplexity_calibration() {
+        let distances: Vec<(f64, usize)> = (0..30).map(|j| (j as f64 * 0.3, j)).collect();
+        let p = conditional_probabilities(&distances, 10.0);
+        let entropy: f64 = -p.iter().filter(|&&v| v > 0.0).map(|v| v * v.ln()).sum::<f64>();
+        assert!((entropy.exp() - 10.0).abs() < 1e-2);
+        assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_load_embedding() {
+        let path = "embedding_test.csv";
+        std::fs::write(path, "author,x,y\n1,0.5,1.5\n# comment\n2 -1 2\n").unwrap();
+        let embedding = load_embedding(path).unwrap();
+        assert_eq!(embedding.len(), 2);
+        assert_eq!(embedding[&1], vec![0.5, 1.5]);
+        assert_eq!(embedding[&2], vec![-1.0, 2.0]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
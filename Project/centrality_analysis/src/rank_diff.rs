@@ -0,0 +1,72 @@
+use crate::bootstrap::ranks;
+use crate::top_k;
+use std::collections::{HashMap, HashSet};
+
+/// An author in the top `k` of the second ranking, and where they were in the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Movement {
+    pub author: usize,
+    pub score: f64,
+    pub rank: usize,
+    /// Rank in the first ranking; `None` if the author is not in it at all.
+    pub previous: Option<usize>,
+}
+
+impl Movement {
+    /// Places gained (positive) or lost (negative) since the first ranking.
+    pub fn change(&self) -> Option<isize> {
+        self.previous.map(|previous| previous as isize - self.rank as isize)
+    }
+}
+
+/// Two top-`k` lists side by side: `first[i]` and `second[i]` hold rank `i + 1` of
+/// each, as `(author, score, rank)` and as a movement from the first ranking.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankDiff {
+    pub first: Vec<(usize, f64, usize)>,
+    pub second: Vec<Movement>,
+    /// Authors in the first top `k` but not in the second, with their new rank if any.
+    pub dropped: Vec<(usize, Option<usize>)>,
+}
+
+/// Compare the top `k` of two rankings of (mostly) the same authors, such as one measure
+/// in two runs or two measures on one graph. Ranks are over all scored authors, ties
+/// sharing the best rank, so movement is meaningful beyond the top `k` too.
+pub fn rank_diff(first: &HashMap<usize, f64>, second: &HashMap<usize, f64>, k: usize) -> RankDiff {
+    let (first_ranks, second_ranks) = (ranks(first), ranks(second));
+    let first_top: Vec<(usize, f64, usize)> =
+        top_k(first, k).into_iter().map(|(author, score)| (author, score, first_ranks[&author])).collect();
+    let second_top: Vec<Movement> = top_k(second, k)
+        .into_iter()
+        .map(|(author, score)| Movement {
+            author,
+            score,
+            rank: second_ranks[&author],
+            previous: first_ranks.get(&author).copied(),
+        })
+        .collect();
+    let kept: HashSet<usize> = second_top.iter().map(|movement| movement.author).collect();
+    let dropped = first_top
+        .iter()
+        .filter(|(author, _, _)| !kept.contains(author))
+        .map(|&(author, _, _)| (author, second_ranks.get(&author).copied()))
+        .collect();
+    RankDiff { first: first_top, second: second_top, dropped }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_diff() {
+        let first: HashMap<usize, f64> = [(1, 0.9), (2, 0.8), (3, 0.7), (4, 0.1)].into_iter().collect();
+        let second: HashMap<usize, f64> = [(1, 0.5), (2, 0.1), (3, 0.9), (5, 0.6)].into_iter().collect();
+        let diff = rank_diff(&first, &second, 3);
+        assert_eq!(diff.first, vec![(1, 0.9, 1), (2, 0.8, 2), (3, 0.7, 3)]);
+        let moves: Vec<(usize, Option<isize>)> = diff.second.iter().map(|m| (m.author, m.change())).collect();
+        // 3 climbs two places, 5 is new, 1 falls to third.
+        assert_eq!(moves, vec![(3, Some(2)), (5, None), (1, Some(-2))]);
+        assert_eq!(diff.dropped, vec![(2, Some(4))]);
+    }
+}
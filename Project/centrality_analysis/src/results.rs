@@ -0,0 +1,220 @@
+use crate::audit::AuditLog;
+use crate::{AuthorGraph, Centralities};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Everything the default analysis computes, in columns: `degree[i]` etc. belong to
+/// `authors[i]`, with authors in increasing order. Columns keep the encoding compact
+/// and load directly into array libraries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisResults {
+    pub dataset: String,
+    pub nodes: usize,
+    pub edges: usize,
+    pub components: usize,
+    pub authors: Vec<usize>,
+    pub degree: Vec<usize>,
+    pub betweenness: Vec<f64>,
+    pub closeness: Vec<f64>,
+    pub harmonic: Vec<f64>,
+    pub eigenvector: Vec<f64>,
+    /// Local clustering coefficients, and the graph's transitivity; empty and zero unless
+    /// set with [`AnalysisResults::with_clustering`].
+    #[serde(default)]
+    pub clustering: Vec<f64>,
+    #[serde(default)]
+    pub transitivity: f64,
+    /// Preprocessing applied while loading; empty in results written before it was recorded.
+    #[serde(default)]
+    pub audit: AuditLog,
+}
+
+impl AnalysisResults {
+    pub fn new(dataset: &str, graph: &AuthorGraph, components: usize, centralities: &Centralities) -> AnalysisResults {
+        let mut authors: Vec<usize> = graph.node_weights().copied().collect();
+        authors.sort_unstable();
+        let column = |scores: &HashMap<usize, f64>| authors.iter().map(|a| scores[a]).collect();
+        AnalysisResults {
+            dataset: dataset.to_string(),
+            nodes: graph.node_count(),
+            edges: graph.edge_count(),
+            components,
+            degree: authors.iter().map(|a| centralities.degree[a] as usize).collect(),
+            betweenness: column(&centralities.betweenness),
+            closeness: column(&centralities.closeness),
+            harmonic: column(&centralities.harmonic),
+            eigenvector: column(&centralities.eigenvector),
+            authors,
+            clustering: Vec::new(),
+            transitivity: 0.0,
+            audit: AuditLog::default(),
+        }
+    }
+
+    pub fn with_clustering(mut self, clustering: &HashMap<usize, f64>, transitivity: f64) -> AnalysisResults {
+        self.clustering = self.authors.iter().map(|a| clustering[a]).collect();
+        self.transitivity = transitivity;
+        self
+    }
+
+    pub fn with_audit(mut self, audit: AuditLog) -> AnalysisResults {
+        self.audit = audit;
+        self
+    }
+
+    /// Every author's score under a measure by its name, as in [`Centralities::measures`],
+    /// or `clustering` when the results carry it.
+    pub fn scores(&self, measure: &str) -> Option<HashMap<usize, f64>> {
+        let column: Vec<f64> = match measure {
+            "degree" => self.degree.iter().map(|&degree| degree as f64).collect(),
+            "betweenness" => self.betweenness.clone(),
+            "closeness" => self.closeness.clone(),
+            "harmonic" => self.harmonic.clone(),
+            "eigenvector" => self.eigenvector.clone(),
+            "clustering" if !self.clustering.is_empty() => self.clustering.clone(),
+            _ => return None,
+        };
+        Some(self.authors.iter().copied().zip(column).collect())
+    }
+
+    /// At most `limit` author rows starting at row `offset`, for consumers that read the
+    /// columns piecewise. [`ResultPage::next`] is the offset of the following page.
+    pub fn page(&self, offset: usize, limit: usize) -> ResultPage {
+        let total = self.authors.len();
+        let start = offset.min(total);
+        let end = start.saturating_add(limit).min(total);
+        let clustering = if self.clustering.is_empty() { Vec::new() } else { self.clustering[start..end].to_vec() };
+        ResultPage {
+            offset: start,
+            total,
+            next: (end < total).then_some(end),
+            authors: self.authors[start..end].to_vec(),
+            degree: self.degree[start..end].to_vec(),
+            betweenness: self.betweenness[start..end].to_vec(),
+            closeness: self.closeness[start..end].to_vec(),
+            harmonic: self.harmonic[start..end].to_vec(),
+            eigenvector: self.eigenvector[start..end].to_vec(),
+            clustering,
+        }
+    }
+}
+
+/// A contiguous slice of the author rows of [`AnalysisResults`], in the same column layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultPage {
+    pub offset: usize,
+    /// Number of authors in the whole result.
+    pub total: usize,
+    /// Offset of the next page, or `None` on the last one.
+    pub next: Option<usize>,
+    pub authors: Vec<usize>,
+    pub degree: Vec<usize>,
+    pub betweenness: Vec<f64>,
+    pub closeness: Vec<f64>,
+    pub harmonic: Vec<f64>,
+    pub eigenvector: Vec<f64>,
+    /// Empty if the results carry no clustering coefficients.
+    pub clustering: Vec<f64>,
+}
+
+/// Serialization of [`AnalysisResults`], chosen by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultFormat {
+    Json,
+    /// MessagePack (`.msgpack` or `.mp`), with structs as maps keyed by field name.
+    MessagePack,
+    /// CBOR (`.cbor`, RFC 8949).
+    Cbor,
+}
+
+impl ResultFormat {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<ResultFormat, String> {
+        let path = path.as_ref();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Ok(ResultFormat::Json),
+            Some("msgpack") | Some("mp") => Ok(ResultFormat::MessagePack),
+            Some("cbor") => Ok(ResultFormat::Cbor),
+            _ => Err(format!("cannot tell the format of {} (expected .json, .msgpack or .cbor)", path.display())),
+        }
+    }
+}
+
+fn invalid_data(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+/// Write the results in the format given by the extension of `path`.
+pub fn write_results<P: AsRef<Path>>(results: &AnalysisResults, path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    let format = ResultFormat::from_path(path).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    match format {
+        ResultFormat::Json => serde_json::to_writer(&mut writer, results)?,
+        ResultFormat::MessagePack => rmp_serde::encode::write_named(&mut writer, results).map_err(invalid_data)?,
+        ResultFormat::Cbor => ciborium::ser::into_writer(results, &mut writer).map_err(invalid_data)?,
+    }
+    writer.flush()
+}
+
+/// Read results written by [`write_results`].
+pub fn read_results<P: AsRef<Path>>(path: P) -> io::Result<AnalysisResults> {
+    let path = path.as_ref();
+    let format = ResultFormat::from_path(path).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let reader = BufReader::new(File::open(path)?);
+    match format {
+        ResultFormat::Json => Ok(serde_json::from_reader(reader)?),
+        ResultFormat::MessagePack => rmp_serde::from_read(reader).map_err(invalid_data),
+        ResultFormat::Cbor => ciborium::de::from_reader(reader).map_err(invalid_data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{local_clustering, transitivity};
+    use crate::{centrality_scores, load_graph_from_reader};
+
+    #[test]
+    fn test_results_round_trip() {
+        let graph = load_graph_from_reader("1 2\n2 3\n3 1\n3 4\n".as_bytes()).unwrap();
+        let mut audit = AuditLog::default();
+        audit.record("self-loops dropped", 1);
+        let results = AnalysisResults::new("toy.txt", &graph, 1, &centrality_scores(&graph))
+            .with_clustering(&local_clustering(&graph), transitivity(&graph))
+            .with_audit(audit);
+        assert_eq!(results.authors, vec![1, 2, 3, 4]);
+        assert_eq!(results.degree, vec![2, 2, 3, 1]);
+        assert_eq!((results.clustering[2], results.transitivity), (1.0 / 3.0, 0.6));
+
+        let mut sizes = Vec::new();
+        for path in ["results_test.json", "results_test.msgpack", "results_test.cbor"] {
+            write_results(&results, path).unwrap();
+            assert_eq!(read_results(path).unwrap(), results);
+            sizes.push(fs::metadata(path).unwrap().len());
+            fs::remove_file(path).unwrap();
+        }
+        assert!(sizes[1] < sizes[0] && sizes[2] < sizes[0], "{:?}", sizes);
+        assert!(write_results(&results, "results_test.txt").is_err());
+    }
+
+    #[test]
+    fn test_result_pages() {
+        let graph = load_graph_from_reader("1 2\n2 3\n3 1\n3 4\n4 5\n".as_bytes()).unwrap();
+        let results = AnalysisResults::new("toy.txt", &graph, 1, &centrality_scores(&graph));
+        let first = results.page(0, 2);
+        assert_eq!((first.authors, first.degree, first.next), (vec![1, 2], vec![2, 2], Some(2)));
+        let last = results.page(4, 2);
+        assert_eq!((last.authors, last.total, last.next), (vec![5], 5, None));
+        assert_eq!(last.betweenness, results.betweenness[4..]);
+        assert!(results.page(9, 2).authors.is_empty());
+        assert_eq!(results.scores("degree").unwrap()[&3], 3.0);
+        assert!(results.scores("clustering").is_none() && results.scores("katz").is_none());
+        assert_eq!(results.page(0, usize::MAX).next, None);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::audit::AuditLog;
+use crate::noise::rewire;
+use crate::AuthorGraph;
+use petgraph::visit::EdgeRef;
+
+/// Double edge swaps per collaboration when randomising the graph for the normalised
+/// coefficient; enough for the rich-club coefficient of the result to stop changing.
+pub const SWAPS_PER_EDGE: usize = 10;
+
+/// The club of authors with more than `degree` collaborators.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RichClubLevel {
+    pub degree: usize,
+    pub authors: usize,
+    /// Collaborations among the club's authors.
+    pub collaborations: usize,
+    /// Fraction of the possible collaborations among them that exist.
+    pub coefficient: f64,
+}
+
+/// Rich-club coefficient phi(k) for every degree threshold `k` whose club has at least
+/// two authors, from 0 up. An edge is in the club of every `k` below the smaller degree of
+/// its two authors, so all levels are counted in one pass over the edges.
+pub fn rich_club(graph: &AuthorGraph) -> Vec<RichClubLevel> {
+    let degree: Vec<usize> = graph.node_indices().map(|node| graph.neighbors(node).count()).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+    let (mut authors, mut collaborations) = (vec![0; max_degree + 1], vec![0; max_degree + 1]);
+    degree.iter().for_each(|&d| authors[d] += 1);
+    for edge in graph.edge_references() {
+        collaborations[degree[edge.source().index()].min(degree[edge.target().index()])] += 1;
+    }
+
+    // Suffix sums: authors (and collaborations) with degree above each threshold.
+    let mut levels = Vec::new();
+    let (mut above, mut within) = (0, 0);
+    for k in (0..max_degree).rev() {
+        above += authors[k + 1];
+        within += collaborations[k + 1];
+        if above >= 2 {
+            let coefficient = 2.0 * within as f64 / (above * (above - 1)) as f64;
+            levels.push(RichClubLevel { degree: k, authors: above, collaborations: within, coefficient });
+        }
+    }
+    levels.reverse();
+    levels
+}
+
+/// Mean rich-club coefficient at each level of [`rich_club`] over `randomizations`
+/// degree-preserving rewirings of the graph. Rewiring keeps every author's degree, so
+/// the clubs have the same authors and the levels line up; dividing the observed
+/// coefficient by this one gives the normalised coefficient, above 1 when hubs
+/// collaborate with each other more than their degrees alone explain.
+pub fn random_rich_club(graph: &AuthorGraph, randomizations: usize, seed: u64) -> Vec<f64> {
+    let mut sums = vec![0.0; rich_club(graph).len()];
+    for run in 0..randomizations {
+        let rewired = rewire(graph, 2.0 * SWAPS_PER_EDGE as f64, seed + run as u64, &mut AuditLog::default());
+        for (sum, level) in sums.iter_mut().zip(rich_club(&rewired)) {
+            *sum += level.coefficient;
+        }
+    }
+    sums.into_iter().map(|sum| sum / randomizations.max(1) as f64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::complete;
+    use crate::load_graph_from_reader;
+
+    #[test]
+    fn test_rich_club() {
+        // Two triangles sharing the edge 1-2, plus the pendant author 4 (networkx: 0.6,
+        // 5/6 and 1 for k = 0, 1, 2).
+        let graph = load_graph_from_reader("0 1\n0 2\n1 2\n1 3\n2 3\n3 4\n".as_bytes()).unwrap();
+        let levels = rich_club(&graph);
+        let summary: Vec<(usize, usize, usize)> = levels.iter().map(|l| (l.degree, l.authors, l.collaborations)).collect();
+        assert_eq!(summary, vec![(0, 5, 6), (1, 4, 5), (2, 3, 3)]);
+        assert!((levels[0].coefficient - 0.6).abs() < 1e-12);
+        assert!((levels[1].coefficient - 5.0 / 6.0).abs() < 1e-12);
+        assert_eq!(levels[2].coefficient, 1.0);
+    }
+
+    #[test]
+    fn test_random_rich_club_keeps_the_levels() {
+        // A complete graph cannot be rewired, so the random coefficient is the observed one.
+        assert_eq!(random_rich_club(&complete(5), 3, 1), vec![1.0; 4]);
+
+        // Two hubs with three leaves each: six leaves cannot absorb both hubs' degrees, so
+        // every rewiring keeps the hubs' collaboration while moving the leaves around.
+        let graph = load_graph_from_reader("0 1\n0 2\n0 3\n0 4\n1 5\n1 6\n1 7\n".as_bytes()).unwrap();
+        let observed: Vec<f64> = rich_club(&graph).iter().map(|level| level.coefficient).collect();
+        assert_eq!(observed, vec![0.25, 1.0, 1.0, 1.0]);
+        assert_eq!(random_rich_club(&graph, 5, 7), observed);
+    }
+}
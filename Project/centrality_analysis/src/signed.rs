@@ -0,0 +1,205 @@
+use crate::line_fields;
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Undirected;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Collaboration graph whose edges carry a sign, +1 (e.g. trust) or -1 (distrust).
+pub type SignedGraph = Graph<usize, i8, Undirected>;
+
+/// Parse an edge sign: `+`, `-`, or a number whose sign is taken (`1`, `-1`, `+1`).
+pub fn parse_sign(field: &str) -> Option<i8> {
+    match field {
+        "+" => Some(1),
+        "-" => Some(-1),
+        _ => field.parse::<f64>().ok().filter(|v| *v != 0.0).map(|v| if v > 0.0 { 1 } else { -1 }),
+    }
+}
+
+/// Load `from to [sign]` lines; edges without a sign are positive, so plain edge lists load
+/// as all-positive graphs. Like `load_graph`, comments, self-loops and repeated edges (the
+/// first sign wins) are skipped.
+pub fn load_signed_graph<P: AsRef<Path>>(path: P) -> io::Result<SignedGraph> {
+    let mut graph = SignedGraph::new_undirected();
+    let mut node_map = HashMap::new();
+    let mut seen = HashSet::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let Some(fields) = line_fields(&line, false) else {
+            continue;
+        };
+        let fields: Vec<&str> = fields.collect();
+        let sign = match fields.get(2) {
+            Some(field) => parse_sign(field),
+            None => Some(1),
+        };
+        let (Some(from), Some(to), Some(sign)) = (
+            fields.first().and_then(|f| f.parse::<usize>().ok()),
+            fields.get(1).and_then(|f| f.parse::<usize>().ok()),
+            sign,
+        ) else {
+            continue;
+        };
+        if from == to || !seen.insert((from.min(to), from.max(to))) {
+            continue;
+        }
+        let from_index = *node_map.entry(from).or_insert_with(|| graph.add_node(from));
+        let to_index = *node_map.entry(to).or_insert_with(|| graph.add_node(to));
+        graph.add_edge(from_index, to_index, sign);
+    }
+    Ok(graph)
+}
+
+/// Triangles counted by their number of negative edges; triangles with zero or two
+/// negative edges are balanced in the sense of Heider and Cartwright–Harary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TriadCensus {
+    pub by_negative_edges: [usize; 4],
+}
+
+impl TriadCensus {
+    pub fn total(&self) -> usize {
+        self.by_negative_edges.iter().sum()
+    }
+
+    /// Fraction of triangles that are balanced; one for a graph without triangles.
+    pub fn balance_ratio(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 1.0;
+        }
+        (self.by_negative_edges[0] + self.by_negative_edges[2]) as f64 / total as f64
+    }
+}
+
+pub fn triad_census(graph: &SignedGraph) -> TriadCensus {
+    let mut census = TriadCensus::default();
+    for_each_triangle(graph, |a, b, c| {
+        let negative = [a, b, c].iter().filter(|&&sign| sign < 0).count();
+        census.by_negative_edges[negative] += 1;
+    });
+    census
+}
+
+/// Local signed clustering coefficient (Kunegis et al., 2009): the sum of the sign
+/// products of the triangles through each author over the number of pairs of their
+/// collaborators. It lies in [-1, 1] and reduces to the usual clustering coefficient when
+/// all edges are positive; authors with fewer than two collaborators get zero.
+pub fn signed_clustering(graph: &SignedGraph) -> HashMap<usize, f64> {
+    let mut products = vec![0i64; graph.node_count()];
+    let signs = edge_signs(graph);
+    for node in graph.node_indices() {
+        let neighbours: Vec<NodeIndex> = graph.neighbors(node).collect();
+        for (i, &j) in neighbours.iter().enumerate() {
+            for &k in &neighbours[i + 1..] {
+                if let Some(&jk) = signs.get(&key(j, k)) {
+                    products[node.index()] += (signs[&key(node, j)] * jk * signs[&key(node, k)]) as i64;
+                }
+            }
+        }
+    }
+    graph
+        .node_indices()
+        .map(|node| {
+            let degree = graph.neighbors(node).count() as i64;
+            let pairs = degree * (degree - 1) / 2;
+            let value = if pairs == 0 { 0.0 } else { products[node.index()] as f64 / pairs as f64 };
+            (graph[node], value)
+        })
+        .collect()
+}
+
+/// Global signed clustering coefficient: three times the summed sign products of all
+/// triangles over the number of connected triples.
+pub fn global_signed_clustering(graph: &SignedGraph) -> f64 {
+    let mut total = 0i64;
+    for_each_triangle(graph, |a, b, c| total += (a * b * c) as i64);
+    let triples: usize = graph
+        .node_indices()
+        .map(|node| {
+            let degree = graph.neighbors(node).count();
+            degree * degree.saturating_sub(1) / 2
+        })
+        .sum();
+    if triples == 0 {
+        return 0.0;
+    }
+    3.0 * total as f64 / triples as f64
+}
+
+fn key(a: NodeIndex, b: NodeIndex) -> (usize, usize) {
+    (a.index().min(b.index()), a.index().max(b.index()))
+}
+
+fn edge_signs(graph: &SignedGraph) -> HashMap<(usize, usize), i8> {
+    graph
+        .edge_references()
+        .map(|edge| (key(edge.source(), edge.target()), *edge.weight()))
+        .collect()
+}
+
+/// Call `visit` with the three edge signs of every triangle, each triangle once.
+fn for_each_triangle<F: FnMut(i8, i8, i8)>(graph: &SignedGraph, mut visit: F) {
+    let signs = edge_signs(graph);
+    for a in graph.node_indices() {
+        let higher: Vec<NodeIndex> = graph.neighbors(a).filter(|&b| b > a).collect();
+        for (i, &b) in higher.iter().enumerate() {
+            for &c in &higher[i + 1..] {
+                if let Some(&bc) = signs.get(&key(b, c)) {
+                    visit(signs[&key(a, b)], signs[&key(a, c)], bc);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Triangle 0-1-2 with one negative edge, triangle 1-2-3 with two, and a pendant 3-4.
+    fn two_triangles() -> SignedGraph {
+        let mut graph = SignedGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for &(a, b, sign) in &[(0, 1, 1), (0, 2, -1), (1, 2, 1), (1, 3, -1), (2, 3, -1), (3, 4, 1)] {
+            graph.add_edge(nodes[a], nodes[b], sign);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_triad_census_and_balance() {
+        let census = triad_census(&two_triangles());
+        assert_eq!(census.by_negative_edges, [0, 1, 1, 0]);
+        assert_eq!(census.balance_ratio(), 0.5);
+    }
+
+    #[test]
+    fn test_signed_clustering() {
+        let graph = two_triangles();
+        let clustering = signed_clustering(&graph);
+        // Author 1 has 3 collaborator pairs; triangles 0-1-2 (product -1) and 1-2-3 (+1).
+        assert_eq!(clustering[&1], 0.0);
+        assert_eq!(clustering[&0], -1.0);
+        // Author 3: pairs (1,2), (1,4), (2,4); only 1-2-3 closes, with product +1.
+        assert!((clustering[&3] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(clustering[&4], 0.0);
+        // Triples: 1 + 3 + 3 + 3 + 0 = 10; triangle products sum to 0.
+        assert_eq!(global_signed_clustering(&graph), 0.0);
+    }
+
+    #[test]
+    fn test_load_signed_graph() {
+        let path = "signed_test.txt";
+        std::fs::write(path, "# from to sign\n1 2 +\n2 3 -1\n3 1\n1 2 -\n4 4 +\n").unwrap();
+        let graph = load_signed_graph(path).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        let mut signs: Vec<i8> = graph.edge_weights().copied().collect();
+        signs.sort_unstable();
+        assert_eq!(signs, vec![-1, 1, 1]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
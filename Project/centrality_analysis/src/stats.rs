@@ -0,0 +1,504 @@
+use crate::anf::{exact_neighbourhood_function, hyper_anf};
+use crate::{largest_component, strength, AuthorGraph, WeightedAuthorGraph};
+use petgraph::algo::connected_components;
+use petgraph::visit::EdgeRef;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Graphs up to this many authors get exact distance statistics by default.
+pub const EXACT_LIMIT: usize = 50_000;
+
+/// HyperLogLog registers (log2) used for estimated distance statistics.
+const ANF_LOG2_REGISTERS: u32 = 8;
+
+/// Dataset summary in the layout of the SNAP dataset pages.
+#[derive(Debug, Clone)]
+pub struct GraphStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub components: usize,
+    pub largest_component_nodes: usize,
+    pub largest_component_edges: usize,
+    pub triangles: usize,
+    /// Mean local clustering coefficient, counting authors with fewer than two
+    /// collaborators as zero.
+    pub average_clustering: f64,
+    /// Global clustering: the fraction of connected triples that are closed.
+    pub transitivity: f64,
+    /// Pearson correlation of the degrees at the two ends of each collaboration.
+    pub assortativity: f64,
+    /// Longest shortest path; a lower bound when estimated.
+    pub diameter: usize,
+    /// Interpolated 90th percentile of the pairwise distances.
+    pub effective_diameter: f64,
+    /// Whether the distance statistics come from exact BFS rather than HyperANF.
+    pub exact: bool,
+}
+
+/// Summary of the weights of a weighted collaboration graph, to print beside [`GraphStats`].
+#[derive(Debug, Clone)]
+pub struct WeightedStats {
+    pub min_strength: f64,
+    pub mean_strength: f64,
+    pub max_strength: f64,
+    /// Pearson correlation of each author's strength with their degree.
+    pub strength_degree_correlation: f64,
+    /// Pearson correlation of the strengths at the two ends of each collaboration.
+    pub strength_assortativity: f64,
+    pub barrat_clustering: f64,
+    pub onnela_clustering: f64,
+}
+
+/// Summarize the weights of the graph; the averages are over all authors.
+pub fn weighted_stats(graph: &WeightedAuthorGraph) -> WeightedStats {
+    let strengths = strength(graph);
+    let n = graph.node_count().max(1) as f64;
+    let average = |values: HashMap<usize, f64>| values.values().sum::<f64>() / n;
+    WeightedStats {
+        min_strength: strengths.values().copied().reduce(f64::min).unwrap_or(0.0),
+        mean_strength: strengths.values().sum::<f64>() / n,
+        max_strength: strengths.values().copied().reduce(f64::max).unwrap_or(0.0),
+        strength_degree_correlation: strength_degree_correlation(graph),
+        strength_assortativity: strength_assortativity(graph),
+        barrat_clustering: average(weighted_clustering(graph, WeightedClustering::Barrat)),
+        onnela_clustering: average(weighted_clustering(graph, WeightedClustering::Onnela)),
+    }
+}
+
+/// Summarize the graph. Distance statistics use a BFS from every author when `exact` is
+/// set, and HyperANF (seeded by `seed`) otherwise.
+pub fn graph_stats(graph: &AuthorGraph, exact: bool, seed: u64) -> GraphStats {
+    let component = largest_component(graph);
+    let mut in_component = vec![false; graph.node_count()];
+    for node in &component {
+        in_component[node.index()] = true;
+    }
+    let largest_component_edges = graph
+        .edge_references()
+        .filter(|edge| in_component[edge.source().index()])
+        .count();
+
+    let triangles = triangles_per_author(graph);
+    let clustering: f64 = local_clustering(graph).values().sum();
+
+    let function = if exact {
+        exact_neighbourhood_function(graph)
+    } else {
+        hyper_anf(graph, ANF_LOG2_REGISTERS, seed)
+    };
+    GraphStats {
+        nodes: graph.node_count(),
+        edges: graph.edge_count(),
+        components: connected_components(graph),
+        largest_component_nodes: component.len(),
+        largest_component_edges,
+        triangles: triangles.iter().sum::<usize>() / 3,
+        average_clustering: clustering / graph.node_count().max(1) as f64,
+        transitivity: transitivity(graph),
+        assortativity: degree_assortativity(graph),
+        diameter: function.pairs.len().saturating_sub(1),
+        effective_diameter: function.effective_diameter(0.9),
+        exact,
+    }
+}
+
+/// Number of triangles through each author, indexed by `NodeIndex::index()`.
+///
+/// Authors are ranked by degree (ties by index) and every collaboration is oriented from
+/// the lower-ranked to the higher-ranked author, so each triangle is found exactly once,
+/// from its lowest-ranked corner, by merging the sorted forward lists of an edge's two
+/// ends. No author has more than O(sqrt(m)) forward neighbours, which bounds the work by
+/// O(m^1.5) even when a few authors have thousands of collaborators.
+pub fn triangles_per_author(graph: &AuthorGraph) -> Vec<usize> {
+    let n = graph.node_count();
+    let degree: Vec<usize> = graph.node_indices().map(|node| graph.neighbors(node).count()).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_unstable_by_key(|&node| (degree[node], node));
+    let mut rank = vec![0; n];
+    for (position, &node) in order.iter().enumerate() {
+        rank[node] = position;
+    }
+    // Forward neighbours of each author by rank, sorted and without repeats.
+    let forward: Vec<Vec<usize>> = graph
+        .node_indices()
+        .map(|node| {
+            let own = rank[node.index()];
+            let mut later: Vec<usize> = graph.neighbors(node).map(|other| rank[other.index()]).filter(|&r| r > own).collect();
+            later.sort_unstable();
+            later.dedup();
+            later
+        })
+        .collect();
+
+    let mut counts = vec![0; n];
+    for a in 0..n {
+        let first = &forward[a];
+        for &b in first {
+            let second = &forward[order[b]];
+            let (mut i, mut j) = (0, 0);
+            while i < first.len() && j < second.len() {
+                match first[i].cmp(&second[j]) {
+                    std::cmp::Ordering::Less => i += 1,
+                    std::cmp::Ordering::Greater => j += 1,
+                    std::cmp::Ordering::Equal => {
+                        counts[a] += 1;
+                        counts[order[b]] += 1;
+                        counts[order[first[i]]] += 1;
+                        i += 1;
+                        j += 1;
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Total number of triangles in the graph.
+pub fn triangle_count(graph: &AuthorGraph) -> usize {
+    triangles_per_author(graph).iter().sum::<usize>() / 3
+}
+
+/// Local clustering coefficient of every author: the fraction of pairs of their
+/// collaborators who have also collaborated, zero for authors with fewer than two.
+pub fn local_clustering(graph: &AuthorGraph) -> HashMap<usize, f64> {
+    let triangles = triangles_per_author(graph);
+    graph
+        .node_indices()
+        .map(|node| {
+            let degree = graph.neighbors(node).count();
+            let clustering = if degree < 2 {
+                0.0
+            } else {
+                2.0 * triangles[node.index()] as f64 / (degree * (degree - 1)) as f64
+            };
+            (graph[node], clustering)
+        })
+        .collect()
+}
+
+/// Which weighted generalization of the local clustering coefficient to compute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WeightedClustering {
+    /// Barrat et al. (2004): closed pairs of collaborators count by the mean weight of the
+    /// author's two edges to them, over the author's strength times (degree - 1).
+    #[default]
+    Barrat,
+    /// Onnela et al. (2005): closed pairs count by the geometric mean of the three weights
+    /// of their triangle, each divided by the largest weight, as networkx's `clustering`.
+    Onnela,
+}
+
+impl FromStr for WeightedClustering {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<WeightedClustering, String> {
+        match name {
+            "barrat" => Ok(WeightedClustering::Barrat),
+            "onnela" => Ok(WeightedClustering::Onnela),
+            _ => Err(format!("unknown weighted clustering: {} (expected barrat or onnela)", name)),
+        }
+    }
+}
+
+/// Weighted local clustering coefficient of every author, zero for authors with fewer than
+/// two collaborators. Both variants equal [`local_clustering`] when all weights are equal.
+/// Parallel edges count as one edge weighing their sum, and self-loops are ignored.
+pub fn weighted_clustering(graph: &WeightedAuthorGraph, variant: WeightedClustering) -> HashMap<usize, f64> {
+    let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); graph.node_count()];
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b {
+            *weights[a].entry(b).or_insert(0.0) += *edge.weight();
+            *weights[b].entry(a).or_insert(0.0) += *edge.weight();
+        }
+    }
+    let max_weight = weights.iter().flat_map(|neighbours| neighbours.values().copied()).fold(0.0, f64::max);
+    graph
+        .node_indices()
+        .map(|node| {
+            let neighbours: Vec<(usize, f64)> = weights[node.index()].iter().map(|(&other, &weight)| (other, weight)).collect();
+            let degree = neighbours.len();
+            if degree < 2 {
+                return (graph[node], 0.0);
+            }
+            // Each closed pair once; both definitions sum over ordered pairs, hence the 2.
+            let mut closed = 0.0;
+            for (position, &(j, w_ij)) in neighbours.iter().enumerate() {
+                for &(h, w_ih) in &neighbours[position + 1..] {
+                    if let Some(&w_jh) = weights[j].get(&h) {
+                        closed += match variant {
+                            WeightedClustering::Barrat => (w_ij + w_ih) / 2.0,
+                            WeightedClustering::Onnela => (w_ij * w_ih * w_jh).cbrt() / max_weight,
+                        };
+                    }
+                }
+            }
+            let normalization = match variant {
+                WeightedClustering::Barrat => neighbours.iter().map(|&(_, weight)| weight).sum::<f64>() * (degree - 1) as f64,
+                WeightedClustering::Onnela => (degree * (degree - 1)) as f64,
+            };
+            (graph[node], 2.0 * closed / normalization)
+        })
+        .collect()
+}
+
+/// Transitivity (global clustering coefficient): three times the number of triangles
+/// over the number of connected triples, i.e. paths of two collaborations. Unlike the
+/// average of [`local_clustering`] it is dominated by the high-degree authors.
+pub fn transitivity(graph: &AuthorGraph) -> f64 {
+    let closed: usize = triangles_per_author(graph).iter().sum();
+    let triples: usize = graph
+        .node_indices()
+        .map(|node| {
+            let degree = graph.neighbors(node).count();
+            degree * degree.saturating_sub(1) / 2
+        })
+        .sum();
+    if triples == 0 {
+        0.0
+    } else {
+        closed as f64 / triples as f64
+    }
+}
+
+/// Degree assortativity (Newman): the Pearson correlation between the degrees of the
+/// two authors of a collaboration, over both orientations of every edge. Positive when
+/// prolific authors collaborate with each other, as in most co-authorship networks,
+/// negative when hubs collaborate mostly with occasional authors. Zero when every
+/// collaboration joins authors of the same degree, where the correlation is undefined.
+pub fn degree_assortativity(graph: &AuthorGraph) -> f64 {
+    let degree: Vec<f64> = graph.node_indices().map(|node| graph.neighbors(node).count() as f64).collect();
+    endpoint_correlation(graph.edge_references().map(|edge| (degree[edge.source().index()], degree[edge.target().index()])))
+}
+
+/// Weighted assortativity: [`degree_assortativity`] with strengths in place of degrees, as
+/// networkx's `degree_assortativity_coefficient(G, weight="weight")`. Positive when
+/// authors with many joint papers write with each other.
+pub fn strength_assortativity(graph: &WeightedAuthorGraph) -> f64 {
+    let strengths = strength_by_index(graph);
+    endpoint_correlation(graph.edge_references().map(|edge| (strengths[edge.source().index()], strengths[edge.target().index()])))
+}
+
+/// Pearson correlation of the values at the two ends of the given edges, over both
+/// orientations; zero where it is undefined.
+fn endpoint_correlation(ends: impl Iterator<Item = (f64, f64)>) -> f64 {
+    let (mut products, mut sums, mut squares) = (0.0, 0.0, 0.0);
+    let mut edges = 0.0;
+    for (a, b) in ends {
+        products += a * b;
+        sums += (a + b) / 2.0;
+        squares += (a * a + b * b) / 2.0;
+        edges += 1.0;
+    }
+    if edges == 0.0 {
+        return 0.0;
+    }
+    let mean = sums / edges;
+    let variance = squares / edges - mean * mean;
+    if variance <= f64::EPSILON * squares / edges {
+        return 0.0;
+    }
+    (products / edges - mean * mean) / variance
+}
+
+/// Pearson correlation across authors between strength and degree: how closely the
+/// number of joint papers follows the number of collaborators. Zero where undefined.
+pub fn strength_degree_correlation(graph: &WeightedAuthorGraph) -> f64 {
+    let strengths = strength_by_index(graph);
+    let degrees: Vec<f64> = graph.node_indices().map(|node| graph.edges(node).count() as f64).collect();
+    let n = graph.node_count() as f64;
+    if n == 0.0 {
+        return 0.0;
+    }
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / n;
+    let (mean_strength, mean_degree) = (mean(&strengths), mean(&degrees));
+    let (mut covariance, mut strength_variance, mut degree_variance) = (0.0, 0.0, 0.0);
+    for (s, k) in strengths.iter().zip(&degrees) {
+        covariance += (s - mean_strength) * (k - mean_degree);
+        strength_variance += (s - mean_strength).powi(2);
+        degree_variance += (k - mean_degree).powi(2);
+    }
+    if strength_variance == 0.0 || degree_variance == 0.0 {
+        return 0.0;
+    }
+    covariance / (strength_variance * degree_variance).sqrt()
+}
+
+/// Strength of every author, indexed by `NodeIndex::index()`.
+fn strength_by_index(graph: &WeightedAuthorGraph) -> Vec<f64> {
+    graph.node_indices().map(|node| graph.edges(node).map(|edge| *edge.weight()).sum()).collect()
+}
+
+/// Number of authors having each strength, ordered by strength.
+pub fn strength_distribution(graph: &WeightedAuthorGraph) -> Vec<(f64, usize)> {
+    let mut strengths = strength_by_index(graph);
+    strengths.sort_unstable_by(f64::total_cmp);
+    let mut distribution: Vec<(f64, usize)> = Vec::new();
+    for value in strengths {
+        match distribution.last_mut() {
+            Some((last, count)) if *last == value => *count += 1,
+            _ => distribution.push((value, 1)),
+        }
+    }
+    distribution
+}
+
+/// Authors whose strength lies in `lower..upper`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrengthBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub authors: usize,
+}
+
+impl StrengthBin {
+    /// Authors per unit of strength, comparable across bins of different widths.
+    pub fn density(&self) -> f64 {
+        self.authors as f64 / (self.upper - self.lower)
+    }
+
+    /// Geometric centre of the bin, where it sits on a log-log plot.
+    pub fn centre(&self) -> f64 {
+        (self.lower * self.upper).sqrt()
+    }
+}
+
+/// Strength distribution in logarithmic bins, `bins_per_decade` to each factor of ten,
+/// with edges at powers of 10^(1 / bins_per_decade), like [`crate::log_binned_degrees`]
+/// but for strengths that need not be whole. Authors without collaborators and empty
+/// bins are left out.
+pub fn log_binned_strengths(graph: &WeightedAuthorGraph, bins_per_decade: usize) -> Vec<StrengthBin> {
+    let bins_per_decade = bins_per_decade.max(1) as f64;
+    let edge = |step: f64| 10f64.powf(step / bins_per_decade);
+    let mut bins: Vec<StrengthBin> = Vec::new();
+    for (value, count) in strength_distribution(graph).into_iter().filter(|&(value, _)| value > 0.0) {
+        let step = (value.log10() * bins_per_decade).floor();
+        // Guard against log10 rounding a value onto the wrong side of an edge.
+        let step = if edge(step) > value { step - 1.0 } else if edge(step + 1.0) <= value { step + 1.0 } else { step };
+        match bins.last_mut() {
+            Some(bin) if bin.lower == edge(step) => bin.authors += count,
+            _ => bins.push(StrengthBin { lower: edge(step), upper: edge(step + 1.0), authors: count }),
+        }
+    }
+    bins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unweighted;
+
+    #[test]
+    fn test_graph_stats() {
+        // A 5-path plus a separate edge.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..7).map(|author| graph.add_node(author)).collect();
+        for i in 0..4 {
+            graph.add_edge(nodes[i], nodes[i + 1], ());
+        }
+        graph.add_edge(nodes[5], nodes[6], ());
+
+        let stats = graph_stats(&graph, true, 0);
+        assert_eq!((stats.nodes, stats.edges, stats.components), (7, 5, 2));
+        assert_eq!((stats.largest_component_nodes, stats.largest_component_edges), (5, 4));
+        assert_eq!(stats.diameter, 4);
+        // Pairs within distance t: 7, 17, 23, 27, 29; 90% of 29 lies between t = 2 and 3.
+        assert!((stats.effective_diameter - (2.0 + (26.1 - 23.0) / 4.0)).abs() < 1e-9);
+        assert_eq!((stats.triangles, stats.average_clustering), (0, 0.0));
+        // The path ends meet degree-2 authors, but the separate edge pairs two ends (networkx: 1/6).
+        assert!((stats.assortativity - 1.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_triangles_and_clustering() {
+        // Two triangles sharing the edge 1-2, plus the pendant author 4.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for &(a, b) in &[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 4)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        assert_eq!(triangles_per_author(&graph), vec![1, 2, 2, 1, 0]);
+        let stats = graph_stats(&graph, true, 0);
+        assert_eq!(stats.triangles, 2);
+        // Local clustering 1, 2/3, 2/3, 1/3 and 0.
+        assert!((stats.average_clustering - (1.0 + 2.0 / 3.0 + 2.0 / 3.0 + 1.0 / 3.0) / 5.0).abs() < 1e-12);
+        assert_eq!(local_clustering(&graph)[&3], 1.0 / 3.0);
+        // 2 triangles and 1 + 3 + 3 + 3 = 10 connected triples.
+        assert!((stats.transitivity - 0.6).abs() < 1e-12);
+        // The pendant author hangs off a degree-3 author: slightly disassortative.
+        assert!((stats.assortativity + 2.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_clustering() {
+        // The graph of test_triangles_and_clustering with weights on its edges.
+        let mut graph = WeightedAuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for &(a, b, weight) in &[(0, 1, 1.0), (0, 2, 2.0), (1, 2, 3.0), (1, 3, 1.0), (2, 3, 4.0), (3, 4, 2.0)] {
+            graph.add_edge(nodes[a], nodes[b], weight);
+        }
+        // Barrat by its definition; Onnela by networkx's clustering(G, weight="weight").
+        let barrat = [1.0, 0.8, 0.6666666666666666, 0.35714285714285715, 0.0];
+        let onnela = [0.4542801482080349, 0.3422124231615669, 0.3422124231615669, 0.19078570709222198, 0.0];
+        for (variant, expected) in [(WeightedClustering::Barrat, barrat), (WeightedClustering::Onnela, onnela)] {
+            let clustering = weighted_clustering(&graph, variant);
+            for author in 0..5 {
+                assert!((clustering[&author] - expected[author]).abs() < 1e-12, "{:?} {}: {}", variant, author, clustering[&author]);
+            }
+        }
+        // Equal weights give the unweighted coefficients.
+        let unit = graph.map(|_, &author| author, |_, _| 1.0);
+        let plain = local_clustering(&unweighted(&unit));
+        for variant in [WeightedClustering::Barrat, WeightedClustering::Onnela] {
+            let clustering = weighted_clustering(&unit, variant);
+            assert!((0..5).all(|author| (clustering[&author] - plain[&author]).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_strength_statistics() {
+        let mut graph = WeightedAuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|author| graph.add_node(author)).collect();
+        for &(a, b, weight) in &[(0, 1, 1.0), (0, 2, 2.0), (1, 2, 3.0), (1, 3, 1.0), (2, 3, 4.0), (3, 4, 2.0)] {
+            graph.add_edge(nodes[a], nodes[b], weight);
+        }
+        // Strengths 3, 5, 9, 7 and 2.
+        assert_eq!(strength_distribution(&graph), vec![(2.0, 1), (3.0, 1), (5.0, 1), (7.0, 1), (9.0, 1)]);
+        // networkx: degree_assortativity_coefficient(G, weight="weight"), and the Pearson
+        // correlation of G.degree(weight="weight") with G.degree().
+        assert!((strength_assortativity(&graph) + 0.33001245330012463).abs() < 1e-12);
+        assert!((strength_degree_correlation(&graph) - 0.8394339701512575).abs() < 1e-12);
+        let stats = weighted_stats(&graph);
+        assert_eq!((stats.min_strength, stats.mean_strength, stats.max_strength), (2.0, 5.2, 9.0));
+
+        // One bin per decade: [1, 10) holds every author, two per decade split it at sqrt(10).
+        let bins = log_binned_strengths(&graph, 1);
+        assert_eq!(bins, vec![StrengthBin { lower: 1.0, upper: 10.0, authors: 5 }]);
+        let halves: Vec<usize> = log_binned_strengths(&graph, 2).iter().map(|bin| bin.authors).collect();
+        assert_eq!(halves, vec![2, 3]);
+
+        // With equal weights strengths are degrees.
+        let unit = graph.map(|_, &author| author, |_, _| 1.0);
+        assert!((strength_assortativity(&unit) - degree_assortativity(&unweighted(&unit))).abs() < 1e-12);
+        assert!((strength_degree_correlation(&unit) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_triangles_around_a_hub() {
+        // Hub 0 joined to a 6-cycle, with the chords 1-3 and 4-6 and a repeated edge: the
+        // hub is ranked last, so every triangle is found from a rim author.
+        let mut graph = AuthorGraph::new_undirected();
+        let nodes: Vec<_> = (0..7).map(|author| graph.add_node(author)).collect();
+        for i in 1..7 {
+            graph.add_edge(nodes[0], nodes[i], ());
+            graph.add_edge(nodes[i], nodes[i % 6 + 1], ());
+        }
+        for &(a, b) in &[(1, 3), (4, 6), (1, 2)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        // 6 triangles of the hub and a rim edge, 2 of a chord and the rim, 2 of a chord and the hub.
+        assert_eq!(triangles_per_author(&graph), vec![8, 4, 3, 4, 4, 3, 4]);
+        assert_eq!(triangle_count(&graph), 10);
+    }
+}
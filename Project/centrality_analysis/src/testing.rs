@@ -0,0 +1,185 @@
+use petgraph::algo::is_isomorphic;
+use petgraph::graph::Graph;
+use petgraph::visit::EdgeRef;
+use petgraph::EdgeType;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// How far a computed score may be from the expected one: at most
+/// `absolute + relative * |expected|`, as in numpy's `isclose`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Tolerance {
+    pub fn absolute(absolute: f64) -> Tolerance {
+        Tolerance { absolute, relative: 0.0 }
+    }
+
+    pub fn relative(relative: f64) -> Tolerance {
+        Tolerance { absolute: 0.0, relative }
+    }
+
+    /// Whether `actual` is within the tolerance of `expected`. NaN, which some measures
+    /// return where they are undefined, is only close to NaN.
+    pub fn accepts(&self, actual: f64, expected: f64) -> bool {
+        if expected.is_nan() || actual.is_nan() {
+            return expected.is_nan() && actual.is_nan();
+        }
+        actual == expected || (actual - expected).abs() <= self.absolute + self.relative * expected.abs()
+    }
+}
+
+/// Panic unless `actual` scores exactly the authors of `expected`, each within
+/// `tolerance`. The message lists the missing and unexpected authors and the five worst
+/// mismatches, so a failing test shows how far off the measure is, not just that it is.
+pub fn assert_scores_close(actual: &HashMap<usize, f64>, expected: &HashMap<usize, f64>, tolerance: Tolerance) {
+    let missing: BTreeSet<usize> = expected.keys().filter(|author| !actual.contains_key(author)).copied().collect();
+    let extra: BTreeSet<usize> = actual.keys().filter(|author| !expected.contains_key(author)).copied().collect();
+    let mut mismatches: Vec<(usize, f64, f64)> = expected
+        .iter()
+        .filter_map(|(&author, &value)| actual.get(&author).map(|&score| (author, score, value)))
+        .filter(|&(_, score, value)| !tolerance.accepts(score, value))
+        .collect();
+    if missing.is_empty() && extra.is_empty() && mismatches.is_empty() {
+        return;
+    }
+    mismatches.sort_by(|a, b| (b.1 - b.2).abs().total_cmp(&(a.1 - a.2).abs()).then(a.0.cmp(&b.0)));
+    let mut message = format!("scores differ beyond {:?}:", tolerance);
+    if !missing.is_empty() {
+        message += &format!(" missing authors {:?};", missing);
+    }
+    if !extra.is_empty() {
+        message += &format!(" unexpected authors {:?};", extra);
+    }
+    if !mismatches.is_empty() {
+        message += &format!(" {} scores off, worst", mismatches.len());
+        for (author, score, value) in mismatches.iter().take(5) {
+            message += &format!(" {}: {} instead of {},", author, score, value);
+        }
+        message.pop();
+    }
+    panic!("{}", message.trim_end_matches(';'));
+}
+
+/// What one graph has that another lacks, by author id: the authors, and the edges with
+/// how many more times they occur (more than once only in graphs with parallel edges).
+/// Undirected edges are keyed with the smaller id first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphDifference {
+    pub authors_only_in_first: BTreeSet<usize>,
+    pub authors_only_in_second: BTreeSet<usize>,
+    pub edges_only_in_first: BTreeMap<(usize, usize), usize>,
+    pub edges_only_in_second: BTreeMap<(usize, usize), usize>,
+}
+
+impl GraphDifference {
+    pub fn is_empty(&self) -> bool {
+        *self == GraphDifference::default()
+    }
+}
+
+/// Compare two graphs by author ids, ignoring node indices, edge order and weights.
+pub fn graph_difference<N, E, Ty: EdgeType>(first: &Graph<usize, N, Ty>, second: &Graph<usize, E, Ty>) -> GraphDifference {
+    type Counts = BTreeMap<(usize, usize), usize>;
+    fn authors<W, Ty: EdgeType>(graph: &Graph<usize, W, Ty>) -> BTreeSet<usize> {
+        graph.node_weights().copied().collect()
+    }
+    fn edges<W, Ty: EdgeType>(graph: &Graph<usize, W, Ty>) -> Counts {
+        let mut counts = BTreeMap::new();
+        for edge in graph.edge_references() {
+            let (a, b) = (graph[edge.source()], graph[edge.target()]);
+            let key = if Ty::is_directed() { (a, b) } else { (a.min(b), a.max(b)) };
+            *counts.entry(key).or_insert(0) += 1;
+        }
+        counts
+    }
+    fn surplus(of: &Counts, over: &Counts) -> Counts {
+        of.iter()
+            .map(|(&key, &count)| (key, count.saturating_sub(over.get(&key).copied().unwrap_or(0))))
+            .filter(|&(_, surplus)| surplus > 0)
+            .collect()
+    }
+    let (first_authors, second_authors) = (authors(first), authors(second));
+    let (first_edges, second_edges) = (edges(first), edges(second));
+    GraphDifference {
+        authors_only_in_first: first_authors.difference(&second_authors).copied().collect(),
+        authors_only_in_second: second_authors.difference(&first_authors).copied().collect(),
+        edges_only_in_first: surplus(&first_edges, &second_edges),
+        edges_only_in_second: surplus(&second_edges, &first_edges),
+    }
+}
+
+/// Whether two graphs have the same authors and the same edges between them, however
+/// they were built; `isomorphic` also ignores the ids.
+pub fn same_graph<N, E, Ty: EdgeType>(first: &Graph<usize, N, Ty>, second: &Graph<usize, E, Ty>) -> bool {
+    graph_difference(first, second).is_empty()
+}
+
+/// Whether some relabelling of the authors turns one graph into the other (VF2, as in
+/// networkx's `is_isomorphic`). Exponential in the worst case, but instant on the graphs
+/// tests are written against.
+pub fn isomorphic<N, E, Ty: EdgeType>(first: &Graph<usize, N, Ty>, second: &Graph<usize, E, Ty>) -> bool {
+    is_isomorphic(first, second)
+}
+
+/// Panic unless `same_graph` holds, listing what differs.
+pub fn assert_same_graph<N, E, Ty: EdgeType>(first: &Graph<usize, N, Ty>, second: &Graph<usize, E, Ty>) {
+    let difference = graph_difference(first, second);
+    assert!(difference.is_empty(), "graphs differ: {:?}", difference);
+}
+
+/// Panic unless `isomorphic` holds.
+pub fn assert_isomorphic<N, E, Ty: EdgeType>(first: &Graph<usize, N, Ty>, second: &Graph<usize, E, Ty>) {
+    assert!(
+        isomorphic(first, second),
+        "graphs are not isomorphic: {} nodes and {} edges against {} and {}",
+        first.node_count(),
+        first.edge_count(),
+        second.node_count(),
+        second.edge_count()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{path, star};
+    use crate::{load_graph_from_reader, AuthorGraph};
+
+    fn graph(input: &str) -> AuthorGraph {
+        load_graph_from_reader(input.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_graph_equality() {
+        // Same collaborations in another order and direction.
+        let original = graph("1 2\n2 3\n3 4\n");
+        assert_same_graph(&original, &graph("4 3\n2 1\n3 2\n"));
+        // The path 0-1-2-3 is the same shape, but on other ids.
+        assert!(!same_graph(&original, &path(4)));
+        assert_isomorphic(&original, &path(4));
+        assert!(!isomorphic(&path(4), &star(3)));
+
+        let difference = graph_difference(&original, &graph("1 2\n2 3\n3 5\n"));
+        assert_eq!(difference.authors_only_in_first, BTreeSet::from([4]));
+        assert_eq!(difference.edges_only_in_second, BTreeMap::from([((3, 5), 1)]));
+    }
+
+    #[test]
+    fn test_assert_scores_close() {
+        let expected: HashMap<usize, f64> = [(1, 0.5), (2, 100.0), (3, f64::NAN)].into_iter().collect();
+        let actual: HashMap<usize, f64> = [(1, 0.5 + 1e-10), (2, 100.001), (3, f64::NAN)].into_iter().collect();
+        assert_scores_close(&actual, &expected, Tolerance { absolute: 1e-9, relative: 1e-4 });
+        assert!(!Tolerance::absolute(1e-9).accepts(100.001, 100.0));
+        assert!(!Tolerance::relative(0.1).accepts(0.0, f64::NAN));
+
+        let off: HashMap<usize, f64> = [(1, 0.6), (2, 100.0), (4, 1.0)].into_iter().collect();
+        let message = *std::panic::catch_unwind(|| assert_scores_close(&off, &expected, Tolerance::absolute(1e-9)))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert!(message.contains("missing authors {3}; unexpected authors {4}; 1 scores off, worst 1: 0.6 instead of 0.5"));
+    }
+}
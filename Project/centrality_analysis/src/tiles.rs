@@ -0,0 +1,161 @@
+use crate::community::Partition;
+use crate::layout::Layout;
+use crate::plots::{CommunityPalette, Viewport};
+use crate::AuthorGraph;
+use petgraph::visit::EdgeRef;
+use plotters::prelude::*;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Viewport of tile `(x, y)` at `zoom`: the full layout is split into `2^zoom` tiles per
+/// side, counted from the top-left corner as in web map tiles.
+pub fn tile_viewport(zoom: u32, x: u32, y: u32) -> Viewport {
+    let full = Viewport::FULL;
+    let tiles = (1u32 << zoom) as f64;
+    let width = (full.x.1 - full.x.0) / tiles;
+    let height = (full.y.1 - full.y.0) / tiles;
+    let left = full.x.0 + x as f64 * width;
+    let top = full.y.1 - y as f64 * height;
+    Viewport { x: (left, left + width), y: (top - height, top) }
+}
+
+/// Indices of the tiles along one axis, the `i`-th covering `[start + i * step, start +
+/// (i + 1) * step)`, that overlap `[low, high]`.
+fn tile_range(low: f64, high: f64, start: f64, step: f64, tiles: u32) -> std::ops::Range<u32> {
+    if high < start {
+        return 0..0;
+    }
+    let first = ((low - start) / step).floor().max(0.0) as u32;
+    let last = (((high - start) / step).floor().max(0.0) as u32).min(tiles - 1);
+    first.min(tiles)..last + 1
+}
+
+/// Render the layout as a tile pyramid `dir/{zoom}/{x}/{y}.png` for zoom levels
+/// `0..=max_zoom`, plus `dir/index.html`, a Leaflet viewer that pans and zooms over it.
+/// Each level is drawn from edge and node lists bucketed by tile, so no level needs the
+/// full-resolution image in memory. Returns the number of tiles written.
+pub fn render_tiles<P: AsRef<Path>>(
+    graph: &AuthorGraph,
+    layout: &Layout,
+    communities: Option<&Partition>,
+    max_zoom: u32,
+    tile_size: u32,
+    dir: P,
+) -> Result<usize, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    let palette = CommunityPalette::new(communities);
+    let full = Viewport::FULL;
+    let mut written = 0;
+    for zoom in 0..=max_zoom {
+        let tiles = 1u32 << zoom;
+        let step_x = (full.x.1 - full.x.0) / tiles as f64;
+        let step_y = (full.y.1 - full.y.0) / tiles as f64;
+        let index = |x: u32, y: u32| (y * tiles + x) as usize;
+        let mut edges = vec![Vec::new(); (tiles * tiles) as usize];
+        let mut nodes = vec![Vec::new(); (tiles * tiles) as usize];
+        for edge in graph.edge_references() {
+            let (a, b) = (layout[edge.source().index()], layout[edge.target().index()]);
+            // Tile rows count downwards from the top edge of the layout.
+            for y in tile_range(full.y.1 - a.1.max(b.1), full.y.1 - a.1.min(b.1), 0.0, step_y, tiles) {
+                for x in tile_range(a.0.min(b.0), a.0.max(b.0), full.x.0, step_x, tiles) {
+                    edges[index(x, y)].push((a, b));
+                }
+            }
+        }
+        // Nodes near a tile border are drawn on both sides so their circles are not cut.
+        let radius = (zoom + 2).min(8);
+        let pad_x = step_x * radius as f64 / tile_size as f64;
+        let pad_y = step_y * radius as f64 / tile_size as f64;
+        for node in graph.node_indices() {
+            let (px, py) = layout[node.index()];
+            for y in tile_range(full.y.1 - py - pad_y, full.y.1 - py + pad_y, 0.0, step_y, tiles) {
+                for x in tile_range(px - pad_x, px + pad_x, full.x.0, step_x, tiles) {
+                    nodes[index(x, y)].push(((px, py), palette.colour(graph[node])));
+                }
+            }
+        }
+
+        for x in 0..tiles {
+            let column = dir.join(zoom.to_string()).join(x.to_string());
+            fs::create_dir_all(&column)?;
+            for y in 0..tiles {
+                let viewport = tile_viewport(zoom, x, y);
+                let path = column.join(format!("{}.png", y));
+                let root = BitMapBackend::new(&path, (tile_size, tile_size)).into_drawing_area();
+                root.fill(&WHITE)?;
+                let mut chart = ChartBuilder::on(&root)
+                    .build_cartesian_2d(viewport.x.0..viewport.x.1, viewport.y.0..viewport.y.1)?;
+                chart.draw_series(edges[index(x, y)].iter().filter_map(|&(a, b)| {
+                    let (a, b) = viewport.clip(a, b)?;
+                    Some(PathElement::new(vec![a, b], BLACK.mix(0.15)))
+                }))?;
+                chart.draw_series(
+                    nodes[index(x, y)]
+                        .iter()
+                        .map(|&(position, colour)| Circle::new(position, radius as i32, colour.filled())),
+                )?;
+                root.present()?;
+                written += 1;
+            }
+        }
+    }
+    fs::write(dir.join("index.html"), viewer_html(max_zoom, tile_size))?;
+    Ok(written)
+}
+
+/// A standalone page showing the pyramid with Leaflet's flat (non-geographic) projection.
+fn viewer_html(max_zoom: u32, tile_size: u32) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Collaboration Network</title>
+<link rel="stylesheet" href="https://unpkg.com/leaflet@1.9.4/dist/leaflet.css">
+<script src="https://unpkg.com/leaflet@1.9.4/dist/leaflet.js"></script>
+<style>html, body, #map {{ height: 100%; margin: 0; background: #fff; }}</style>
+</head>
+<body>
+<div id="map"></div>
+<script>
+var size = {tile_size};
+var bounds = [[-size, 0], [0, size]];
+var map = L.map("map", {{ crs: L.CRS.Simple, minZoom: 0, maxZoom: {max_zoom} + 2 }});
+L.tileLayer("{{z}}/{{x}}/{{y}}.png", {{ tileSize: size, noWrap: true, bounds: bounds, maxNativeZoom: {max_zoom} }}).addTo(map);
+map.fitBounds(bounds);
+</script>
+</body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::circular_layout;
+    use crate::load_graph_from_reader;
+
+    #[test]
+    fn test_tile_viewport() {
+        assert_eq!(tile_viewport(0, 0, 0), Viewport::FULL);
+        // At zoom 1 the top-left tile is the quadrant with negative x and positive y.
+        let top_left = tile_viewport(1, 0, 0);
+        assert!((top_left.x.0 + 1.1).abs() < 1e-12 && top_left.x.1.abs() < 1e-12);
+        assert!(top_left.y.0.abs() < 1e-12 && (top_left.y.1 - 1.1).abs() < 1e-12);
+        assert_eq!(tile_range(-1.1, 1.1, -1.1, 1.1, 2), 0..2);
+        assert_eq!(tile_range(0.2, 0.3, -1.1, 0.55, 4), 2..3);
+    }
+
+    #[test]
+    fn test_render_tiles() {
+        let graph = load_graph_from_reader("1 2\n2 3\n3 1\n3 4\n".as_bytes()).unwrap();
+        let dir = Path::new("tiles_test");
+        let written = render_tiles(&graph, &circular_layout(&graph), None, 1, 64, dir).unwrap();
+        assert_eq!(written, 1 + 4);
+        assert!(dir.join("1/1/0.png").exists());
+        assert!(fs::read_to_string(dir.join("index.html")).unwrap().contains("maxNativeZoom: 1"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+use crate::stats::GraphStats;
+use crate::{largest_component, line_fields, AuthorGraph};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Statistics published on the SNAP page of a dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub name: &'static str,
+    pub nodes: usize,
+    pub edges: usize,
+    pub largest_component_nodes: usize,
+    pub largest_component_edges: usize,
+    pub triangles: usize,
+    pub average_clustering: f64,
+    pub diameter: usize,
+    pub effective_diameter: f64,
+}
+
+/// SNAP's collaboration networks (https://snap.stanford.edu/data/#canets).
+pub const REFERENCES: [Reference; 5] = [
+    Reference {
+        name: "ca-GrQc",
+        nodes: 5242,
+        edges: 14496,
+        largest_component_nodes: 4158,
+        largest_component_edges: 13428,
+        triangles: 48260,
+        average_clustering: 0.5296,
+        diameter: 17,
+        effective_diameter: 7.6,
+    },
+    Reference {
+        name: "ca-HepTh",
+        nodes: 9877,
+        edges: 25998,
+        largest_component_nodes: 8638,
+        largest_component_edges: 24827,
+        triangles: 28339,
+        average_clustering: 0.4714,
+        diameter: 17,
+        effective_diameter: 7.4,
+    },
+    Reference {
+        name: "ca-HepPh",
+        nodes: 12008,
+        edges: 118521,
+        largest_component_nodes: 11204,
+        largest_component_edges: 117649,
+        triangles: 3358499,
+        average_clustering: 0.6115,
+        diameter: 13,
+        effective_diameter: 5.8,
+    },
+    Reference {
+        name: "ca-AstroPh",
+        nodes: 18772,
+        edges: 198110,
+        largest_component_nodes: 17903,
+        largest_component_edges: 197031,
+        triangles: 1351441,
+        average_clustering: 0.6306,
+        diameter: 14,
+        effective_diameter: 5.0,
+    },
+    Reference {
+        name: "ca-CondMat",
+        nodes: 23133,
+        edges: 93497,
+        largest_component_nodes: 21363,
+        largest_component_edges: 91342,
+        triangles: 173361,
+        average_clustering: 0.6334,
+        diameter: 14,
+        effective_diameter: 6.5,
+    },
+];
+
+/// Reference values for a dataset file, recognised by its name (`CA-GrQc.txt`,
+/// `ca-grqc.txt.gz`, ...).
+pub fn reference_for<P: AsRef<Path>>(path: P) -> Option<&'static Reference> {
+    let file_name = path.as_ref().file_name()?.to_string_lossy().to_lowercase();
+    let stem = file_name.trim_end_matches(".gz").trim_end_matches(".txt");
+    REFERENCES.iter().find(|reference| reference.name.to_lowercase() == stem)
+}
+
+/// One computed statistic next to its reference value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub statistic: &'static str,
+    pub computed: f64,
+    pub expected: f64,
+    /// Largest accepted absolute difference; SNAP rounds its published values.
+    pub tolerance: f64,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        (self.computed - self.expected).abs() <= self.tolerance
+    }
+}
+
+/// Authors with a self-loop in the edge list, which `load_graph` drops but SNAP counts.
+pub fn self_loop_authors<P: AsRef<Path>>(path: P) -> io::Result<HashSet<usize>> {
+    let mut authors = HashSet::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let Some(fields) = line_fields(&line, false) else {
+            continue;
+        };
+        let nodes: Vec<usize> = fields.filter_map(|x| x.parse().ok()).collect();
+        if let [from, to] = nodes[..] {
+            if from == to {
+                authors.insert(from);
+            }
+        }
+    }
+    Ok(authors)
+}
+
+/// Compare statistics of the loaded graph with the reference. The self-loops dropped by
+/// the loader are added back to node and edge counts, as SNAP keeps them; they do not
+/// affect triangles, clustering or distances.
+pub fn compare(
+    graph: &AuthorGraph,
+    stats: &GraphStats,
+    self_loops: &HashSet<usize>,
+    reference: &Reference,
+) -> Vec<Check> {
+    let authors: HashSet<usize> = graph.node_weights().copied().collect();
+    let component: HashSet<usize> = largest_component(graph).into_iter().map(|node| graph[node]).collect();
+    let loop_only = self_loops.iter().filter(|author| !authors.contains(author)).count();
+    let component_loops = self_loops.iter().filter(|author| component.contains(author)).count();
+    let nodes = stats.nodes + loop_only;
+    // Loop-only authors have no clustering, but count in the mean.
+    let clustering = stats.average_clustering * stats.nodes as f64 / nodes.max(1) as f64;
+
+    let count = |statistic, computed: usize, expected: usize| Check {
+        statistic,
+        computed: computed as f64,
+        expected: expected as f64,
+        tolerance: 0.0,
+    };
+    vec![
+        count("Nodes", nodes, reference.nodes),
+        count("Edges", stats.edges + self_loops.len(), reference.edges),
+        count("Nodes in largest WCC", stats.largest_component_nodes, reference.largest_component_nodes),
+        count(
+            "Edges in largest WCC",
+            stats.largest_component_edges + component_loops,
+            reference.largest_component_edges,
+        ),
+        count("Triangles", stats.triangles, reference.triangles),
+        Check {
+            statistic: "Average clustering coefficient",
+            computed: clustering,
+            expected: reference.average_clustering,
+            tolerance: 5e-4,
+        },
+        count("Diameter", stats.diameter, reference.diameter),
+        // SNAP estimates the effective diameter from sampled BFS runs.
+        Check {
+            statistic: "90-percentile effective diameter",
+            computed: stats.effective_diameter,
+            expected: reference.effective_diameter,
+            tolerance: 0.2,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_graph;
+    use crate::stats::graph_stats;
+
+    #[test]
+    fn test_reference_lookup() {
+        assert_eq!(reference_for("data/CA-GrQc.txt").unwrap().nodes, 5242);
+        assert_eq!(reference_for("ca-condmat.txt.gz").unwrap().name, "ca-CondMat");
+        assert!(reference_for("graph.txt").is_none());
+    }
+
+    #[test]
+    fn test_compare_restores_self_loops() {
+        let path = "verify_test.txt";
+        std::fs::write(path, "1\t2\n2\t3\n3\t1\n3\t3\n4\t4\n").unwrap();
+        let graph = load_graph(path).unwrap();
+        let loops = self_loop_authors(path).unwrap();
+        let reference = Reference {
+            name: "triangle",
+            nodes: 4,
+            edges: 5,
+            largest_component_nodes: 3,
+            largest_component_edges: 4,
+            triangles: 1,
+            average_clustering: 0.75,
+            diameter: 1,
+            effective_diameter: 0.85,
+        };
+        let checks = compare(&graph, &graph_stats(&graph, true, 0), &loops, &reference);
+        assert!(checks.iter().all(Check::passed), "{:?}", checks);
+        std::fs::remove_file(path).unwrap();
+    }
+}